@@ -29,6 +29,73 @@ pub fn acyclicity(graph: impl RandomAccessGraph, pl: &mut impl ProgressLog) -> b
     acyclic.is_continue()
 }
 
+/// Returns a cycle of the graph, if any.
+///
+/// A [depth-first visit](SeqPath) keeps track of the nodes on the visit
+/// path; when a back arc from `u` to `v` is found, `v` is on the path, and the
+/// nodes on the path from `v` to `u` form a cycle. The cycle is returned as
+/// the sequence of its nodes starting from `v`, so that each node has an arc
+/// to the next one and the last node has an arc to the first one. A loop is
+/// returned as a cycle made of a single node.
+///
+/// If the graph is acyclic, [`None`] is returned.
+///
+/// # Arguments
+/// * `graph`: the graph.
+/// * `pl`: A progress logger.
+///
+/// # Examples
+/// ```
+/// use dsi_progress_logger::no_logging;
+/// use webgraph::{labels::Left, prelude::VecGraph};
+/// use webgraph_algo::algo::find_cycle;
+///
+/// let graph = Left(VecGraph::from_arc_list([(0, 1), (1, 2), (2, 3), (3, 1)]));
+/// assert_eq!(find_cycle(&graph, no_logging![]), Some(vec![1, 2, 3]));
+///
+/// let graph = Left(VecGraph::from_arc_list([(0, 1), (1, 2)]));
+/// assert_eq!(find_cycle(&graph, no_logging![]), None);
+/// ```
+pub fn find_cycle(graph: impl RandomAccessGraph, pl: &mut impl ProgressLog) -> Option<Vec<usize>> {
+    let num_nodes = graph.num_nodes();
+    pl.item_name("node");
+    pl.expected_updates(Some(num_nodes));
+    pl.start("Looking for a cycle");
+
+    let mut visit = SeqPath::new(&graph);
+    // The nodes on the visit path
+    let mut path = Vec::new();
+
+    let cycle = visit.visit_all(
+        |event| {
+            match event {
+                EventPred::Previsit { curr, .. } => path.push(curr),
+                EventPred::Postvisit { .. } => {
+                    path.pop();
+                }
+                EventPred::Revisit {
+                    curr,
+                    on_stack: true,
+                    ..
+                } => {
+                    // The source of the back arc is the last node of the path
+                    let start = path
+                        .iter()
+                        .rposition(|&node| node == curr)
+                        .expect("the target of a back arc should be on the visit path");
+                    return Break(path[start..].to_vec());
+                }
+                _ => {}
+            }
+            Continue(())
+        },
+        pl,
+    );
+
+    pl.done();
+    cycle.break_value()
+}
+
 /// Trait providing an easy way to test a [`RandomAccessGraph`] for
 /// acyclicity.
 ///
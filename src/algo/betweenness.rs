@@ -0,0 +1,155 @@
+//! Algorithms used to compute the betweenness centrality of a graph's nodes.
+//!
+//! # Examples
+//! ```
+//! use dsi_progress_logger::no_logging;
+//! use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
+//! use webgraph_algo::{prelude::betweenness, threads};
+//!
+//! let graph = Left(VecGraph::from_arc_list([(0, 1), (1, 2), (2, 3)]));
+//!
+//! let betweenness = betweenness::brandes(&graph, &threads![], no_logging![]);
+//!
+//! assert_eq!(betweenness, vec![0.0, 2.0, 2.0, 0.0]);
+//! ```
+
+use dsi_progress_logger::ProgressLog;
+use rayon::ThreadPool;
+use std::{
+    collections::VecDeque,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use webgraph::traits::RandomAccessGraph;
+
+/// The state of a thread computing the contribution to the betweenness of a
+/// subset of the sources.
+struct BrandesState {
+    /// The partial betweenness accumulated by this thread.
+    betweenness: Vec<f64>,
+    /// The number of shortest paths from the current source, stored as a
+    /// floating-point number as it grows exponentially on some graphs.
+    sigma: Vec<f64>,
+    /// The distance from the current source, or [`usize::MAX`] if unknown.
+    distance: Vec<usize>,
+    /// The dependency of the current source on each node.
+    delta: Vec<f64>,
+    /// The nodes reached by the current visit, in nondecreasing distance order.
+    stack: Vec<usize>,
+    /// The queue of the breadth-first visit.
+    queue: VecDeque<usize>,
+}
+
+impl BrandesState {
+    fn new(num_nodes: usize) -> Self {
+        Self {
+            betweenness: vec![0.0; num_nodes],
+            sigma: vec![0.0; num_nodes],
+            distance: vec![usize::MAX; num_nodes],
+            delta: vec![0.0; num_nodes],
+            stack: Vec::new(),
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Adds to the partial betweenness the dependencies of `src`.
+    fn process(&mut self, graph: &impl RandomAccessGraph, src: usize) {
+        self.sigma[src] = 1.0;
+        self.distance[src] = 0;
+        self.queue.push_back(src);
+
+        while let Some(node) = self.queue.pop_front() {
+            self.stack.push(node);
+            let d = self.distance[node] + 1;
+            for succ in graph.successors(node) {
+                if self.distance[succ] == usize::MAX {
+                    self.distance[succ] = d;
+                    self.queue.push_back(succ);
+                }
+                // Self-loops never satisfy this condition, so they do not
+                // contribute to the number of shortest paths
+                if self.distance[succ] == d {
+                    self.sigma[succ] += self.sigma[node];
+                }
+            }
+        }
+
+        // Nodes are scanned in nonincreasing distance order, so the
+        // dependencies of all successors on a shortest path are final
+        for &node in self.stack.iter().rev() {
+            let d = self.distance[node] + 1;
+            let sigma = self.sigma[node];
+            let mut delta = 0.0;
+            for succ in graph.successors(node) {
+                if self.distance[succ] == d {
+                    delta += sigma / self.sigma[succ] * (1.0 + self.delta[succ]);
+                }
+            }
+            self.delta[node] = delta;
+            if node != src {
+                self.betweenness[node] += delta;
+            }
+        }
+
+        // Only reached nodes have been modified
+        for node in self.stack.drain(..) {
+            self.sigma[node] = 0.0;
+            self.distance[node] = usize::MAX;
+            self.delta[node] = 0.0;
+        }
+    }
+}
+
+/// Computes the betweenness centrality of all nodes using Brandes' algorithm.
+///
+/// For each source a breadth-first visit counts the shortest paths to every
+/// other node, and dependencies are then propagated back in reverse order of
+/// distance. Every thread of `thread_pool` processes in turn a different
+/// source, accumulating the contributions of its sources in a private vector,
+/// and the vectors are summed at the end.
+///
+/// The betweenness of a node `v` is the sum, over all pairs of distinct nodes
+/// `s` and `t` different from `v`, of the fraction of shortest paths from `s`
+/// to `t` passing through `v`. Self-loops do not lie on any shortest path, and
+/// isolated nodes have betweenness zero.
+///
+/// # Arguments
+/// * `graph`: the graph.
+/// * `thread_pool`: The thread pool to use for parallel computation.
+/// * `pl`: A progress logger.
+pub fn brandes(
+    graph: impl RandomAccessGraph + Sync,
+    thread_pool: &ThreadPool,
+    pl: &mut impl ProgressLog,
+) -> Vec<f64> {
+    let num_nodes = graph.num_nodes();
+    pl.item_name("node");
+    pl.expected_updates(Some(num_nodes));
+    pl.start("Computing betweenness centrality...");
+
+    let current_index = AtomicUsize::new(0);
+
+    let partials = thread_pool.broadcast(|_| {
+        let mut state = BrandesState::new(num_nodes);
+        let mut src = current_index.fetch_add(1, Ordering::Relaxed);
+
+        while src < num_nodes {
+            state.process(&graph, src);
+            src = current_index.fetch_add(1, Ordering::Relaxed);
+        }
+
+        state.betweenness
+    });
+
+    let mut betweenness = vec![0.0; num_nodes];
+    for partial in partials {
+        betweenness
+            .iter_mut()
+            .zip(partial)
+            .for_each(|(b, p)| *b += p);
+    }
+
+    pl.update_with_count(num_nodes);
+    pl.done();
+
+    betweenness
+}
@@ -0,0 +1,119 @@
+//! Algorithms used to compute the exact closeness centrality of a graph's
+//! nodes.
+//!
+//! [HyperBall](crate::algo::hyperball) provides approximate closeness
+//! centralities for large graphs; the algorithms in this module perform a
+//! breadth-first visit from every node, and are thus suitable only for
+//! medium-sized graphs.
+//!
+//! # Examples
+//! ```
+//! use dsi_progress_logger::no_logging;
+//! use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
+//! use webgraph_algo::{prelude::closeness, threads};
+//!
+//! let graph = Left(VecGraph::from_arc_list([(0, 1), (1, 2), (2, 3)]));
+//!
+//! let closeness = closeness::exact(&graph, false, &threads![], no_logging![]);
+//!
+//! assert_eq!(closeness, vec![0.5, 2.0 / 3.0, 1.0, 0.0]);
+//! ```
+
+use crate::algo::visits::{
+    breadth_first::{EventNoPred, ParFairNoPred},
+    Parallel,
+};
+use dsi_progress_logger::{no_logging, ProgressLog};
+use no_break::NoBreak;
+use rayon::ThreadPool;
+use std::{
+    ops::ControlFlow::Continue,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use sync_cell_slice::SyncSlice;
+use webgraph::traits::RandomAccessGraph;
+
+const VISIT_GRANULARITY: usize = 32;
+
+/// Computes the exact closeness centrality of all nodes.
+///
+/// The closeness centrality of a node `v` is the number of nodes reachable
+/// from `v` (excluding `v`) divided by the sum of their distances from `v`.
+/// As in the case of [HyperBall](crate::algo::hyperball), the closeness
+/// centrality of a node that cannot reach any other node is zero.
+///
+/// If `normalized` is true, the closeness centrality of each node is further
+/// multiplied by the fraction of the other nodes of the graph that it can
+/// reach, so that centralities of nodes in different components are
+/// comparable.
+///
+/// Every thread of `thread_pool` visits in turn a different source with a
+/// [parallel breadth-first visit](ParFairNoPred).
+///
+/// # Arguments
+/// * `graph`: the graph.
+/// * `normalized`: whether to normalize centralities by the fraction of
+///   reachable nodes.
+/// * `thread_pool`: The thread pool to use for parallel computation.
+/// * `pl`: A progress logger.
+pub fn exact(
+    graph: impl RandomAccessGraph + Sync,
+    normalized: bool,
+    thread_pool: &ThreadPool,
+    pl: &mut impl ProgressLog,
+) -> Vec<f64> {
+    let num_nodes = graph.num_nodes();
+    pl.item_name("node");
+    pl.expected_updates(Some(num_nodes));
+    pl.start("Computing closeness centrality...");
+
+    let mut closeness = vec![0.0; num_nodes];
+    let closeness_mut = closeness.as_sync_slice();
+    let current_index = AtomicUsize::new(0);
+
+    thread_pool.broadcast(|_| {
+        let mut bfs = ParFairNoPred::new(&graph, VISIT_GRANULARITY);
+        let mut src = current_index.fetch_add(1, Ordering::Relaxed);
+
+        while src < num_nodes {
+            let reachable = AtomicUsize::new(0);
+            let sum_of_distances = AtomicUsize::new(0);
+
+            bfs.reset();
+            bfs.par_visit(
+                src,
+                |event| {
+                    if let EventNoPred::Unknown { distance, .. } = event {
+                        if distance > 0 {
+                            reachable.fetch_add(1, Ordering::Relaxed);
+                            sum_of_distances.fetch_add(distance, Ordering::Relaxed);
+                        }
+                    }
+                    Continue(())
+                },
+                thread_pool,
+                no_logging![],
+            )
+            .continue_value_no_break();
+
+            let reachable = reachable.into_inner();
+            let sum_of_distances = sum_of_distances.into_inner();
+            let value = if sum_of_distances == 0 {
+                0.0
+            } else if normalized {
+                (reachable * reachable) as f64 / (sum_of_distances as f64 * (num_nodes - 1) as f64)
+            } else {
+                reachable as f64 / sum_of_distances as f64
+            };
+            // Safety: each node is accessed exactly once
+            unsafe { closeness_mut[src].set(value) };
+
+            src = current_index.fetch_add(1, Ordering::Relaxed);
+        }
+    });
+
+    pl.update_with_count(num_nodes);
+    pl.done();
+
+    closeness
+}
@@ -0,0 +1,125 @@
+//! Algorithms used to compute the clustering coefficients of a graph.
+//!
+//! Clustering coefficients are computed on the undirected version of the
+//! graph, in which two nodes are neighbors if there is an arc between them in
+//! either direction. Multiple arcs and self-loops are ignored.
+//!
+//! # Examples
+//! ```
+//! use dsi_progress_logger::no_logging;
+//! use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
+//! use webgraph_algo::{prelude::clustering, threads};
+//!
+//! // A triangle with a pendant node
+//! let arcs = [(0, 1), (1, 2), (2, 0), (2, 3)];
+//! let graph = Left(VecGraph::from_arc_list(arcs));
+//! let transpose = Left(VecGraph::from_arc_list(arcs.map(|(u, v)| (v, u))));
+//!
+//! let local = clustering::local_coefficient(&graph, &transpose, &threads![], no_logging![]);
+//!
+//! assert_eq!(local, vec![1.0, 1.0, 1.0 / 3.0, 0.0]);
+//! ```
+
+use crate::algo::triangles::count_from_neighbors;
+use dsi_progress_logger::ProgressLog;
+use rayon::ThreadPool;
+use webgraph::traits::RandomAccessGraph;
+
+/// Computes the local clustering coefficient of every node.
+///
+/// The local clustering coefficient of a node `v` of degree `d` is the number
+/// of triangles containing `v` divided by `d(d - 1) / 2`, that is, the
+/// fraction of pairs of neighbors of `v` that are neighbors. Nodes with degree
+/// smaller than two have local clustering coefficient zero.
+///
+/// # Arguments
+/// * `graph`: the graph.
+/// * `transpose`: the transposed of `graph`.
+/// * `thread_pool`: The thread pool to use for parallel computation.
+/// * `pl`: A progress logger.
+pub fn local_coefficient(
+    graph: impl RandomAccessGraph + Sync,
+    transpose: impl RandomAccessGraph + Sync,
+    thread_pool: &ThreadPool,
+    pl: &mut impl ProgressLog,
+) -> Vec<f64> {
+    local_coefficient_and_degrees(graph, transpose, thread_pool, pl).0
+}
+
+/// Computes the global clustering coefficient of the graph.
+///
+/// The global clustering coefficient is the average of the [local clustering
+/// coefficients](local_coefficient) of the nodes with degree at least two. If
+/// there are no such nodes, the global clustering coefficient is zero.
+///
+/// # Arguments
+/// * `graph`: the graph.
+/// * `transpose`: the transposed of `graph`.
+/// * `thread_pool`: The thread pool to use for parallel computation.
+/// * `pl`: A progress logger.
+pub fn global_coefficient(
+    graph: impl RandomAccessGraph + Sync,
+    transpose: impl RandomAccessGraph + Sync,
+    thread_pool: &ThreadPool,
+    pl: &mut impl ProgressLog,
+) -> f64 {
+    let (local, degree) = local_coefficient_and_degrees(graph, transpose, thread_pool, pl);
+
+    let (sum, count) = local
+        .iter()
+        .zip(degree)
+        .filter(|(_, d)| *d >= 2)
+        .fold((0.0, 0), |(sum, count), (c, _)| (sum + c, count + 1));
+
+    if count == 0 {
+        0.0
+    } else {
+        sum / count as f64
+    }
+}
+
+/// Returns the local clustering coefficients and the undirected degrees of
+/// all nodes.
+fn local_coefficient_and_degrees(
+    graph: impl RandomAccessGraph + Sync,
+    transpose: impl RandomAccessGraph + Sync,
+    thread_pool: &ThreadPool,
+    pl: &mut impl ProgressLog,
+) -> (Vec<f64>, Vec<usize>) {
+    let num_nodes = graph.num_nodes();
+    assert_eq!(
+        transpose.num_nodes(),
+        num_nodes,
+        "the transpose should have the same number of nodes of the graph ({}). Got {}.",
+        num_nodes,
+        transpose.num_nodes()
+    );
+
+    pl.item_name("node");
+    pl.expected_updates(Some(num_nodes));
+    pl.start("Computing clustering coefficients...");
+
+    let (_, triangles, degree) = thread_pool.install(|| {
+        count_from_neighbors(num_nodes, |node, neighbors| {
+            neighbors.extend(graph.successors(node));
+            neighbors.extend(transpose.successors(node));
+        })
+    });
+
+    let local = triangles
+        .into_iter()
+        .zip(&degree)
+        .map(|(t, &d)| {
+            if d < 2 {
+                0.0
+            } else {
+                2.0 * t as f64 / (d * (d - 1)) as f64
+            }
+        })
+        .collect();
+
+    pl.update_with_count(num_nodes);
+    pl.done();
+
+    (local, degree)
+}
@@ -0,0 +1,79 @@
+//! Algorithms used to compute the connected components of an undirected
+//! graph.
+//!
+//! Undirected graphs are represented as symmetric graphs. For directed
+//! graphs, see [strongly connected components](crate::algo::sccs).
+//!
+//! # Examples
+//! ```
+//! use dsi_progress_logger::no_logging;
+//! use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
+//! use webgraph_algo::{prelude::components, threads};
+//!
+//! // An edge and an isolated node
+//! let graph = Left(VecGraph::from_arc_list([(0, 1), (1, 0), (2, 2)]));
+//!
+//! assert_eq!(components::count_undirected(&graph, &threads![], no_logging![]), 2);
+//! ```
+
+use crate::{
+    algo::visits::{
+        breadth_first::{EventNoPred, ParFairNoPred},
+        Parallel,
+    },
+    utils::check_symmetric,
+};
+use dsi_progress_logger::ProgressLog;
+use no_break::NoBreak;
+use rayon::ThreadPool;
+use std::{
+    ops::ControlFlow::Continue,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use webgraph::traits::RandomAccessGraph;
+
+const VISIT_GRANULARITY: usize = 32;
+
+/// Returns the number of connected components of a symmetric graph.
+///
+/// The graph is visited using [fair parallel breadth-first
+/// visits](ParFairNoPred) starting from each node that has not been visited
+/// yet, and the number of visits started is returned. Since the graph is
+/// symmetric, there is no need of a transpose, and the computation is
+/// simpler and faster than computing [strongly connected
+/// components](crate::algo::sccs).
+///
+/// # Arguments
+/// * `graph`: the graph. It must be symmetric.
+/// * `thread_pool`: The thread pool to use for parallel computation.
+/// * `pl`: A progress logger.
+pub fn count_undirected(
+    graph: impl RandomAccessGraph + Sync,
+    thread_pool: &ThreadPool,
+    pl: &mut impl ProgressLog,
+) -> usize {
+    debug_assert!(check_symmetric(&graph));
+
+    pl.item_name("node");
+    pl.expected_updates(Some(graph.num_nodes()));
+    pl.start("Counting connected components...");
+
+    let num_components = AtomicUsize::new(0);
+    let mut visit = ParFairNoPred::new(&graph, VISIT_GRANULARITY);
+    visit
+        .par_visit_all(
+            |event| {
+                if let EventNoPred::Init { .. } = event {
+                    num_components.fetch_add(1, Ordering::Relaxed);
+                }
+                Continue(())
+            },
+            thread_pool,
+            pl,
+        )
+        .continue_value_no_break();
+
+    pl.done();
+
+    num_components.into_inner()
+}
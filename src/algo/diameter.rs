@@ -0,0 +1,121 @@
+//! Algorithms used to estimate the diameter of a graph.
+//!
+//! [ExactSumSweep](crate::algo::exact_sum_sweep) computes the exact diameter
+//! of a graph; the algorithms in this module provide fast bounds.
+//!
+//! # Examples
+//! ```
+//! use dsi_progress_logger::no_logging;
+//! use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
+//! use webgraph_algo::{prelude::diameter, threads};
+//!
+//! // A symmetric path 0 - 1 - 2 - 3
+//! let graph = Left(VecGraph::from_arc_list([
+//!     (0, 1), (1, 0), (1, 2), (2, 1), (2, 3), (3, 2),
+//! ]));
+//!
+//! let (lower_bound, (u, v)) = diameter::double_sweep(&graph, &threads![], no_logging![]);
+//!
+//! assert_eq!(lower_bound, 3);
+//! assert_eq!((u, v), (3, 0));
+//! ```
+
+use crate::{
+    algo::visits::{
+        breadth_first::{EventNoPred, ParFairNoPred},
+        Parallel,
+    },
+    utils::check_symmetric,
+};
+use dsi_progress_logger::ProgressLog;
+use no_break::NoBreak;
+use rayon::ThreadPool;
+use std::{
+    ops::ControlFlow::Continue,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+use webgraph::traits::RandomAccessGraph;
+
+const VISIT_GRANULARITY: usize = 32;
+
+/// Computes a lower bound on the diameter of a symmetric graph using the
+/// double-sweep heuristic.
+///
+/// A breadth-first visit from node 0 finds a node `u` at maximum distance
+/// from 0, and a second breadth-first visit from `u` finds a node `v` at
+/// maximum distance from `u`. The distance between `u` and `v`, which is
+/// returned together with the pair `(u, v)`, is a lower bound on the diameter
+/// of the connected component of 0, and it is often tight in practice.
+///
+/// The visits are [fair parallel breadth-first visits](ParFairNoPred), so if
+/// several nodes are at maximum distance the choice among them is arbitrary.
+///
+/// # Arguments
+/// * `graph`: the graph. It must be symmetric.
+/// * `thread_pool`: The thread pool to use for parallel computation.
+/// * `pl`: A progress logger.
+///
+/// # Panics
+/// Panics if the graph has no nodes.
+pub fn double_sweep(
+    graph: impl RandomAccessGraph + Sync,
+    thread_pool: &ThreadPool,
+    pl: &mut impl ProgressLog,
+) -> (usize, (usize, usize)) {
+    debug_assert!(check_symmetric(&graph));
+    assert!(
+        graph.num_nodes() > 0,
+        "the graph should have at least a node"
+    );
+
+    pl.item_name("node");
+    pl.expected_updates(None);
+    pl.start("Computing double sweep...");
+
+    let mut visit = ParFairNoPred::new(&graph, VISIT_GRANULARITY);
+    let (_, u) = farthest(&mut visit, 0, thread_pool, pl);
+    visit.reset();
+    let (lower_bound, v) = farthest(&mut visit, u, thread_pool, pl);
+
+    pl.done();
+
+    (lower_bound, (u, v))
+}
+
+/// Returns the maximum distance from `root` of a node reachable from `root`,
+/// and a node at that distance.
+fn farthest(
+    visit: &mut impl Parallel<EventNoPred>,
+    root: usize,
+    thread_pool: &ThreadPool,
+    pl: &mut impl ProgressLog,
+) -> (usize, usize) {
+    let max_distance = AtomicUsize::new(0);
+    let farthest = Mutex::new((0, root));
+
+    visit
+        .par_visit(
+            root,
+            |event| {
+                if let EventNoPred::Unknown { curr, distance, .. } = event {
+                    // We lock only if the distance might be larger
+                    if distance > max_distance.load(Ordering::Relaxed) {
+                        let mut farthest = farthest.lock().unwrap();
+                        if distance > farthest.0 {
+                            *farthest = (distance, curr);
+                            max_distance.store(distance, Ordering::Relaxed);
+                        }
+                    }
+                }
+                Continue(())
+            },
+            thread_pool,
+            pl,
+        )
+        .continue_value_no_break();
+
+    farthest.into_inner().unwrap()
+}
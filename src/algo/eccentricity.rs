@@ -0,0 +1,129 @@
+//! Algorithms used to compute the eccentricity of a single node.
+//!
+//! To compute the eccentricities of all nodes, or the radius and the diameter
+//! of a graph, use [ExactSumSweep](crate::algo::exact_sum_sweep), which needs
+//! far fewer visits than one per node.
+//!
+//! # Examples
+//! ```
+//! use dsi_progress_logger::no_logging;
+//! use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
+//! use webgraph_algo::{prelude::eccentricity, threads};
+//!
+//! let graph = Left(VecGraph::from_arc_list([(0, 1), (1, 2), (2, 0), (2, 3)]));
+//! let transpose = Left(VecGraph::from_arc_list([(1, 0), (2, 1), (0, 2), (3, 2)]));
+//!
+//! assert_eq!(eccentricity::single_source(&graph, 0, &threads![], no_logging![]), 3);
+//! assert_eq!(
+//!     eccentricity::forward_and_backward(&graph, &transpose, 3, &threads![], no_logging![]),
+//!     (0, 3)
+//! );
+//! ```
+
+use crate::{
+    algo::visits::{
+        breadth_first::{EventNoPred, ParFairNoPred},
+        Parallel,
+    },
+    utils::check_transposed,
+};
+use dsi_progress_logger::ProgressLog;
+use no_break::NoBreak;
+use rayon::ThreadPool;
+use std::{
+    ops::ControlFlow::Continue,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use webgraph::traits::RandomAccessGraph;
+
+const VISIT_GRANULARITY: usize = 32;
+
+/// Returns the maximum distance from `source` of a node reachable from
+/// `source`.
+fn max_distance(
+    graph: impl RandomAccessGraph + Sync,
+    source: usize,
+    thread_pool: &ThreadPool,
+    pl: &mut impl ProgressLog,
+) -> usize {
+    let max_distance = AtomicUsize::new(0);
+    ParFairNoPred::new(&graph, VISIT_GRANULARITY)
+        .par_visit(
+            source,
+            |event| {
+                if let EventNoPred::Unknown { distance, .. } = event {
+                    max_distance.fetch_max(distance, Ordering::Relaxed);
+                }
+                Continue(())
+            },
+            thread_pool,
+            pl,
+        )
+        .continue_value_no_break();
+    max_distance.into_inner()
+}
+
+/// Returns the (forward) eccentricity of a node, that is, the maximum
+/// distance from the node of a node reachable from it.
+///
+/// The eccentricity is computed using a [fair parallel breadth-first
+/// visit](ParFairNoPred). Only reachable nodes are considered, so nodes
+/// without successors have eccentricity zero.
+///
+/// # Arguments
+/// * `graph`: the graph.
+/// * `source`: the node whose eccentricity should be computed.
+/// * `thread_pool`: The thread pool to use for parallel computation.
+/// * `pl`: A progress logger.
+pub fn single_source(
+    graph: impl RandomAccessGraph + Sync,
+    source: usize,
+    thread_pool: &ThreadPool,
+    pl: &mut impl ProgressLog,
+) -> usize {
+    pl.item_name("node");
+    pl.expected_updates(None);
+    pl.start(format!("Computing the eccentricity of node {}...", source));
+
+    let eccentricity = max_distance(graph, source, thread_pool, pl);
+
+    pl.done();
+
+    eccentricity
+}
+
+/// Returns the forward and backward eccentricities of a node.
+///
+/// The backward eccentricity of a node is the maximum distance to the node
+/// of a node from which it is reachable, that is, its forward eccentricity in
+/// the transpose. See [`single_source`] for details.
+///
+/// # Arguments
+/// * `graph`: the graph.
+/// * `transpose`: the transpose of `graph`.
+/// * `source`: the node whose eccentricities should be computed.
+/// * `thread_pool`: The thread pool to use for parallel computation.
+/// * `pl`: A progress logger.
+pub fn forward_and_backward(
+    graph: impl RandomAccessGraph + Sync,
+    transpose: impl RandomAccessGraph + Sync,
+    source: usize,
+    thread_pool: &ThreadPool,
+    pl: &mut impl ProgressLog,
+) -> (usize, usize) {
+    debug_assert!(check_transposed(&graph, &transpose));
+
+    pl.item_name("node");
+    pl.expected_updates(None);
+    pl.start(format!(
+        "Computing the forward and backward eccentricities of node {}...",
+        source
+    ));
+
+    let forward = max_distance(graph, source, thread_pool, pl);
+    let backward = max_distance(transpose, source, thread_pool, pl);
+
+    pl.done();
+
+    (forward, backward)
+}
@@ -3,13 +3,14 @@ use crate::{
         exact_sum_sweep::{output_level::Output, scc_graph::SccGraph},
         sccs::{self, BasicSccs},
         visits::{
-            breadth_first::{EventNoPred, ParFairNoPred},
-            FilterArgs, Parallel,
+            breadth_first::{EventNoPred, EventPred, ParFairNoPred, ParFairPred},
+            Parallel,
         },
     },
     traits::StronglyConnectedComponents,
     utils::*,
 };
+use anyhow::{ensure, Context, Result};
 use dsi_progress_logger::no_logging;
 use dsi_progress_logger::*;
 use no_break::NoBreak;
@@ -19,7 +20,7 @@ use std::{
     ops::ControlFlow::Continue,
     sync::{
         atomic::{AtomicUsize, Ordering},
-        RwLock,
+        Mutex, RwLock,
     },
 };
 use sux::bits::AtomicBitVec;
@@ -29,6 +30,23 @@ use webgraph::traits::RandomAccessGraph;
 /// Experimentally obtained sane value for the granularity of the visits.
 const VISIT_GRANULARITY: usize = 64;
 
+/// The state of the bounds of the *ExactSumSweep* algorithm after a step of
+/// its main loop, as passed to the hook of
+/// [`compute_directed_with_hook`](super::OutputLevel::compute_directed_with_hook)
+/// and [`compute_undirected_with_hook`](super::OutputLevel::compute_undirected_with_hook).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundsSnapshot {
+    /// The upper bound of the radius.
+    pub radius_high: usize,
+    /// The lower bound of the diameter.
+    pub diameter_low: usize,
+    /// The number of nodes whose bounds must still be tightened to obtain
+    /// the required output.
+    pub missing_nodes: usize,
+    /// Number of iterations performed until now.
+    pub iterations: usize,
+}
+
 /// The implementation of the *SumSweep* algorithm on directed graphs.
 pub struct DirExactSumSweepComputer<
     'a,
@@ -79,6 +97,9 @@ pub struct DirExactSumSweepComputer<
     /// of the next vertex to process).
     pub backward_tot: Box<[usize]>,
     pub compute_radial_vertices: bool,
+    /// Whether the graph is symmetric, and thus its own transpose. In this
+    /// case forward and backward bounds coincide, and each visit updates both.
+    pub symmetric: bool,
     pub visit: V1,
     pub transposed_visit: V2,
 }
@@ -116,6 +137,7 @@ impl<'a, G: RandomAccessGraph + Sync>
             scc_graph,
             visit,
             transposed_visit,
+            true,
             pl,
         )
     }
@@ -125,7 +147,7 @@ impl<'a, G1: RandomAccessGraph + Sync, G2: RandomAccessGraph + Sync>
     DirExactSumSweepComputer<'a, G1, G2, ParFairNoPred<&'a G1>, ParFairNoPred<&'a G2>>
 {
     /// Build a new instance to compute the *ExactSumSweep* algorithm on
-    /// directed graphs.
+    /// directed graphs using precomputed strongly connected components.
     ///
     /// # Arguments
     /// * `graph`: the direct graph.
@@ -134,22 +156,28 @@ impl<'a, G1: RandomAccessGraph + Sync, G2: RandomAccessGraph + Sync>
     /// * `radial_vertices`: an [`AtomicBitVec`] where `v[i]` is true if node
     ///    `i` is to be considered radial vertex. If [`None`] the algorithm will
     ///    use the biggest connected component.
+    /// * `scc`: the strongly connected components of `graph`.
     /// * `pl`: a progress logger.
-    pub fn new_directed(
+    pub fn new_directed_with_sccs(
         graph: &'a G1,
         transpose: &'a G2,
         output: Output,
         radial_vertices: Option<AtomicBitVec>,
+        scc: BasicSccs,
         pl: &mut impl ProgressLog,
     ) -> Self {
         assert_eq!(graph.num_nodes(), transpose.num_nodes());
         assert_eq!(graph.num_arcs(), transpose.num_arcs());
+        assert_eq!(
+            scc.components().len(),
+            graph.num_nodes(),
+            "the strongly connected components should have an entry for each node"
+        );
         debug_assert!(
             check_transposed(graph, transpose),
             "transpose should be the transpose of graph"
         );
 
-        let scc = sccs::tarjan(graph, pl);
         let scc_graph = SccGraph::new_directed(graph, transpose, &scc, pl);
         let visit = ParFairNoPred::new(graph, VISIT_GRANULARITY);
         let transposed_visit = ParFairNoPred::new(transpose, VISIT_GRANULARITY);
@@ -163,6 +191,7 @@ impl<'a, G1: RandomAccessGraph + Sync, G2: RandomAccessGraph + Sync>
             scc_graph,
             visit,
             transposed_visit,
+            false,
             pl,
         )
     }
@@ -186,6 +215,7 @@ impl<
         scc_graph: SccGraph<G1, G2, BasicSccs>,
         visit: V1,
         transposed_visit: V2,
+        symmetric: bool,
         pl: &mut impl ProgressLog,
     ) -> Self {
         let num_nodes = graph.num_nodes();
@@ -228,6 +258,7 @@ impl<
             radius_vertex: 0,
             diameter_vertex: 0,
             compute_radial_vertices,
+            symmetric,
             visit,
             transposed_visit,
         }
@@ -305,7 +336,36 @@ impl<
     /// * `thread_pool`: The thread pool to use for parallel computation.
     /// * `pl`: A progress logger.
     pub fn compute(&mut self, thread_pool: &ThreadPool, pl: &mut impl ProgressLog) {
+        self.compute_with_hook(thread_pool, pl, |_| {});
+    }
+
+    /// Computes diameter, radius, and/or all eccentricities, passing to
+    /// `hook` a [snapshot of the bounds](BoundsSnapshot) after each step of
+    /// the main loop.
+    ///
+    /// The hook is called on the thread calling this method, between
+    /// parallel steps, so it does not need to be [`Send`]. It is passed as an
+    /// argument rather than stored in the computer because the computer is
+    /// shared among the threads of `thread_pool`.
+    ///
+    /// # Arguments
+    /// * `thread_pool`: The thread pool to use for parallel computation.
+    /// * `pl`: A progress logger.
+    /// * `hook`: the function receiving the snapshots.
+    pub fn compute_with_hook(
+        &mut self,
+        thread_pool: &ThreadPool,
+        pl: &mut impl ProgressLog,
+        mut hook: impl FnMut(BoundsSnapshot),
+    ) {
         if self.num_nodes == 0 {
+            // There are no eccentricities to compute, so all outputs are
+            // trivially available, with a zero radius and diameter
+            self.radius_high = 0;
+            self.forward_iter = Some(0);
+            self.all_iter = Some(0);
+            self.radius_iterations = Some(0);
+            self.diameter_iterations = Some(0);
             return;
         }
 
@@ -411,11 +471,79 @@ impl<
                 missing_nodes,
                 self.num_nodes * 2
             ));
+
+            hook(BoundsSnapshot {
+                radius_high: self.radius_high,
+                diameter_low: self.diameter_low,
+                missing_nodes,
+                iterations: self.iterations,
+            });
         }
 
         pl.done();
     }
 
+    /// Returns the forward eccentricities of all nodes.
+    ///
+    /// The eccentricities are available only if [`compute`](Self::compute)
+    /// has found all forward eccentricities, which is guaranteed if the output
+    /// level is [`Output::All`] or [`Output::AllForward`]. Otherwise, an error
+    /// is returned.
+    pub fn forward_eccentricities(&self) -> Result<&[usize]> {
+        ensure!(
+            self.forward_iter.is_some(),
+            "Forward eccentricities have not been computed: the output level {:?} is insufficient. Use All or AllForward",
+            self.output
+        );
+        Ok(&self.forward_low)
+    }
+
+    /// Returns the backward eccentricities of all nodes.
+    ///
+    /// The eccentricities are available only if [`compute`](Self::compute)
+    /// has found all eccentricities, which is guaranteed if the output level
+    /// is [`Output::All`]. Otherwise, an error is returned.
+    pub fn backward_eccentricities(&self) -> Result<&[usize]> {
+        ensure!(
+            self.all_iter.is_some(),
+            "Backward eccentricities have not been computed: the output level {:?} is insufficient. Use All",
+            self.output
+        );
+        Ok(&self.backward_high)
+    }
+
+    /// Returns the center of the graph, that is, the radial vertices whose
+    /// forward eccentricity equals the radius.
+    ///
+    /// On directed graphs the center is based on forward eccentricities.
+    /// The center is available only if all forward eccentricities have been
+    /// computed (see [`forward_eccentricities`](Self::forward_eccentricities)).
+    /// Otherwise, an error is returned.
+    pub fn center(&self) -> Result<Vec<usize>> {
+        let eccentricities = self
+            .forward_eccentricities()
+            .with_context(|| "Cannot compute the center")?;
+        Ok((0..self.num_nodes)
+            .filter(|&node| self.radial_vertices[node] && eccentricities[node] == self.radius_high)
+            .collect())
+    }
+
+    /// Returns the periphery of the graph, that is, the nodes whose forward
+    /// eccentricity equals the diameter.
+    ///
+    /// On directed graphs the periphery is based on forward eccentricities.
+    /// The periphery is available only if all forward eccentricities have been
+    /// computed (see [`forward_eccentricities`](Self::forward_eccentricities)).
+    /// Otherwise, an error is returned.
+    pub fn periphery(&self) -> Result<Vec<usize>> {
+        let eccentricities = self
+            .forward_eccentricities()
+            .with_context(|| "Cannot compute the periphery")?;
+        Ok((0..self.num_nodes)
+            .filter(|&node| eccentricities[node] == self.diameter_low)
+            .collect())
+    }
+
     /// Uses a heuristic to decide which is the best pivot to choose in each strongly connected
     /// component, in order to perform the [`Self::all_cc_upper_bound`] method.
     ///
@@ -496,8 +624,7 @@ impl<
         pl.start("Computing radial vertices...");
 
         let component = self.scc.components();
-        let scc_sizes = self.scc.compute_sizes();
-        let max_size_scc = math::argmax(&scc_sizes).expect("Could not find max size scc.");
+        let max_size_scc = self.scc.largest_component();
 
         pl.info(format_args!(
             "Searching for biggest strongly connected component"
@@ -536,6 +663,9 @@ impl<
     /// Performs a (forward or backward) BFS, updating lower bounds on the eccentricities
     /// of all visited vertices.
     ///
+    /// On [symmetric](Self::symmetric) graphs the two directions coincide, so
+    /// a single BFS updates both forward and backward bounds.
+    ///
     /// For more information see Section 4.1 of the paper.
     ///
     /// # Arguments
@@ -554,7 +684,8 @@ impl<
         message: impl FnOnce(usize) -> String,
     ) {
         if let Some(start) = start {
-            if forward {
+            // On symmetric graphs a backward step updates the forward bounds, too
+            if forward && !self.symmetric {
                 self.forward_step_sum_sweep(start, thread_pool, pl, message);
             } else {
                 self.backwards_step_sum_sweep(start, thread_pool, pl, message);
@@ -581,6 +712,9 @@ impl<
 
         let forward_low = self.forward_low.as_sync_slice();
         let forward_tot = self.forward_tot.as_sync_slice();
+        let backward_low = self.backward_low.as_sync_slice();
+        let backward_tot = self.backward_tot.as_sync_slice();
+        let symmetric = self.symmetric;
 
         self.transposed_visit
             .par_visit(
@@ -599,9 +733,15 @@ impl<
                         let node_forward_high = self.forward_high[node];
 
                         unsafe { forward_tot[node].set(forward_tot[node].get() + distance) };
+                        if symmetric {
+                            unsafe { backward_tot[node].set(forward_tot[node].get()) };
+                        }
 
                         if node_forward_low != node_forward_high && node_forward_low < distance {
                             unsafe { forward_low[node].set(distance) };
+                            if symmetric {
+                                unsafe { backward_low[node].set(distance) };
+                            }
 
                             if distance == node_forward_high && self.radial_vertices[node] {
                                 let mut update_radius = false;
@@ -642,6 +782,14 @@ impl<
             self.diameter_low = ecc_start;
             self.diameter_vertex = start;
         }
+        if symmetric {
+            self.forward_low[start] = ecc_start;
+            self.forward_high[start] = ecc_start;
+            if self.radial_vertices[start] && self.radius_high > ecc_start {
+                self.radius_high = ecc_start;
+                self.radius_vertex = start;
+            }
+        }
 
         pl.done();
     }
@@ -776,8 +924,9 @@ impl<
                 let pivot_component = components[p];
                 let component_ecc_pivot = &ecc_pivot[pivot_component];
 
-                bfs.par_visit_filtered(
+                bfs.visit_subset(
                     p,
+                    &|node| components[node] == pivot_component,
                     |event| {
                         if let EventNoPred::Unknown { curr, distance, .. } = event {
                             // Safety: each node is accessed exactly once
@@ -786,7 +935,6 @@ impl<
                         };
                         Continue(())
                     },
-                    |FilterArgs::<EventNoPred> { curr, .. }| components[curr] == pivot_component,
                     thread_pool,
                     no_logging![],
                 )
@@ -825,8 +973,12 @@ impl<
 
         let (dist_pivot_f, mut ecc_pivot_f) =
             self.compute_dist_pivot(&pivot, true, thread_pool, &mut pl.clone());
-        let (dist_pivot_b, mut ecc_pivot_b) =
-            self.compute_dist_pivot(&pivot, false, thread_pool, &mut pl.clone());
+        // On symmetric graphs backward distances are forward distances
+        let (dist_pivot_b, mut ecc_pivot_b) = if self.symmetric {
+            (dist_pivot_f.clone(), ecc_pivot_f.clone())
+        } else {
+            self.compute_dist_pivot(&pivot, false, thread_pool, &mut pl.clone())
+        };
         let components = self.scc.components();
 
         // Tarjan's algorithm emits components in reverse topological order.
@@ -930,7 +1082,7 @@ impl<
 
         (self.radius_high, self.radius_vertex) = radius.into_inner().unwrap();
 
-        self.iterations += 3;
+        self.iterations += if self.symmetric { 2 } else { 3 };
 
         pl.done();
     }
@@ -1013,3 +1165,217 @@ impl<
         }
     }
 }
+
+/// Returns a shortest path whose length equals the diameter of a graph.
+///
+/// The path is computed on demand from the results of the *ExactSumSweep*
+/// algorithm, as it requires a breadth-first visit from `diametral_vertex`,
+/// which is repeated on `transpose` if the vertex witnesses the diameter with
+/// its backward eccentricity. The path starts and ends with the endpoints of
+/// the diameter.
+///
+/// An error is returned if `diametral_vertex` is not a node of the graph, or
+/// if its forward and backward eccentricities differ from `diameter`.
+///
+/// # Arguments
+/// * `graph`: the graph.
+/// * `transpose`: the transpose of `graph`; for undirected graphs, `graph`
+///   itself.
+/// * `diametral_vertex`: a vertex whose eccentricity equals the diameter, as
+///   returned by an [`OutputLevel`](super::OutputLevel) computing the
+///   diameter.
+/// * `diameter`: the diameter of `graph`.
+/// * `thread_pool`: The thread pool to use for parallel computation.
+///
+/// # Examples
+///
+/// ```
+/// use dsi_progress_logger::no_logging;
+/// use webgraph::graphs::vec_graph::VecGraph;
+/// use webgraph::labels::proj::Left;
+/// use webgraph_algo::algo::exact_sum_sweep::{self, OutputLevel};
+/// use webgraph_algo::threads;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let graph = Left(VecGraph::from_arc_list([(0, 1), (1, 2), (2, 0), (2, 3)]));
+/// let transpose = Left(VecGraph::from_arc_list([(1, 0), (2, 1), (0, 2), (3, 2)]));
+///
+/// let result = exact_sum_sweep::Diameter::compute_directed(
+///     &graph,
+///     &transpose,
+///     None,
+///     &threads![],
+///     no_logging![],
+/// );
+/// let path = exact_sum_sweep::diametral_path(
+///     &graph,
+///     &transpose,
+///     result.diametral_vertex,
+///     result.diameter,
+///     &threads![],
+/// )?;
+///
+/// assert_eq!(path, vec![0, 1, 2, 3]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn diametral_path(
+    graph: impl RandomAccessGraph + Sync,
+    transpose: impl RandomAccessGraph + Sync,
+    diametral_vertex: usize,
+    diameter: usize,
+    thread_pool: &ThreadPool,
+) -> Result<Vec<usize>> {
+    ensure!(
+        transpose.num_nodes() == graph.num_nodes(),
+        "The graph has {} nodes, but its transpose has {} nodes",
+        graph.num_nodes(),
+        transpose.num_nodes()
+    );
+    ensure!(
+        diametral_vertex < graph.num_nodes(),
+        "Node {} does not exist in a graph with {} nodes",
+        diametral_vertex,
+        graph.num_nodes()
+    );
+
+    let path = farthest_path(&graph, diametral_vertex, thread_pool);
+    if path.len() == diameter + 1 {
+        return Ok(path);
+    }
+
+    let mut path = farthest_path(&transpose, diametral_vertex, thread_pool);
+    ensure!(
+        path.len() == diameter + 1,
+        "Node {} does not have eccentricity {}",
+        diametral_vertex,
+        diameter
+    );
+    path.reverse();
+    Ok(path)
+}
+
+/// Returns a shortest path from `root` to a node at maximum distance from
+/// `root`.
+fn farthest_path(
+    graph: &(impl RandomAccessGraph + Sync),
+    root: usize,
+    thread_pool: &ThreadPool,
+) -> Vec<usize> {
+    let mut parents = vec![root; graph.num_nodes()];
+    let parents_sync = parents.as_sync_slice();
+    let max_distance = AtomicUsize::new(0);
+    let farthest = Mutex::new((0, root));
+
+    ParFairPred::new(graph, VISIT_GRANULARITY)
+        .par_visit(
+            root,
+            |event| {
+                if let EventPred::Unknown {
+                    curr,
+                    pred,
+                    distance,
+                    ..
+                } = event
+                {
+                    // Safety: each node is discovered exactly once
+                    unsafe { parents_sync[curr].set(pred) };
+                    // We lock only if the distance might be larger
+                    if distance > max_distance.load(Ordering::Relaxed) {
+                        let mut farthest = farthest.lock().unwrap();
+                        if distance > farthest.0 {
+                            *farthest = (distance, curr);
+                            max_distance.store(distance, Ordering::Relaxed);
+                        }
+                    }
+                }
+                Continue(())
+            },
+            thread_pool,
+            no_logging![],
+        )
+        .continue_value_no_break();
+
+    let (_, mut node) = farthest.into_inner().unwrap();
+    let mut path = vec![node];
+    while node != root {
+        node = parents[node];
+        path.push(node);
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::threads;
+    use webgraph::{graphs::vec_graph::VecGraph, labels::Left, prelude::BvGraph};
+
+    #[cfg_attr(feature = "slow_tests", test)]
+    #[cfg_attr(not(feature = "slow_tests"), allow(dead_code))]
+    fn test_eccentricities_cnr_2000() -> Result<()> {
+        let basename = "tests/graphs/cnr-2000";
+
+        let graph = BvGraph::with_basename(basename).load()?;
+        let transpose = BvGraph::with_basename(basename.to_owned() + "-t").load()?;
+
+        let mut computer = DirExactSumSweepComputer::new_directed_with_sccs(
+            &graph,
+            &transpose,
+            Output::All,
+            None,
+            sccs::tarjan(&graph, no_logging![]),
+            no_logging![],
+        );
+        computer.compute(&threads![], no_logging![]);
+
+        let forward = computer.forward_eccentricities()?;
+        let backward = computer.backward_eccentricities()?;
+
+        assert_eq!(forward.iter().max(), Some(&computer.diameter_low));
+        assert_eq!(backward.iter().max(), Some(&computer.diameter_low));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_center_periphery_path() -> Result<()> {
+        let graph = Left(VecGraph::from_arc_list([
+            (0, 1),
+            (1, 0),
+            (1, 2),
+            (2, 1),
+            (2, 3),
+            (3, 2),
+            (3, 4),
+            (4, 3),
+        ]));
+
+        let mut computer =
+            DirExactSumSweepComputer::new_undirected(&graph, Output::All, no_logging![]);
+        computer.compute(&threads![], no_logging![]);
+
+        assert_eq!(computer.center()?, vec![2]);
+        assert_eq!(computer.periphery()?, vec![0, 4]);
+
+        // With an even number of nodes the center contains two nodes
+        let graph = Left(VecGraph::from_arc_list([
+            (0, 1),
+            (1, 0),
+            (1, 2),
+            (2, 1),
+            (2, 3),
+            (3, 2),
+        ]));
+
+        let mut computer =
+            DirExactSumSweepComputer::new_undirected(&graph, Output::All, no_logging![]);
+        computer.compute(&threads![], no_logging![]);
+
+        assert_eq!(computer.center()?, vec![1, 2]);
+        assert_eq!(computer.periphery()?, vec![0, 3]);
+
+        Ok(())
+    }
+}
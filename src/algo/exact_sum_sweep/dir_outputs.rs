@@ -10,9 +10,15 @@ pub struct All {
     pub diameter: usize,
     /// The radius.
     pub radius: usize,
-    /// A vertex whose eccentricity equals the diameter.
+    /// The radial vertices whose (forward) eccentricity equals the radius.
+    pub center: Box<[usize]>,
+    /// The vertices whose (forward) eccentricity equals the diameter.
+    pub periphery: Box<[usize]>,
+    /// A vertex whose eccentricity equals the diameter. If the graph has no
+    /// nodes, it is 0 and does not denote a vertex.
     pub diametral_vertex: usize,
-    /// A vertex whose eccentrivity equals the radius.
+    /// A vertex whose eccentricity equals the radius. If there are no radial
+    /// vertices, it is 0 and does not denote a vertex.
     pub radial_vertex: usize,
     /// Number of iterations before the radius was found.
     pub radius_iterations: usize,
@@ -34,9 +40,15 @@ pub struct AllForward {
     pub diameter: usize,
     /// The radius.
     pub radius: usize,
-    /// A vertex whose eccentricity equals the diameter.
+    /// The radial vertices whose (forward) eccentricity equals the radius.
+    pub center: Box<[usize]>,
+    /// The vertices whose (forward) eccentricity equals the diameter.
+    pub periphery: Box<[usize]>,
+    /// A vertex whose eccentricity equals the diameter. If the graph has no
+    /// nodes, it is 0 and does not denote a vertex.
     pub diametral_vertex: usize,
-    /// A vertex whose eccentrivity equals the radius.
+    /// A vertex whose eccentricity equals the radius. If there are no radial
+    /// vertices, it is 0 and does not denote a vertex.
     pub radial_vertex: usize,
     /// Number of iterations before the radius was found.
     pub radius_iterations: usize,
@@ -54,9 +66,11 @@ pub struct RadiusDiameter {
     pub diameter: usize,
     /// The radius.
     pub radius: usize,
-    /// A vertex whose eccentricity equals the diameter.
+    /// A vertex whose eccentricity equals the diameter. If the graph has no
+    /// nodes, it is 0 and does not denote a vertex.
     pub diametral_vertex: usize,
-    /// A vertex whose eccentrivity equals the radius.
+    /// A vertex whose eccentricity equals the radius. If there are no radial
+    /// vertices, it is 0 and does not denote a vertex.
     pub radial_vertex: usize,
     /// Number of iterations before the radius was found.
     pub radius_iterations: usize,
@@ -70,7 +84,8 @@ pub struct RadiusDiameter {
 pub struct Diameter {
     /// The diameter.
     pub diameter: usize,
-    /// A vertex whose eccentricity equals the diameter.
+    /// A vertex whose eccentricity equals the diameter. If the graph has no
+    /// nodes, it is 0 and does not denote a vertex.
     pub diametral_vertex: usize,
     /// Number of iterations before the diameter was found.
     pub diameter_iterations: usize,
@@ -82,7 +97,8 @@ pub struct Diameter {
 pub struct Radius {
     /// The radius.
     pub radius: usize,
-    /// A vertex whose eccentricity equals the radius.
+    /// A vertex whose eccentricity equals the radius. If there are no radial
+    /// vertices, it is 0 and does not denote a vertex.
     pub radial_vertex: usize,
     /// Number of iterations before the radius was found.
     pub radius_iterations: usize,
@@ -118,4 +118,6 @@ pub mod outputs {
     }
 }
 
+pub use computer::{diametral_path, BoundsSnapshot};
 pub use output_level::*;
+pub use scc_graph::{SccGraph, SccGraphConnection};
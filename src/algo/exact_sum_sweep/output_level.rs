@@ -1,4 +1,8 @@
-use super::{computer::DirExactSumSweepComputer, dir_outputs, undir_outputs};
+use super::{
+    computer::{BoundsSnapshot, DirExactSumSweepComputer},
+    dir_outputs, undir_outputs,
+};
+use crate::algo::sccs::{self, BasicSccs};
 use dsi_progress_logger::ProgressLog;
 use rayon::ThreadPool;
 use sux::bits::AtomicBitVec;
@@ -37,12 +41,79 @@ pub trait OutputLevel {
     ///    radial vertex. If [`None`] the algorithm will use the biggest connected component.
     /// * `thread_pool`: The thread pool to use for parallel computation.
     /// * `pl`: a progress logger.
+    ///
+    /// The strongly connected components of `graph` are computed using
+    /// [Tarjan's algorithm](sccs::tarjan). If they are already available, use
+    /// [`compute_directed_with_sccs`](Self::compute_directed_with_sccs).
     fn compute_directed(
         graph: impl RandomAccessGraph + Sync,
         transpose: impl RandomAccessGraph + Sync,
         radial_vertices: Option<AtomicBitVec>,
         thread_pool: &ThreadPool,
         pl: &mut impl ProgressLog,
+    ) -> Self::DirectedOutput {
+        let sccs = sccs::tarjan(&graph, pl);
+        Self::compute_directed_with_sccs(graph, transpose, radial_vertices, sccs, thread_pool, pl)
+    }
+
+    /// Build a new instance to compute the *ExactSumSweep* algorithm on the
+    /// specified directed graph using precomputed strongly connected
+    /// components and returns the results.
+    ///
+    /// # Arguments
+    /// * `graph`: the direct graph.
+    /// * `transpose`: the transpose of `graph`.
+    /// * `radial_vertices`: an [`AtomicBitVec`] where `v[i]` is true if node `i` is to be considered
+    ///    radial vertex. If [`None`] the algorithm will use the biggest connected component.
+    /// * `sccs`: the strongly connected components of `graph`, as computed,
+    ///   for example, by [`sccs::tarjan`].
+    /// * `thread_pool`: The thread pool to use for parallel computation.
+    /// * `pl`: a progress logger.
+    fn compute_directed_with_sccs(
+        graph: impl RandomAccessGraph + Sync,
+        transpose: impl RandomAccessGraph + Sync,
+        radial_vertices: Option<AtomicBitVec>,
+        sccs: BasicSccs,
+        thread_pool: &ThreadPool,
+        pl: &mut impl ProgressLog,
+    ) -> Self::DirectedOutput {
+        Self::compute_directed_with_hook(
+            graph,
+            transpose,
+            radial_vertices,
+            sccs,
+            thread_pool,
+            pl,
+            |_| {},
+        )
+    }
+
+    /// Build a new instance to compute the *ExactSumSweep* algorithm on the
+    /// specified directed graph using precomputed strongly connected
+    /// components and returns the results, passing to `hook` a [snapshot of
+    /// the bounds](BoundsSnapshot) after each step of the main loop.
+    ///
+    /// The hook is called on the thread calling this method, between
+    /// parallel steps, so it does not need to be [`Send`].
+    ///
+    /// # Arguments
+    /// * `graph`: the direct graph.
+    /// * `transpose`: the transpose of `graph`.
+    /// * `radial_vertices`: an [`AtomicBitVec`] where `v[i]` is true if node `i` is to be considered
+    ///    radial vertex. If [`None`] the algorithm will use the biggest connected component.
+    /// * `sccs`: the strongly connected components of `graph`, as computed,
+    ///   for example, by [`sccs::tarjan`].
+    /// * `thread_pool`: The thread pool to use for parallel computation.
+    /// * `pl`: a progress logger.
+    /// * `hook`: the function receiving the snapshots.
+    fn compute_directed_with_hook(
+        graph: impl RandomAccessGraph + Sync,
+        transpose: impl RandomAccessGraph + Sync,
+        radial_vertices: Option<AtomicBitVec>,
+        sccs: BasicSccs,
+        thread_pool: &ThreadPool,
+        pl: &mut impl ProgressLog,
+        hook: impl FnMut(BoundsSnapshot),
     ) -> Self::DirectedOutput;
 
     /// Build a new instance to compute the *ExactSumSweep* algorithm on the specified
@@ -50,13 +121,34 @@ pub trait OutputLevel {
     ///
     /// # Arguments
     /// * `graph`: the graph.
-    /// * `output`: the desired output of the algorithm.
     /// * `thread_pool`: The thread pool to use for parallel computation.
     /// * `pl`: a progress logger.
     fn compute_undirected(
         graph: impl RandomAccessGraph + Sync,
         thread_pool: &ThreadPool,
         pl: &mut impl ProgressLog,
+    ) -> Self::UndirectedOutput {
+        Self::compute_undirected_with_hook(graph, thread_pool, pl, |_| {})
+    }
+
+    /// Build a new instance to compute the *ExactSumSweep* algorithm on the
+    /// specified undirected graph and returns the results, passing to `hook`
+    /// a [snapshot of the bounds](BoundsSnapshot) after each step of the main
+    /// loop.
+    ///
+    /// The hook is called on the thread calling this method, between
+    /// parallel steps, so it does not need to be [`Send`].
+    ///
+    /// # Arguments
+    /// * `graph`: the graph.
+    /// * `thread_pool`: The thread pool to use for parallel computation.
+    /// * `pl`: a progress logger.
+    /// * `hook`: the function receiving the snapshots.
+    fn compute_undirected_with_hook(
+        graph: impl RandomAccessGraph + Sync,
+        thread_pool: &ThreadPool,
+        pl: &mut impl ProgressLog,
+        hook: impl FnMut(BoundsSnapshot),
     ) -> Self::UndirectedOutput;
 }
 
@@ -69,30 +161,37 @@ impl OutputLevel for All {
     type DirectedOutput = dir_outputs::All;
     type UndirectedOutput = undir_outputs::All;
 
-    fn compute_directed(
+    fn compute_directed_with_hook(
         graph: impl RandomAccessGraph + Sync,
         transpose: impl RandomAccessGraph + Sync,
         radial_vertices: Option<AtomicBitVec>,
+        sccs: BasicSccs,
         thread_pool: &ThreadPool,
         pl: &mut impl ProgressLog,
+        hook: impl FnMut(BoundsSnapshot),
     ) -> Self::DirectedOutput {
-        let mut computer = DirExactSumSweepComputer::new_directed(
+        let mut computer = DirExactSumSweepComputer::new_directed_with_sccs(
             &graph,
             &transpose,
             Output::All,
             radial_vertices,
+            sccs,
             pl,
         );
-        computer.compute(thread_pool, pl);
+        computer.compute_with_hook(thread_pool, pl, hook);
 
-        assert!(
-            computer.all_iter.is_some(),
-            "Trying to build All without all eccentricities computed"
-        );
-        assert!(
-            computer.forward_iter.is_some(),
-            "Trying to build All without all forward eccentricities computed"
-        );
+        if let Err(e) = computer.backward_eccentricities() {
+            panic!(
+                "Trying to build All without all eccentricities computed: {}",
+                e
+            );
+        }
+        if let Err(e) = computer.forward_eccentricities() {
+            panic!(
+                "Trying to build All without all forward eccentricities computed: {}",
+                e
+            );
+        }
         assert!(
             computer.diameter_iterations.is_some(),
             "Trying to build All without the diameter computed"
@@ -102,6 +201,9 @@ impl OutputLevel for All {
             "Trying to build All without the radius computed"
         );
 
+        // Forward eccentricities have been checked above
+        let center = computer.center().unwrap().into_boxed_slice();
+        let periphery = computer.periphery().unwrap().into_boxed_slice();
         let diameter = computer.diameter_low;
         let radius = computer.radius_high;
         let diametral_vertex = computer.diameter_vertex;
@@ -118,6 +220,8 @@ impl OutputLevel for All {
             backward_eccentricities,
             diameter,
             radius,
+            center,
+            periphery,
             diametral_vertex,
             radial_vertex,
             radius_iterations,
@@ -127,18 +231,21 @@ impl OutputLevel for All {
         }
     }
 
-    fn compute_undirected(
+    fn compute_undirected_with_hook(
         graph: impl RandomAccessGraph + Sync,
         thread_pool: &ThreadPool,
         pl: &mut impl ProgressLog,
+        hook: impl FnMut(BoundsSnapshot),
     ) -> Self::UndirectedOutput {
         let mut computer = DirExactSumSweepComputer::new_undirected(&graph, Output::All, pl);
-        computer.compute(thread_pool, pl);
+        computer.compute_with_hook(thread_pool, pl, hook);
 
-        assert!(
-            computer.forward_iter.is_some(),
-            "Trying to build All without all forward eccentricities computed"
-        );
+        if let Err(e) = computer.forward_eccentricities() {
+            panic!(
+                "Trying to build All without all forward eccentricities computed: {}",
+                e
+            );
+        }
         assert!(
             computer.diameter_iterations.is_some(),
             "Trying to build All without the diameter computed"
@@ -148,6 +255,9 @@ impl OutputLevel for All {
             "Trying to build All without the radius computed"
         );
 
+        // Forward eccentricities have been checked above
+        let center = computer.center().unwrap().into_boxed_slice();
+        let periphery = computer.periphery().unwrap().into_boxed_slice();
         let diameter = computer.diameter_low;
         let radius = computer.radius_high;
         let diametral_vertex = computer.diameter_vertex;
@@ -161,6 +271,8 @@ impl OutputLevel for All {
             eccentricities,
             diameter,
             radius,
+            center,
+            periphery,
             diametral_vertex,
             radial_vertex,
             radius_iterations,
@@ -177,26 +289,31 @@ impl OutputLevel for AllForward {
     type DirectedOutput = dir_outputs::AllForward;
     type UndirectedOutput = undir_outputs::All;
 
-    fn compute_directed(
+    fn compute_directed_with_hook(
         graph: impl RandomAccessGraph + Sync,
         transpose: impl RandomAccessGraph + Sync,
         radial_vertices: Option<AtomicBitVec>,
+        sccs: BasicSccs,
         thread_pool: &ThreadPool,
         pl: &mut impl ProgressLog,
+        hook: impl FnMut(BoundsSnapshot),
     ) -> Self::DirectedOutput {
-        let mut computer = DirExactSumSweepComputer::new_directed(
+        let mut computer = DirExactSumSweepComputer::new_directed_with_sccs(
             &graph,
             &transpose,
             Output::AllForward,
             radial_vertices,
+            sccs,
             pl,
         );
-        computer.compute(thread_pool, pl);
+        computer.compute_with_hook(thread_pool, pl, hook);
 
-        assert!(
-            computer.forward_iter.is_some(),
-            "Trying to build AllForward without all forward eccentricities computed"
-        );
+        if let Err(e) = computer.forward_eccentricities() {
+            panic!(
+                "Trying to build AllForward without all forward eccentricities computed: {}",
+                e
+            );
+        }
         assert!(
             computer.diameter_iterations.is_some(),
             "Trying to build AllForward without the diameter computed"
@@ -206,6 +323,9 @@ impl OutputLevel for AllForward {
             "Trying to build AllForward without the radius computed"
         );
 
+        // Forward eccentricities have been checked above
+        let center = computer.center().unwrap().into_boxed_slice();
+        let periphery = computer.periphery().unwrap().into_boxed_slice();
         let diameter = computer.diameter_low;
         let radius = computer.radius_high;
         let diametral_vertex = computer.diameter_vertex;
@@ -219,6 +339,8 @@ impl OutputLevel for AllForward {
             forward_eccentricities,
             diameter,
             radius,
+            center,
+            periphery,
             diametral_vertex,
             radial_vertex,
             radius_iterations,
@@ -228,37 +350,74 @@ impl OutputLevel for AllForward {
     }
 
     #[inline(always)]
-    fn compute_undirected(
+    fn compute_undirected_with_hook(
         graph: impl RandomAccessGraph + Sync,
         thread_pool: &ThreadPool,
         pl: &mut impl ProgressLog,
+        hook: impl FnMut(BoundsSnapshot),
     ) -> Self::UndirectedOutput {
-        All::compute_undirected(graph, thread_pool, pl)
+        All::compute_undirected_with_hook(graph, thread_pool, pl, hook)
     }
 }
 
 /// Computes both the diameter and the radius of the graph.
+///
+/// This is the output level to use if you are just interested in the radius
+/// and the diameter, as the computation usually requires far fewer visits than
+/// computing all eccentricities. The result is a
+/// [`RadiusDiameter`](super::outputs::directed::RadiusDiameter) structure
+/// containing the radius, the diameter, and two vertices witnessing them.
+///
+/// # Examples
+///
+/// ```
+/// use dsi_progress_logger::no_logging;
+/// use webgraph::graphs::vec_graph::VecGraph;
+/// use webgraph::labels::proj::Left;
+/// use webgraph_algo::algo::exact_sum_sweep::{OutputLevel, RadiusDiameter};
+/// use webgraph_algo::threads;
+///
+/// // A directed triangle with an arc leaving it
+/// let graph = Left(VecGraph::from_arc_list([(0, 1), (1, 2), (2, 0), (2, 3)]));
+/// let transpose = Left(VecGraph::from_arc_list([(1, 0), (2, 1), (0, 2), (3, 2)]));
+///
+/// let result = RadiusDiameter::compute_directed(
+///     &graph,
+///     &transpose,
+///     None,
+///     &threads![],
+///     no_logging![],
+/// );
+///
+/// // The farthest node from 0 is 3
+/// assert_eq!(result.diameter, 3);
+/// // Node 3 cannot reach the triangle, so it is not a radial vertex
+/// assert_eq!(result.radius, 2);
+/// ```
 pub struct RadiusDiameter;
 
 impl OutputLevel for RadiusDiameter {
     type DirectedOutput = dir_outputs::RadiusDiameter;
     type UndirectedOutput = undir_outputs::RadiusDiameter;
 
-    fn compute_directed(
+    fn compute_directed_with_hook(
         graph: impl RandomAccessGraph + Sync,
         transpose: impl RandomAccessGraph + Sync,
         radial_vertices: Option<AtomicBitVec>,
+        sccs: BasicSccs,
         thread_pool: &ThreadPool,
         pl: &mut impl ProgressLog,
+        hook: impl FnMut(BoundsSnapshot),
     ) -> Self::DirectedOutput {
-        let mut computer = DirExactSumSweepComputer::new_directed(
+        let mut computer = DirExactSumSweepComputer::new_directed_with_sccs(
             &graph,
             &transpose,
             Output::RadiusDiameter,
             radial_vertices,
+            sccs,
             pl,
         );
-        computer.compute(thread_pool, pl);
+        computer.compute_with_hook(thread_pool, pl, hook);
 
         assert!(
             computer.diameter_iterations.is_some(),
@@ -286,14 +445,15 @@ impl OutputLevel for RadiusDiameter {
         }
     }
 
-    fn compute_undirected(
+    fn compute_undirected_with_hook(
         graph: impl RandomAccessGraph + Sync,
         thread_pool: &ThreadPool,
         pl: &mut impl ProgressLog,
+        hook: impl FnMut(BoundsSnapshot),
     ) -> Self::UndirectedOutput {
         let mut computer =
             DirExactSumSweepComputer::new_undirected(&graph, Output::RadiusDiameter, pl);
-        computer.compute(thread_pool, pl);
+        computer.compute_with_hook(thread_pool, pl, hook);
 
         assert!(
             computer.diameter_iterations.is_some(),
@@ -329,21 +489,24 @@ impl OutputLevel for Diameter {
     type DirectedOutput = dir_outputs::Diameter;
     type UndirectedOutput = undir_outputs::Diameter;
 
-    fn compute_directed(
+    fn compute_directed_with_hook(
         graph: impl RandomAccessGraph + Sync,
         transpose: impl RandomAccessGraph + Sync,
         radial_vertices: Option<AtomicBitVec>,
+        sccs: BasicSccs,
         thread_pool: &ThreadPool,
         pl: &mut impl ProgressLog,
+        hook: impl FnMut(BoundsSnapshot),
     ) -> Self::DirectedOutput {
-        let mut computer = DirExactSumSweepComputer::new_directed(
+        let mut computer = DirExactSumSweepComputer::new_directed_with_sccs(
             &graph,
             &transpose,
             Output::Diameter,
             radial_vertices,
+            sccs,
             pl,
         );
-        computer.compute(thread_pool, pl);
+        computer.compute_with_hook(thread_pool, pl, hook);
 
         assert!(
             computer.diameter_iterations.is_some(),
@@ -361,13 +524,14 @@ impl OutputLevel for Diameter {
         }
     }
 
-    fn compute_undirected(
+    fn compute_undirected_with_hook(
         graph: impl RandomAccessGraph + Sync,
         thread_pool: &ThreadPool,
         pl: &mut impl ProgressLog,
+        hook: impl FnMut(BoundsSnapshot),
     ) -> Self::UndirectedOutput {
         let mut computer = DirExactSumSweepComputer::new_undirected(&graph, Output::Diameter, pl);
-        computer.compute(thread_pool, pl);
+        computer.compute_with_hook(thread_pool, pl, hook);
 
         assert!(
             computer.diameter_iterations.is_some(),
@@ -393,21 +557,24 @@ impl OutputLevel for Radius {
     type DirectedOutput = dir_outputs::Radius;
     type UndirectedOutput = undir_outputs::Radius;
 
-    fn compute_directed(
+    fn compute_directed_with_hook(
         graph: impl RandomAccessGraph + Sync,
         transpose: impl RandomAccessGraph + Sync,
         radial_vertices: Option<AtomicBitVec>,
+        sccs: BasicSccs,
         thread_pool: &ThreadPool,
         pl: &mut impl ProgressLog,
+        hook: impl FnMut(BoundsSnapshot),
     ) -> Self::DirectedOutput {
-        let mut computer = DirExactSumSweepComputer::new_directed(
+        let mut computer = DirExactSumSweepComputer::new_directed_with_sccs(
             &graph,
             &transpose,
             Output::Radius,
             radial_vertices,
+            sccs,
             pl,
         );
-        computer.compute(thread_pool, pl);
+        computer.compute_with_hook(thread_pool, pl, hook);
 
         assert!(
             computer.radius_iterations.is_some(),
@@ -425,13 +592,14 @@ impl OutputLevel for Radius {
         }
     }
 
-    fn compute_undirected(
+    fn compute_undirected_with_hook(
         graph: impl RandomAccessGraph + Sync,
         thread_pool: &ThreadPool,
         pl: &mut impl ProgressLog,
+        hook: impl FnMut(BoundsSnapshot),
     ) -> Self::UndirectedOutput {
         let mut computer = DirExactSumSweepComputer::new_undirected(&graph, Output::Radius, pl);
-        computer.compute(thread_pool, pl);
+        computer.compute_with_hook(thread_pool, pl, hook);
 
         assert!(
             computer.radius_iterations.is_some(),
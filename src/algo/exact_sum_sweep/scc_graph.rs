@@ -1,11 +1,24 @@
 use crate::algo::sccs::StronglyConnectedComponents;
+use anyhow::{ensure, Context, Result};
 use dsi_progress_logger::ProgressLog;
 use nonmax::NonMaxUsize;
 use rayon::prelude::*;
-use std::marker::PhantomData;
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    marker::PhantomData,
+    path::Path,
+};
 use webgraph::traits::RandomAccessGraph;
 
-#[derive(Clone, Debug)]
+/// The magic cookie at the start of every serialized [`SccGraph`].
+const MAGIC: &[u8; 8] = b"SCCGRAPH";
+/// The version of the serialization format.
+const VERSION: u64 = 1;
+
+/// A connection between two strongly connected components, witnessed by an arc
+/// of the graph.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SccGraphConnection {
     /// The component this connection is connected to
     pub target: usize,
@@ -15,6 +28,11 @@ pub struct SccGraphConnection {
     pub end: usize,
 }
 
+/// The graph of the strongly connected components of a graph.
+///
+/// For each pair of distinct components connected by at least one arc, the
+/// graph contains a single [connection](SccGraphConnection) associated with
+/// one of such arcs.
 pub struct SccGraph<G1: RandomAccessGraph, G2: RandomAccessGraph, C: StronglyConnectedComponents> {
     /// Slice of offsets where the `i`-th offset is how many elements to skip in [`Self::data`]
     /// in order to reach the first element relative to component `i`.
@@ -101,6 +119,175 @@ impl<G1: RandomAccessGraph, G2: RandomAccessGraph, C: StronglyConnectedComponent
         &self.data[offset..end]
     }
 
+    /// Returns the number of strongly connected components.
+    pub fn num_components(&self) -> usize {
+        self.segments_offset.len()
+    }
+
+    /// Returns the number of connections between strongly connected
+    /// components.
+    pub fn num_connections(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Saves the graph to a file.
+    ///
+    /// The file is a plain binary file in native endianness, so it can be
+    /// [loaded](Self::load) only on an architecture with the same endianness
+    /// and word size of the one that wrote it.
+    ///
+    /// # Arguments
+    /// * `path`: the path of the file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let mut writer = BufWriter::new(
+            File::create(path).with_context(|| format!("Cannot create {}", path.display()))?,
+        );
+
+        writer.write_all(MAGIC)?;
+        write_u64(&mut writer, VERSION)?;
+        write_u64(&mut writer, self.segments_offset.len() as u64)?;
+        write_u64(&mut writer, self.data.len() as u64)?;
+        for &offset in self.segments_offset.iter() {
+            write_u64(&mut writer, offset as u64)?;
+        }
+        for connection in self.data.iter() {
+            write_u64(&mut writer, connection.target as u64)?;
+            write_u64(&mut writer, connection.start as u64)?;
+            write_u64(&mut writer, connection.end as u64)?;
+        }
+
+        writer
+            .flush()
+            .with_context(|| format!("Cannot write {}", path.display()))
+    }
+
+    /// Loads a graph previously [saved](Self::save) to a file.
+    ///
+    /// The graph, its transpose and its strongly connected components are
+    /// used to check that the file refers to them: the number of components
+    /// must match, and the arc witnessing each connection must join nodes of
+    /// the right components.
+    ///
+    /// # Arguments
+    /// * `path`: the path of the file.
+    /// * `graph`: An immutable reference to the graph.
+    /// * `reversed_graph`: An immutable reference to `graph` transposed.
+    /// * `scc`: An immutable reference to a [`StronglyConnectedComponents`] instance.
+    pub fn load(path: impl AsRef<Path>, graph: &G1, reversed_graph: &G2, scc: &C) -> Result<Self> {
+        let path = path.as_ref();
+        let mut reader = BufReader::new(
+            File::open(path).with_context(|| format!("Cannot open {}", path.display()))?,
+        );
+
+        let mut magic = [0; 8];
+        reader
+            .read_exact(&mut magic)
+            .with_context(|| format!("Could not read magic cookie from {}", path.display()))?;
+        ensure!(
+            &magic == MAGIC,
+            "{} is not a strongly connected components graph",
+            path.display()
+        );
+        let version = read_u64(&mut reader)?;
+        ensure!(
+            version == VERSION,
+            "Unsupported version {}. Expected {}",
+            version,
+            VERSION
+        );
+
+        let num_components = read_u64(&mut reader)? as usize;
+        ensure!(
+            num_components == scc.num_components(),
+            "The file contains {} components, but there are {} strongly connected components",
+            num_components,
+            scc.num_components()
+        );
+        let num_connections = read_u64(&mut reader)? as usize;
+
+        let mut segments_offset = Vec::with_capacity(num_components);
+        for _ in 0..num_components {
+            let offset = read_u64(&mut reader)? as usize;
+            ensure!(
+                offset <= num_connections
+                    && segments_offset.last().map_or(true, |&last| last <= offset),
+                "Invalid offset {}",
+                offset
+            );
+            segments_offset.push(offset);
+        }
+
+        let num_nodes = graph.num_nodes();
+        ensure!(
+            reversed_graph.num_nodes() == num_nodes,
+            "The transpose has {} nodes, but the graph has {} nodes",
+            reversed_graph.num_nodes(),
+            num_nodes
+        );
+        let components = scc.components();
+        let mut data = Vec::with_capacity(num_connections);
+        for _ in 0..num_connections {
+            let target = read_u64(&mut reader)? as usize;
+            let start = read_u64(&mut reader)? as usize;
+            let end = read_u64(&mut reader)? as usize;
+            ensure!(
+                start < num_nodes && end < num_nodes && components[end] == target,
+                "Invalid connection from node {} to node {} of component {}",
+                start,
+                end,
+                target
+            );
+            data.push(SccGraphConnection { target, start, end });
+        }
+
+        let scc_graph = Self {
+            segments_offset: segments_offset.into_boxed_slice(),
+            data: data.into_boxed_slice(),
+            _marker: PhantomData,
+        };
+        for component in 0..num_components {
+            for connection in scc_graph.children(component) {
+                ensure!(
+                    components[connection.start] == component,
+                    "The connection from node {} does not start in component {}",
+                    connection.start,
+                    component
+                );
+            }
+        }
+
+        Ok(scc_graph)
+    }
+
+    /// Returns the strongly connected components in topological order.
+    ///
+    /// For each connection, the source component precedes the
+    /// [target](SccGraphConnection::target) component in the returned order.
+    pub fn topological_order(&self) -> Vec<usize> {
+        let num_components = self.num_components();
+        let mut indegree = vec![0_usize; num_components];
+        for connection in self.data.iter() {
+            indegree[connection.target] += 1;
+        }
+
+        let mut order: Vec<usize> = (0..num_components).filter(|&c| indegree[c] == 0).collect();
+        // Nodes in order[..next] have been processed
+        let mut next = 0;
+        while next < order.len() {
+            for connection in self.children(order[next]) {
+                indegree[connection.target] -= 1;
+                if indegree[connection.target] == 0 {
+                    order.push(connection.target);
+                }
+            }
+            next += 1;
+        }
+
+        debug_assert_eq!(order.len(), num_components);
+        order
+    }
+
     /// For each edge in the DAG of strongly connected components, finds a corresponding edge
     /// in the graph. This edge is used in the [`Self::all_cc_upper_bound`] method.
     ///
@@ -124,22 +311,17 @@ impl<G1: RandomAccessGraph, G2: RandomAccessGraph, C: StronglyConnectedComponent
 
         let number_of_scc = scc.num_components();
         let node_components = scc.components();
-        let mut vertices_in_scc = vec![Vec::new(); number_of_scc];
 
         let mut scc_graph = vec![Vec::new(); number_of_scc];
         let mut start_bridges = vec![Vec::new(); number_of_scc];
         let mut end_bridges = vec![Vec::new(); number_of_scc];
 
-        for (vertex, &component) in node_components.iter().enumerate() {
-            vertices_in_scc[component].push(vertex);
-        }
-
         {
             let mut child_components = Vec::new();
             let mut best_start = vec![None; number_of_scc];
             let mut best_end = vec![None; number_of_scc];
 
-            for (c, component) in vertices_in_scc.into_iter().enumerate() {
+            for (c, component) in scc.components_iter().enumerate() {
                 component.into_iter().for_each(|v| {
                     for succ in graph.successors(v) {
                         let succ_component = node_components[succ];
@@ -220,3 +402,14 @@ impl<G1: RandomAccessGraph, G2: RandomAccessGraph, C: StronglyConnectedComponent
         (lengths, connections)
     }
 }
+
+fn write_u64(writer: &mut impl Write, value: u64) -> Result<()> {
+    writer.write_all(&value.to_ne_bytes())?;
+    Ok(())
+}
+
+fn read_u64(reader: &mut impl Read) -> Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_ne_bytes(bytes))
+}
@@ -8,9 +8,15 @@ pub struct All {
     pub diameter: usize,
     /// The radius.
     pub radius: usize,
-    /// A vertex whose eccentricity equals the diameter.
+    /// The radial vertices whose eccentricity equals the radius.
+    pub center: Box<[usize]>,
+    /// The vertices whose eccentricity equals the diameter.
+    pub periphery: Box<[usize]>,
+    /// A vertex whose eccentricity equals the diameter. If the graph has no
+    /// nodes, it is 0 and does not denote a vertex.
     pub diametral_vertex: usize,
-    /// A vertex whose eccentrivity equals the radius.
+    /// A vertex whose eccentricity equals the radius. If there are no radial
+    /// vertices, it is 0 and does not denote a vertex.
     pub radial_vertex: usize,
     /// Number of iterations before the radius was found.
     pub radius_iterations: usize,
@@ -28,9 +34,11 @@ pub struct RadiusDiameter {
     pub diameter: usize,
     /// The radius.
     pub radius: usize,
-    /// A vertex whose eccentricity equals the diameter.
+    /// A vertex whose eccentricity equals the diameter. If the graph has no
+    /// nodes, it is 0 and does not denote a vertex.
     pub diametral_vertex: usize,
-    /// A vertex whose eccentrivity equals the radius.
+    /// A vertex whose eccentricity equals the radius. If there are no radial
+    /// vertices, it is 0 and does not denote a vertex.
     pub radial_vertex: usize,
     /// Number of iterations before the radius was found.
     pub radius_iterations: usize,
@@ -45,7 +53,8 @@ pub struct Diameter {
     /// The diameter.
     pub diameter: usize,
     /// The radius.
-    /// A vertex whose eccentricity equals the diameter.
+    /// A vertex whose eccentricity equals the diameter. If the graph has no
+    /// nodes, it is 0 and does not denote a vertex.
     pub diametral_vertex: usize,
     /// Number of iterations before the diameter was found.
     pub diameter_iterations: usize,
@@ -57,7 +66,8 @@ pub struct Diameter {
 pub struct Radius {
     /// The radius.
     pub radius: usize,
-    /// A vertex whose eccentrivity equals the radius.
+    /// A vertex whose eccentricity equals the radius. If there are no radial
+    /// vertices, it is 0 and does not denote a vertex.
     pub radial_vertex: usize,
     /// Number of iterations before the radius was found.
     pub radius_iterations: usize,
@@ -0,0 +1,327 @@
+//! Low-level serialization of the state of a [`HyperBall`](super::HyperBall)
+//! computation.
+//!
+//! A checkpoint is a plain binary file in native endianness, so it can be
+//! loaded only on an architecture with the same endianness and word size of
+//! the one that wrote it.
+
+use super::Precision;
+use anyhow::{bail, ensure, Context, Result};
+use std::io::{Read, Write};
+use std::sync::atomic::Ordering;
+use sux::{bits::AtomicBitVec, traits::Word};
+
+/// The magic cookie at the start of every checkpoint.
+const MAGIC: &[u8; 8] = b"HYPBCKPT";
+/// The version of the checkpoint format.
+const VERSION: u64 = 2;
+
+/// The state of a [`HyperBall`](super::HyperBall) computation, excluding the
+/// counter arrays, that are read directly into their backends.
+pub(super) struct Checkpoint {
+    pub(super) iteration: usize,
+    pub(super) completed: bool,
+    pub(super) systolic: bool,
+    pub(super) local: bool,
+    pub(super) pre_local: bool,
+    pub(super) modified_counters: u64,
+    pub(super) last: f64,
+    pub(super) relative_increment: f64,
+    pub(super) neighbourhood_function: Vec<f64>,
+    pub(super) curr_modified: AtomicBitVec,
+    pub(super) next_modified: AtomicBitVec,
+    pub(super) must_be_checked: AtomicBitVec,
+    pub(super) next_must_be_checked: AtomicBitVec,
+    pub(super) local_checklist: Vec<usize>,
+    pub(super) local_next_must_be_checked: Vec<usize>,
+    pub(super) sum_of_dists: Option<Vec<f64>>,
+    pub(super) sum_of_inv_dists: Option<Vec<f64>>,
+    pub(super) discounted_centralities: Vec<Vec<f64>>,
+}
+
+/// Writes the header of a checkpoint.
+///
+/// # Arguments
+/// * `writer`: where to write the header.
+/// * `num_nodes`: the number of nodes of the graph.
+/// * `logic`: a description of the counter logic, used to check that the
+///   checkpoint is resumed with the same counter geometry.
+pub(super) fn write_header<W: Word>(
+    writer: &mut impl Write,
+    num_nodes: usize,
+    logic: &str,
+) -> Result<()> {
+    writer.write_all(MAGIC)?;
+    write_u64(writer, VERSION)?;
+    write_u64(writer, std::mem::size_of::<W>() as u64)?;
+    write_u64(writer, num_nodes as u64)?;
+    write_u64(writer, logic.len() as u64)?;
+    writer.write_all(logic.as_bytes())?;
+    Ok(())
+}
+
+/// Reads and validates the header of a checkpoint.
+///
+/// # Arguments
+/// * `reader`: where to read the header from.
+/// * `num_nodes`: the expected number of nodes of the graph.
+/// * `logic`: the expected description of the counter logic.
+pub(super) fn read_header<W: Word>(
+    reader: &mut impl Read,
+    num_nodes: usize,
+    logic: &str,
+) -> Result<()> {
+    let mut magic = [0; 8];
+    reader
+        .read_exact(&mut magic)
+        .with_context(|| "Could not read checkpoint magic cookie")?;
+    ensure!(&magic == MAGIC, "The file is not a HyperBall checkpoint");
+    let version = read_u64(reader)?;
+    ensure!(
+        version == VERSION,
+        "Unsupported checkpoint version {}. Expected {}",
+        version,
+        VERSION
+    );
+    let word_size = read_u64(reader)? as usize;
+    ensure!(
+        word_size == std::mem::size_of::<W>(),
+        "The checkpoint uses words of {} bytes, but the counter arrays use words of {} bytes",
+        word_size,
+        std::mem::size_of::<W>()
+    );
+    let stored_num_nodes = read_u64(reader)? as usize;
+    ensure!(
+        stored_num_nodes == num_nodes,
+        "The checkpoint refers to a graph with {} nodes, but the graph has {} nodes",
+        stored_num_nodes,
+        num_nodes
+    );
+    let logic_len = read_u64(reader)? as usize;
+    let mut stored_logic = vec![0; logic_len];
+    reader.read_exact(&mut stored_logic)?;
+    let stored_logic = String::from_utf8(stored_logic)
+        .with_context(|| "The counter logic description is not valid UTF-8")?;
+    ensure!(
+        stored_logic == logic,
+        "The checkpoint was written using a different counter logic ({}). Current logic is {}",
+        stored_logic,
+        logic
+    );
+    Ok(())
+}
+
+/// The settings of a [`HyperBall`](super::HyperBall) computation that
+/// must not change when resuming it.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct Settings {
+    pub(super) reachable_only: bool,
+    pub(super) weights: Option<Vec<usize>>,
+    pub(super) seed: Option<u64>,
+    pub(super) centrality_precision: Precision,
+}
+
+/// Writes the settings of a computation.
+///
+/// # Arguments
+/// * `writer`: where to write the settings.
+/// * `reachable_only`: whether only the sizes of the reachable sets are
+///   computed.
+/// * `weights`: the node weights, if any.
+/// * `seed`: the seed used to generate the elements of weighted nodes, if any.
+/// * `centrality_precision`: the precision of the centrality accumulators.
+pub(super) fn write_settings(
+    writer: &mut impl Write,
+    reachable_only: bool,
+    weights: Option<&[usize]>,
+    seed: Option<u64>,
+    centrality_precision: Precision,
+) -> Result<()> {
+    write_bool(writer, reachable_only)?;
+    write_bool(writer, weights.is_some())?;
+    if let Some(w) = weights {
+        write_usizes(writer, w)?;
+    }
+    write_bool(writer, seed.is_some())?;
+    write_u64(writer, seed.unwrap_or(0))?;
+    write_u64(
+        writer,
+        match centrality_precision {
+            Precision::F32 => 32,
+            Precision::F64 => 64,
+        },
+    )
+}
+
+/// Reads the settings of a computation written by [`write_settings`].
+pub(super) fn read_settings(reader: &mut impl Read) -> Result<Settings> {
+    let reachable_only = read_bool(reader)?;
+    let weights = if read_bool(reader)? {
+        Some(read_usizes(reader)?)
+    } else {
+        None
+    };
+    let has_seed = read_bool(reader)?;
+    let seed = read_u64(reader)?;
+    let centrality_precision = match read_u64(reader)? {
+        32 => Precision::F32,
+        64 => Precision::F64,
+        bits => bail!("Invalid centrality precision of {} bits", bits),
+    };
+    Ok(Settings {
+        reachable_only,
+        weights,
+        seed: has_seed.then_some(seed),
+        centrality_precision,
+    })
+}
+
+pub(super) fn write_u64(writer: &mut impl Write, value: u64) -> Result<()> {
+    writer.write_all(&value.to_ne_bytes())?;
+    Ok(())
+}
+
+pub(super) fn read_u64(reader: &mut impl Read) -> Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_ne_bytes(bytes))
+}
+
+pub(super) fn write_bool(writer: &mut impl Write, value: bool) -> Result<()> {
+    write_u64(writer, value as u64)
+}
+
+pub(super) fn read_bool(reader: &mut impl Read) -> Result<bool> {
+    Ok(read_u64(reader)? != 0)
+}
+
+pub(super) fn write_f64(writer: &mut impl Write, value: f64) -> Result<()> {
+    write_u64(writer, value.to_bits())
+}
+
+pub(super) fn read_f64(reader: &mut impl Read) -> Result<f64> {
+    Ok(f64::from_bits(read_u64(reader)?))
+}
+
+pub(super) fn write_f64s(writer: &mut impl Write, values: &[f64]) -> Result<()> {
+    write_u64(writer, values.len() as u64)?;
+    for &v in values {
+        write_f64(writer, v)?;
+    }
+    Ok(())
+}
+
+pub(super) fn read_f64s(reader: &mut impl Read) -> Result<Vec<f64>> {
+    let len = read_u64(reader)? as usize;
+    let mut values = Vec::with_capacity(len);
+    for _ in 0..len {
+        values.push(read_f64(reader)?);
+    }
+    Ok(values)
+}
+
+pub(super) fn write_optional_f64s(writer: &mut impl Write, values: Option<&[f64]>) -> Result<()> {
+    write_bool(writer, values.is_some())?;
+    if let Some(v) = values {
+        write_f64s(writer, v)?;
+    }
+    Ok(())
+}
+
+pub(super) fn read_optional_f64s(reader: &mut impl Read) -> Result<Option<Vec<f64>>> {
+    if read_bool(reader)? {
+        Ok(Some(read_f64s(reader)?))
+    } else {
+        Ok(None)
+    }
+}
+
+pub(super) fn write_usizes(writer: &mut impl Write, values: &[usize]) -> Result<()> {
+    write_u64(writer, values.len() as u64)?;
+    for &v in values {
+        write_u64(writer, v as u64)?;
+    }
+    Ok(())
+}
+
+pub(super) fn read_usizes(reader: &mut impl Read) -> Result<Vec<usize>> {
+    let len = read_u64(reader)? as usize;
+    let mut values = Vec::with_capacity(len);
+    for _ in 0..len {
+        values.push(read_u64(reader)? as usize);
+    }
+    Ok(values)
+}
+
+/// Writes the first `len` bits of an [`AtomicBitVec`] packing them in 64-bit
+/// words.
+pub(super) fn write_bitvec(writer: &mut impl Write, bits: &AtomicBitVec, len: usize) -> Result<()> {
+    write_u64(writer, len as u64)?;
+    let mut word = 0_u64;
+    for i in 0..len {
+        if bits[i] {
+            word |= 1 << (i % 64);
+        }
+        if i % 64 == 63 {
+            write_u64(writer, word)?;
+            word = 0;
+        }
+    }
+    if len % 64 != 0 {
+        write_u64(writer, word)?;
+    }
+    Ok(())
+}
+
+/// Reads an [`AtomicBitVec`] written by [`write_bitvec`], checking that it
+/// has the expected length.
+pub(super) fn read_bitvec(reader: &mut impl Read, expected_len: usize) -> Result<AtomicBitVec> {
+    let len = read_u64(reader)? as usize;
+    ensure!(
+        len == expected_len,
+        "Bit vector should have length {}. Got {}",
+        expected_len,
+        len
+    );
+    let bits = AtomicBitVec::new(len);
+    let mut word = 0;
+    for i in 0..len {
+        if i % 64 == 0 {
+            word = read_u64(reader)?;
+        }
+        if word & (1 << (i % 64)) != 0 {
+            bits.set(i, true, Ordering::Relaxed);
+        }
+    }
+    Ok(bits)
+}
+
+/// Writes a slice of words as raw bytes.
+pub(super) fn write_words<W: Word>(writer: &mut impl Write, words: &[W]) -> Result<()> {
+    write_u64(writer, words.len() as u64)?;
+    // SAFETY: words are unsigned integers, so they have no padding and
+    // can be viewed as bytes.
+    let bytes = unsafe {
+        std::slice::from_raw_parts(words.as_ptr() as *const u8, std::mem::size_of_val(words))
+    };
+    writer.write_all(bytes)?;
+    Ok(())
+}
+
+/// Reads a slice of words written by [`write_words`] into `words`, checking
+/// that the stored length matches the length of `words`.
+pub(super) fn read_words<W: Word>(reader: &mut impl Read, words: &mut [W]) -> Result<()> {
+    let len = read_u64(reader)? as usize;
+    ensure!(
+        len == words.len(),
+        "Counter array backend should have {} words. Got {}",
+        words.len(),
+        len
+    );
+    // SAFETY: words are unsigned integers, so every bit pattern is valid.
+    let bytes = unsafe {
+        std::slice::from_raw_parts_mut(words.as_mut_ptr() as *mut u8, std::mem::size_of_val(words))
+    };
+    reader.read_exact(bytes)?;
+    Ok(())
+}
@@ -1,16 +1,31 @@
+use super::checkpoint::{self, Checkpoint};
 use crate::utils::traits::CounterMut;
 use crate::{prelude::*, utils::*};
 use anyhow::{bail, ensure, Context, Result};
-use common_traits::Number;
 use dsi_progress_logger::ProgressLog;
 use kahan::KahanSum;
-use rand::random;
+use rand::{random, rngs::StdRng, Rng, SeedableRng};
 use rayon::{prelude::*, ThreadPool};
+use std::borrow::Cow;
 use std::hash::{BuildHasherDefault, DefaultHasher};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
 use std::sync::{atomic::*, Mutex};
-use sux::{bits::AtomicBitVec, traits::Succ};
+use std::time::{Duration, Instant};
+use sux::{
+    bits::AtomicBitVec,
+    traits::{Succ, Word},
+};
 use webgraph::traits::{RandomAccessGraph, SequentialLabeling};
 
+/// A discount function together with the sink receiving its contributions.
+///
+/// See [`HyperBallBuilder::discount_function_streaming`].
+type StreamingDiscountFunction<'a> = (
+    Box<dyn Fn(usize) -> f64 + Sync + 'a>,
+    Box<dyn Fn(usize, f64) + Sync + 'a>,
+);
+
 /// Builder for [`HyperBall`].
 ///
 /// Create a builder with [`HyperBallBuilder::new`], edit parameters with
@@ -28,6 +43,8 @@ pub struct HyperBallBuilder<
     graph: &'a G1,
     /// The transpose of `graph`, if any.
     transpose: Option<&'a G2>,
+    /// The transpose of `graph` built in memory, if any.
+    transpose_in_memory: Option<CsrGraph>,
     /// The outdegree cumulative function of the graph.
     cumul_outdegree: &'a D,
     /// Whether to compute the sum of distances (e.g., for closeness centrality).
@@ -36,15 +53,29 @@ pub struct HyperBallBuilder<
     sum_of_inverse_distances: bool,
     /// Custom discount functions whose sum should be computed.
     discount_functions: Vec<Box<dyn Fn(usize) -> f64 + Sync + 'a>>,
+    /// Custom discount functions whose contributions are streamed to a sink.
+    streaming_discount_functions: Vec<StreamingDiscountFunction<'a>>,
+    /// The precision of the centrality accumulators.
+    centrality_precision: Precision,
     /// The arc granularity.
     arc_granularity: usize,
+    /// Whether to adapt the granularity to the number of modified counters.
+    adaptive_granularity: bool,
+    /// Whether to compute only the sizes of the reachable sets.
+    reachable_only: bool,
+    /// Whether to check that discount functions are finite when building.
+    validate_discount_functions: bool,
     /// Integer weights for the nodes, if any.
-    weights: Option<&'a [usize]>,
+    weights: Option<Cow<'a, [usize]>>,
+    /// The seed used to generate the elements of weighted nodes, if any.
+    seed: Option<u64>,
     /// A first array of counters.
     array_0: A,
     /// A second array of counters of the same length and with the same logic of
     /// `array_0`.
     array_1: A,
+    /// The state to resume from, if any.
+    checkpoint: Option<Checkpoint>,
     _marker: std::marker::PhantomData<L>,
 }
 
@@ -80,7 +111,8 @@ impl<
     /// * `weights`: the weights to use. If [`None`] every node is assumed to be
     ///   of weight equal to 1.
     /// * `mmap_options`: the options to use for the backend of the counter
-    ///   arrays as a [`TempMmapOptions`].
+    ///   arrays as a [`TempMmapOptions`]. They cannot be
+    ///   [`TempMmapOptions::PersistentFile`].
     pub fn with_hyper_log_log(
         graph: &'a G1,
         transposed: Option<&'a G2>,
@@ -89,6 +121,9 @@ impl<
         weights: Option<&'a [usize]>,
         mmap_options: TempMmapOptions,
     ) -> Result<Self> {
+        mmap_options
+            .ensure_multi_slice()
+            .with_context(|| "Could not initialize the counter arrays")?;
         let num_elements = if let Some(w) = weights {
             ensure!(
                 w.len() == graph.num_nodes(),
@@ -99,7 +134,8 @@ impl<
             graph.num_nodes()
         };
 
-        let logic = HyperLogLogBuilder::new(num_elements)
+        // The logic needs a positive bound even if there is nothing to count
+        let logic = HyperLogLogBuilder::new(num_elements.max(1))
             .log_2_num_reg(log2m)
             .build()
             .with_context(|| "Could not build hyperloglog logic")?;
@@ -113,14 +149,22 @@ impl<
         Ok(Self {
             graph,
             transpose: transposed,
+            transpose_in_memory: None,
             cumul_outdegree: cumul_outdeg,
             sum_of_distances: false,
             sum_of_inverse_distances: false,
             discount_functions: Vec::new(),
+            streaming_discount_functions: Vec::new(),
+            validate_discount_functions: false,
+            centrality_precision: Precision::default(),
             arc_granularity: Self::DEFAULT_GRANULARITY,
-            weights,
+            adaptive_granularity: true,
+            reachable_only: false,
+            weights: weights.map(Cow::Borrowed),
+            seed: None,
             array_0,
             array_1,
+            checkpoint: None,
             _marker: std::marker::PhantomData,
         })
     }
@@ -161,14 +205,22 @@ impl<
         Self {
             graph,
             transpose: None,
+            transpose_in_memory: None,
             cumul_outdegree: cumul_outdeg,
             sum_of_distances: false,
             sum_of_inverse_distances: false,
             discount_functions: Vec::new(),
+            streaming_discount_functions: Vec::new(),
+            validate_discount_functions: false,
+            centrality_precision: Precision::default(),
             arc_granularity: Self::DEFAULT_GRANULARITY,
+            adaptive_granularity: true,
+            reachable_only: false,
             weights: None,
+            seed: None,
             array_0,
             array_1,
+            checkpoint: None,
             _marker: std::marker::PhantomData,
         }
     }
@@ -222,50 +274,188 @@ impl<
             graph.num_nodes(),
             transpose.num_nodes()
         );
-        assert_eq!(
-            transpose.num_arcs(),
-            graph.num_arcs(),
-            "the transpose should have same number of nodes of the graph ({}). Got {}.",
-            graph.num_arcs(),
-            transpose.num_arcs()
-        );
+        if transpose.num_arcs() != graph.num_arcs() {
+            // Self-loops do not affect systolic iterations, so the two
+            // graphs are only required to agree on the remaining arcs
+            let graph_arcs = graph.num_arcs() - num_self_loops(graph) as u64;
+            let transpose_arcs = transpose.num_arcs() - num_self_loops(transpose) as u64;
+            assert_eq!(
+                transpose_arcs,
+                graph_arcs,
+                "the transpose should have same number of arcs of the graph, self-loops excluded ({}). Got {}.",
+                graph_arcs,
+                transpose_arcs
+            );
+        }
         debug_assert!(
-            check_transposed(graph, transpose),
+            check_transposed_up_to_loops(graph, transpose),
             "the transpose should be the transpose of the graph"
         );
         Self {
             graph,
             transpose: Some(transpose),
+            transpose_in_memory: None,
             cumul_outdegree: cumul_outdeg,
             sum_of_distances: false,
             sum_of_inverse_distances: false,
             discount_functions: Vec::new(),
+            streaming_discount_functions: Vec::new(),
+            validate_discount_functions: false,
+            centrality_precision: Precision::default(),
             arc_granularity: Self::DEFAULT_GRANULARITY,
+            adaptive_granularity: true,
+            reachable_only: false,
             weights: None,
+            seed: None,
             array_0,
             array_1,
+            checkpoint: None,
             _marker: std::marker::PhantomData,
         }
     }
 
+    /// Builds in memory the transpose of the graph, so that systolic
+    /// iterations can be performed even if no transpose was provided.
+    ///
+    /// The transpose is stored as a [`CsrGraph`] whose slices are created
+    /// using `mmap_options`: for a graph with *n* nodes and *m* arcs it
+    /// requires (*n* + 1 + *m*) · `size_of::<usize>()` bytes, plus *n* ·
+    /// `size_of::<usize>()` temporary bytes during the construction. Systolic
+    /// iterations usually repay this cost, as they just update the
+    /// predecessors of the counters modified in the previous iteration.
+    ///
+    /// If a transpose was already provided, this method does nothing.
+    ///
+    /// # Arguments
+    /// * `mmap_options`: the options to use for the backend of the transpose
+    ///   as a [`TempMmapOptions`].
+    /// * `pl`: A progress logger.
+    pub fn build_transpose_in_memory(
+        mut self,
+        mmap_options: TempMmapOptions,
+        pl: &mut impl ProgressLog,
+    ) -> Result<Self> {
+        if self.transpose.is_none() {
+            self.transpose_in_memory = Some(
+                CsrGraph::transpose(self.graph, mmap_options, pl)
+                    .with_context(|| "Could not build the transpose in memory")?,
+            );
+        }
+        Ok(self)
+    }
+
     /// Sets whether to compute the sum of distances.
-    pub fn sum_of_distances(mut self, do_sum_of_distances: bool) -> Self {
+    ///
+    /// The sum of distances is needed by [`HyperBall::sum_of_distances`],
+    /// [`HyperBall::closeness_centrality`], [`HyperBall::lin_centrality`] and
+    /// [`HyperBall::nieminen_centrality`].
+    ///
+    /// # Examples
+    /// ```
+    /// use dsi_progress_logger::no_logging;
+    /// use sux::prelude::*;
+    /// use webgraph::labels::Left;
+    /// use webgraph::prelude::VecGraph;
+    /// use webgraph::traits::{RandomAccessGraph, SequentialLabeling};
+    /// use webgraph_algo::{algo::hyperball::HyperBallBuilder, threads};
+    /// use webgraph_algo::utils::{hyper_log_log::HyperLogLogBuilder, SliceCounterArray};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let graph = Left(VecGraph::from_arc_list([(0, 1), (1, 2), (2, 0), (2, 3)]));
+    /// let transpose = Left(VecGraph::from_arc_list([(1, 0), (2, 1), (0, 2), (3, 2)]));
+    ///
+    /// // The outdegree cumulative function of the graph
+    /// let mut builder = EliasFanoBuilder::new(graph.num_nodes() + 1, graph.num_arcs() as usize);
+    /// let mut cumul = 0;
+    /// builder.push(cumul);
+    /// for node in 0..graph.num_nodes() {
+    ///     cumul += graph.outdegree(node);
+    ///     builder.push(cumul);
+    /// }
+    /// let cumulative = builder.build_with_seq_and_dict();
+    ///
+    /// let logic = HyperLogLogBuilder::new(graph.num_nodes())
+    ///     .log_2_num_reg(6)
+    ///     .build()?;
+    /// let mut hyperball = HyperBallBuilder::with_transpose(
+    ///     &graph,
+    ///     &transpose,
+    ///     &cumulative,
+    ///     SliceCounterArray::new(logic.clone(), graph.num_nodes())?,
+    ///     SliceCounterArray::new(logic, graph.num_nodes())?,
+    /// )
+    /// .with_sum_of_distances(true)
+    /// .with_sum_of_inverse_distances(true)
+    /// .build(no_logging![]);
+    /// hyperball.run_until_done(&threads![], no_logging![])?;
+    ///
+    /// assert_eq!(hyperball.closeness_centrality()?.len(), graph.num_nodes());
+    /// assert_eq!(hyperball.harmonic_centralities()?.len(), graph.num_nodes());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_sum_of_distances(mut self, do_sum_of_distances: bool) -> Self {
         self.sum_of_distances = do_sum_of_distances;
         self
     }
 
     /// Sets whether to compute the sum of inverse distances.
-    pub fn sum_of_inverse_distances(mut self, do_sum_of_inverse_distances: bool) -> Self {
+    ///
+    /// The sum of inverse distances is needed by
+    /// [`HyperBall::harmonic_centralities`].
+    pub fn with_sum_of_inverse_distances(mut self, do_sum_of_inverse_distances: bool) -> Self {
         self.sum_of_inverse_distances = do_sum_of_inverse_distances;
         self
     }
 
+    /// Alias for [`with_sum_of_distances`](Self::with_sum_of_distances).
+    pub fn sum_of_distances(self, do_sum_of_distances: bool) -> Self {
+        self.with_sum_of_distances(do_sum_of_distances)
+    }
+
+    /// Alias for
+    /// [`with_sum_of_inverse_distances`](Self::with_sum_of_inverse_distances).
+    pub fn sum_of_inverse_distances(self, do_sum_of_inverse_distances: bool) -> Self {
+        self.with_sum_of_inverse_distances(do_sum_of_inverse_distances)
+    }
+
+    /// Sets the precision of the accumulators used to compute the sum of
+    /// distances, the sum of inverse distances, and the discounted
+    /// centralities.
+    ///
+    /// Using [`Precision::F32`] halves the memory used by the accumulators,
+    /// at the price of a loss of precision. Accessors return `f64` values
+    /// in any case. The default is [`Precision::F64`].
+    pub fn centrality_precision(mut self, precision: Precision) -> Self {
+        self.centrality_precision = precision;
+        self
+    }
+
     /// Sets the base granularity used in the parallel phases of the iterations.
-    pub fn arc_granulatity(mut self, arc_granularity: usize) -> Self {
+    pub fn arc_granularity(mut self, arc_granularity: usize) -> Self {
         self.arc_granularity = arc_granularity;
         self
     }
 
+    /// Sets the base granularity used in the parallel phases of the iterations.
+    #[deprecated(note = "use arc_granularity")]
+    pub fn arc_granulatity(self, arc_granularity: usize) -> Self {
+        self.arc_granularity(arc_granularity)
+    }
+
+    /// Sets whether the granularity should be adapted at each iteration.
+    ///
+    /// By default, when using more than one thread, the base granularity is
+    /// increased at each iteration proportionally to the ratio between the
+    /// number of nodes and the number of counters modified in the previous
+    /// iteration. If adaptation is disabled, the base granularity set with
+    /// [`arc_granularity`](Self::arc_granularity) is used in every
+    /// iteration, which is useful for benchmarking and profiling.
+    pub fn adaptive_granularity(mut self, adaptive_granularity: bool) -> Self {
+        self.adaptive_granularity = adaptive_granularity;
+        self
+    }
+
     /// Sets optional weights for the nodes of the graph.
     ///
     /// # Arguments
@@ -275,7 +465,62 @@ impl<
         if let Some(w) = weights {
             assert_eq!(w.len(), self.graph.num_nodes());
         }
-        self.weights = weights;
+        self.weights = weights.map(Cow::Borrowed);
+        self
+    }
+
+    /// Sets optional real weights for the nodes of the graph.
+    ///
+    /// Counters can only contain an integer number of elements, so each
+    /// weight is rounded to the nearest integer and then used as an integer
+    /// weight set with [`weights`](Self::weights). As a consequence, ball
+    /// sizes and centralities are proportional to the weights only up to
+    /// rounding errors, and nodes of weight smaller than one half are given
+    /// weight zero: weights should be scaled by the caller so that rounding
+    /// errors are negligible. Since scaling all weights by a constant scales
+    /// all sums of distances by the same constant, rankings by centrality are
+    /// not affected by the scaling. As with integer weights, the counter
+    /// logic must be built for a number of elements equal to the sum of the
+    /// rounded weights.
+    ///
+    /// # Arguments
+    /// * `weights`: weights to use for the nodes. If [`None`], every node is
+    ///   assumed to be of weight equal to 1.
+    ///
+    /// # Panics
+    /// Panics if the number of weights is not the number of nodes, or if a
+    /// weight is negative or not finite.
+    pub fn weights_f64(mut self, weights: Option<&[f64]>) -> Self {
+        self.weights = weights.map(|w| {
+            assert_eq!(w.len(), self.graph.num_nodes());
+            Cow::Owned(
+                w.par_iter()
+                    .map(|&weight| {
+                        assert!(
+                            weight.is_finite() && weight >= 0.0,
+                            "weights should be finite and nonnegative, found {}",
+                            weight
+                        );
+                        weight.round() as usize
+                    })
+                    .collect(),
+            )
+        });
+        self
+    }
+
+    /// Sets the seed used to generate the random elements added to the
+    /// counters of weighted nodes.
+    ///
+    /// The elements of each node are generated by a pseudorandom number
+    /// generator whose seed is derived from `seed` and from the node index, so
+    /// the results are reproducible. If no seed is set, elements are generated
+    /// using the thread-local random number generator.
+    ///
+    /// # Arguments
+    /// * `seed`: the base seed.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
         self
     }
 
@@ -289,9 +534,80 @@ impl<
         self
     }
 
-    /// Removes all custom discount functions.
+    /// Adds a new discount function whose contributions are passed to a sink
+    /// instead of being accumulated.
+    ///
+    /// Whenever the estimated number of nodes at distance `d` from a node `x`
+    /// is a positive value `n`, `sink(x, n * discount_function(d))` is called;
+    /// the sum of the values passed to the sink for `x` is thus the
+    /// discounted centrality of `x` that
+    /// [`discount_function`](Self::discount_function) would compute, but no
+    /// memory is allocated to store it. This makes it possible, for example,
+    /// to write contributions directly to external storage.
+    ///
+    /// The sink is called by the threads of the thread pool passed to
+    /// [`HyperBall::run`] while they process nodes, so it must be [`Sync`],
+    /// and it must handle concurrent calls for different nodes. Calls for
+    /// the same node are never concurrent, but they happen from different
+    /// threads at different iterations. Since the sink is called within the
+    /// main loop of each iteration, it should be fast.
+    ///
+    /// Contributions are streamed again if the computation is restarted,
+    /// whereas contributions streamed before a
+    /// [checkpoint](HyperBall::save_state) are not streamed again when
+    /// [resuming](Self::resume_from) from it.
+    ///
+    /// # Arguments
+    /// * `discount_function`: the discount function.
+    /// * `sink`: the function receiving a node and a contribution to its
+    ///   discounted centrality.
+    pub fn discount_function_streaming(
+        mut self,
+        discount_function: impl Fn(usize) -> f64 + Sync + 'a,
+        sink: impl Fn(usize, f64) + Sync + 'a,
+    ) -> Self {
+        self.streaming_discount_functions
+            .push((Box::new(discount_function), Box::new(sink)));
+        self
+    }
+
+    /// Removes all custom discount functions, including streaming ones.
     pub fn no_discount_function(mut self) -> Self {
         self.discount_functions.clear();
+        self.streaming_discount_functions.clear();
+        self
+    }
+
+    /// Sets whether to check, when building, that discount functions are
+    /// finite for all distances from 1 to the number of nodes.
+    ///
+    /// A discount function returning NaN or an infinite value for some
+    /// distance would silently spoil the corresponding discounted centrality.
+    /// If validation is enabled, [`try_build`](HyperBallBuilder::try_build)
+    /// evaluates each discount function on all possible distances and
+    /// returns an error if a value is not finite. Validation is disabled by
+    /// default, as its cost is linear in the number of nodes for each
+    /// discount function.
+    ///
+    /// # Arguments
+    /// * `validate`: whether to validate discount functions.
+    pub fn validate_discount_functions(mut self, validate: bool) -> Self {
+        self.validate_discount_functions = validate;
+        self
+    }
+
+    /// Configures a lean run computing only the sizes of the reachable sets.
+    ///
+    /// No centrality is computed, independently of the other settings of
+    /// the builder, and the neighbourhood function is not accumulated, so
+    /// the resulting [`HyperBall`] supports only
+    /// [`reachable_nodes`](HyperBall::reachable_nodes) and
+    /// [`reachable_nodes_from`](HyperBall::reachable_nodes_from). Since the
+    /// relative increment of the neighbourhood function is not available,
+    /// the threshold passed to [`run`](HyperBall::run) is ignored, and the
+    /// computation stops when no counters are modified.
+    pub fn reachable_only(mut self) -> Self {
+        self.reachable_only = true;
         self
     }
 }
@@ -305,25 +621,246 @@ impl<
         A: CounterArrayMut<L>,
     > HyperBallBuilder<'a, G1, G2, D, L, A>
 {
+    /// Resumes a computation from a checkpoint written by
+    /// [`HyperBall::save_state`].
+    ///
+    /// The counter arrays of the builder are overwritten with the ones stored
+    /// in the checkpoint, and [`HyperBall::run`] will continue from the stored
+    /// iteration rather than starting a new computation. This method must be
+    /// called after configuring the builder in the same way as the one that
+    /// built the checkpointed instance (same graph, counter logic,
+    /// centralities, number of discount functions, node weights and seed,
+    /// centrality precision, and [reachable-only](Self::reachable_only)
+    /// setting); otherwise, an error is returned.
+    ///
+    /// # Arguments
+    /// * `path`: the path of the checkpoint.
+    pub fn resume_from<W: Word>(mut self, path: impl AsRef<Path>) -> Result<Self>
+    where
+        A: AsMut<[W]>,
+    {
+        let path = path.as_ref();
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Could not open checkpoint {}", path.display()))?;
+        let mut reader = BufReader::new(file);
+        let num_nodes = self.graph.num_nodes();
+
+        checkpoint::read_header::<W>(&mut reader, num_nodes, &self.array_0.logic().to_string())
+            .with_context(|| format!("Invalid checkpoint {}", path.display()))?;
+        let settings = checkpoint::read_settings(&mut reader)
+            .with_context(|| format!("Invalid checkpoint {}", path.display()))?;
+        ensure!(
+            settings.reachable_only == self.reachable_only,
+            "The checkpoint and the builder disagree on whether to compute only the sizes of reachable sets"
+        );
+        ensure!(
+            settings.weights.as_deref() == self.weights.as_deref(),
+            "The checkpoint and the builder use different node weights"
+        );
+        ensure!(
+            settings.seed == self.seed,
+            "The checkpoint uses seed {:?}, but the builder uses seed {:?}",
+            settings.seed,
+            self.seed
+        );
+        ensure!(
+            settings.centrality_precision == self.centrality_precision,
+            "The checkpoint uses {:?} centrality precision, but the builder uses {:?}",
+            settings.centrality_precision,
+            self.centrality_precision
+        );
+
+        let state = Checkpoint {
+            iteration: checkpoint::read_u64(&mut reader)? as usize,
+            completed: checkpoint::read_bool(&mut reader)?,
+            systolic: checkpoint::read_bool(&mut reader)?,
+            local: checkpoint::read_bool(&mut reader)?,
+            pre_local: checkpoint::read_bool(&mut reader)?,
+            modified_counters: checkpoint::read_u64(&mut reader)?,
+            last: checkpoint::read_f64(&mut reader)?,
+            relative_increment: checkpoint::read_f64(&mut reader)?,
+            neighbourhood_function: checkpoint::read_f64s(&mut reader)?,
+            curr_modified: checkpoint::read_bitvec(&mut reader, num_nodes)?,
+            next_modified: checkpoint::read_bitvec(&mut reader, num_nodes)?,
+            must_be_checked: checkpoint::read_bitvec(&mut reader, num_nodes)?,
+            next_must_be_checked: checkpoint::read_bitvec(&mut reader, num_nodes)?,
+            local_checklist: checkpoint::read_usizes(&mut reader)?,
+            local_next_must_be_checked: checkpoint::read_usizes(&mut reader)?,
+            sum_of_dists: checkpoint::read_optional_f64s(&mut reader)?,
+            sum_of_inv_dists: checkpoint::read_optional_f64s(&mut reader)?,
+            discounted_centralities: {
+                let num_discount_functions = checkpoint::read_u64(&mut reader)? as usize;
+                let mut centralities = Vec::with_capacity(num_discount_functions);
+                for _ in 0..num_discount_functions {
+                    centralities.push(checkpoint::read_f64s(&mut reader)?);
+                }
+                centralities
+            },
+        };
+
+        ensure!(
+            state.sum_of_dists.is_some() == self.sum_of_distances,
+            "The checkpoint and the builder disagree on whether to compute the sum of distances"
+        );
+        ensure!(
+            state.sum_of_inv_dists.is_some() == self.sum_of_inverse_distances,
+            "The checkpoint and the builder disagree on whether to compute the sum of inverse distances"
+        );
+        ensure!(
+            state.discounted_centralities.len() == self.discount_functions.len(),
+            "The checkpoint contains {} discounted centralities, but the builder has {} discount functions",
+            state.discounted_centralities.len(),
+            self.discount_functions.len()
+        );
+        checkpoint::read_words::<W>(&mut reader, self.array_0.as_mut())
+            .with_context(|| "Could not read array 0")?;
+        checkpoint::read_words::<W>(&mut reader, self.array_1.as_mut())
+            .with_context(|| "Could not read array 1")?;
+
+        self.checkpoint = Some(state);
+        Ok(self)
+    }
+
     /// Builds the [`HyperBall`] instance with the specified [`HyperLogLogBuilder`] and
     /// logs progress with the provided logger.
     ///
+    /// If the builder was configured with [`resume_from`](Self::resume_from),
+    /// the instance will contain the state stored in the checkpoint.
+    ///
     /// # Arguments
     /// * `pl`: A progress logger.
+    ///
+    /// # Panics
+    /// Panics if [discount function
+    /// validation](Self::validate_discount_functions) is enabled and fails.
+    /// Use [`try_build`](Self::try_build) to get an error instead.
     #[allow(clippy::type_complexity)]
     pub fn build(self, pl: &mut impl ProgressLog) -> HyperBall<'a, G1, G2, D, L, A> {
+        self.try_build(pl)
+            .unwrap_or_else(|e| panic!("Could not build HyperBall: {:#}", e))
+    }
+
+    /// Builds the [`HyperBall`] instance like [`build`](Self::build),
+    /// returning an error if [discount function
+    /// validation](Self::validate_discount_functions) is enabled and fails.
+    ///
+    /// # Arguments
+    /// * `pl`: A progress logger.
+    #[allow(clippy::type_complexity)]
+    pub fn try_build(
+        mut self,
+        pl: &mut impl ProgressLog,
+    ) -> Result<HyperBall<'a, G1, G2, D, L, A>> {
         let num_nodes = self.graph.num_nodes();
+        let precision = self.centrality_precision;
+
+        if self.reachable_only {
+            pl.info(format_args!("Computing only the sizes of reachable sets"));
+            self.sum_of_distances = false;
+            self.sum_of_inverse_distances = false;
+            self.discount_functions.clear();
+            self.streaming_discount_functions.clear();
+        }
+
+        if self.validate_discount_functions {
+            pl.info(format_args!(
+                "Validating {} discount functions",
+                self.discount_functions.len() + self.streaming_discount_functions.len()
+            ));
+            // Streaming discount functions are numbered after the others
+            for (index, discount_function) in self
+                .discount_functions
+                .iter()
+                .chain(self.streaming_discount_functions.iter().map(|(f, _)| f))
+                .enumerate()
+            {
+                if let Some(distance) = (1..=num_nodes).find(|&d| !discount_function(d).is_finite())
+                {
+                    bail!(
+                        "Discount function {} is not finite at distance {}: {}",
+                        index,
+                        distance,
+                        discount_function(distance)
+                    );
+                }
+            }
+        }
+
+        if let Some(state) = self.checkpoint {
+            pl.info(format_args!(
+                "Resuming from checkpoint at iteration {}",
+                state.iteration
+            ));
+            pl.info(format_args!(
+                "Using counter logic: {}",
+                self.array_0.logic()
+            ));
+
+            return Ok(HyperBall {
+                graph: self.graph,
+                transposed: self.transpose,
+                transpose_in_memory: self.transpose_in_memory,
+                weight: self.weights,
+                seed: self.seed,
+                centrality_precision: precision,
+                granularity: self.arc_granularity,
+                adaptive_granularity: self.adaptive_granularity,
+                curr_state: self.array_0,
+                next_state: self.array_1,
+                completed: state.completed,
+                // A checkpoint saved before running must be initialized anyway
+                resumed: state.iteration > 0,
+                neighbourhood_function: state.neighbourhood_function,
+                last: state.last,
+                relative_increment: state.relative_increment,
+                iteration_context: IterationContext {
+                    cumul_outdeg: self.cumul_outdegree,
+                    iteration: state.iteration,
+                    current_nf: Mutex::new(0.0),
+                    arc_granularity: 0,
+                    node_cursor: AtomicUsize::new(0),
+                    arc_cursor: Mutex::new((0, 0)),
+                    visited_arcs: AtomicU64::new(0),
+                    modified_counters: AtomicU64::new(state.modified_counters),
+                    systolic: state.systolic,
+                    local: state.local,
+                    pre_local: state.pre_local,
+                    local_checklist: state.local_checklist,
+                    local_next_must_be_checked: Mutex::new(state.local_next_must_be_checked),
+                    must_be_checked: state.must_be_checked,
+                    next_must_be_checked: state.next_must_be_checked,
+                    curr_modified: state.curr_modified,
+                    next_modified: state.next_modified,
+                    sum_of_dists: state
+                        .sum_of_dists
+                        .map(|d| Mutex::new(Accumulator::from_f64s(d, precision))),
+                    sum_of_inv_dists: state
+                        .sum_of_inv_dists
+                        .map(|d| Mutex::new(Accumulator::from_f64s(d, precision))),
+                    reachable_only: self.reachable_only,
+                    discount_functions: self.discount_functions,
+                    streaming_discount_functions: self.streaming_discount_functions,
+                    discounted_centralities: state
+                        .discounted_centralities
+                        .into_iter()
+                        .map(|d| Mutex::new(Accumulator::from_f64s(d, precision)))
+                        .collect(),
+                },
+                last_iteration_stats: IterationStats::default(),
+                _marker: std::marker::PhantomData,
+            });
+        }
 
         let sum_of_distances = if self.sum_of_distances {
             pl.info(format_args!("Initializing sum of distances"));
-            Some(Mutex::new(vec![0.0; num_nodes]))
+            Some(Mutex::new(Accumulator::new(precision, num_nodes)))
         } else {
             pl.info(format_args!("Skipping sum of distances"));
             None
         };
         let sum_of_inverse_distances = if self.sum_of_inverse_distances {
             pl.info(format_args!("Initializing sum of inverse distances"));
-            Some(Mutex::new(vec![0.0; num_nodes]))
+            Some(Mutex::new(Accumulator::new(precision, num_nodes)))
         } else {
             pl.info(format_args!("Skipping sum of inverse distances"));
             None
@@ -335,7 +872,7 @@ impl<
             self.discount_functions.len()
         ));
         for _ in self.discount_functions.iter() {
-            discounted_centralities.push(Mutex::new(vec![0.0; num_nodes]));
+            discounted_centralities.push(Mutex::new(Accumulator::new(precision, num_nodes)));
         }
 
         pl.info(format_args!("Initializing bit vectors"));
@@ -357,14 +894,19 @@ impl<
             self.array_0.logic()
         ));
 
-        HyperBall {
+        Ok(HyperBall {
             graph: self.graph,
             transposed: self.transpose,
+            transpose_in_memory: self.transpose_in_memory,
             weight: self.weights,
+            seed: self.seed,
+            centrality_precision: precision,
             granularity: self.arc_granularity,
+            adaptive_granularity: self.adaptive_granularity,
             curr_state: self.array_0,
             next_state: self.array_1,
             completed: false,
+            resumed: false,
             neighbourhood_function: Vec::new(),
             last: 0.0,
             relative_increment: 0.0,
@@ -388,10 +930,75 @@ impl<
                 next_modified: modified_result_counter,
                 sum_of_dists: sum_of_distances,
                 sum_of_inv_dists: sum_of_inverse_distances,
+                reachable_only: self.reachable_only,
                 discount_functions: self.discount_functions,
+                streaming_discount_functions: self.streaming_discount_functions,
                 discounted_centralities,
             },
+            last_iteration_stats: IterationStats::default(),
             _marker: std::marker::PhantomData,
+        })
+    }
+}
+
+/// The precision of the accumulators used by [`HyperBall`] to compute
+/// centralities.
+///
+/// See [`HyperBallBuilder::centrality_precision`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Precision {
+    /// Single-precision accumulators, using half the memory.
+    F32,
+    /// Double-precision accumulators.
+    #[default]
+    F64,
+}
+
+/// A vector of accumulators with the requested [`Precision`].
+enum Accumulator {
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+}
+
+impl Accumulator {
+    /// Creates a new vector of `len` accumulators set to zero.
+    fn new(precision: Precision, len: usize) -> Self {
+        match precision {
+            Precision::F32 => Self::F32(vec![0.0; len]),
+            Precision::F64 => Self::F64(vec![0.0; len]),
+        }
+    }
+
+    /// Creates a new vector of accumulators from the given values.
+    fn from_f64s(values: Vec<f64>, precision: Precision) -> Self {
+        match precision {
+            Precision::F32 => Self::F32(values.into_iter().map(|v| v as f32).collect()),
+            Precision::F64 => Self::F64(values),
+        }
+    }
+
+    /// Adds `value` to the accumulator of index `index`.
+    #[inline(always)]
+    fn add(&mut self, index: usize, value: f64) {
+        match self {
+            Self::F32(values) => values[index] += value as f32,
+            Self::F64(values) => values[index] += value,
+        }
+    }
+
+    /// Sets all accumulators to zero.
+    fn reset(&mut self) {
+        match self {
+            Self::F32(values) => values.fill(0.0),
+            Self::F64(values) => values.fill(0.0),
+        }
+    }
+
+    /// Returns a copy of the accumulators as a vector of `f64`.
+    fn to_f64s(&self) -> Vec<f64> {
+        match self {
+            Self::F32(values) => values.iter().map(|&v| v as f64).collect(),
+            Self::F64(values) => values.clone(),
         }
     }
 }
@@ -444,13 +1051,18 @@ struct IterationContext<'a, G1: SequentialLabeling, D> {
     /// Whether each counter has been modified during the current iteration.
     next_modified: AtomicBitVec,
     /// The sum of the distances from every given node, if requested.
-    sum_of_dists: Option<Mutex<Vec<f64>>>,
+    sum_of_dists: Option<Mutex<Accumulator>>,
     /// The sum of inverse distances from each given node, if requested.
-    sum_of_inv_dists: Option<Mutex<Vec<f64>>>,
+    sum_of_inv_dists: Option<Mutex<Accumulator>>,
+    /// Whether only the sizes of the reachable sets are computed, in which
+    /// case the neighbourhood function is not accumulated.
+    reachable_only: bool,
     /// Custom discount functions whose sum should be computed.
     discount_functions: Vec<Box<dyn Fn(usize) -> f64 + Sync + 'a>>,
     /// The overall discount centrality for every [`Self::discount_functions`].
-    discounted_centralities: Vec<Mutex<Vec<f64>>>,
+    discounted_centralities: Vec<Mutex<Accumulator>>,
+    /// Custom discount functions whose contributions are streamed to a sink.
+    streaming_discount_functions: Vec<StreamingDiscountFunction<'a>>,
 }
 
 impl<G1: SequentialLabeling, D> IterationContext<'_, G1, D> {
@@ -464,9 +1076,67 @@ impl<G1: SequentialLabeling, D> IterationContext<'_, G1, D> {
     }
 }
 
+/// Statistics about an iteration of [`HyperBall`].
+///
+/// See [`HyperBall::last_iteration_stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IterationStats {
+    /// The number of the iteration, starting from 1, or 0 if no iteration
+    /// has been performed yet.
+    pub iteration: usize,
+    /// The number of arcs visited during the iteration.
+    pub visited_arcs: u64,
+    /// The number of counters modified during the iteration.
+    pub modified_counters: u64,
+    /// The time spent in the parallel phase of the iteration.
+    pub elapsed: Duration,
+}
+
 /// An algorithm that computes an approximation of the neighbourhood function,
 /// of the size of the reachable sets, and of (discounted) positive geometric
 /// centralities of a graph.
+///
+/// # Thread pools
+///
+/// Neither [`HyperBallBuilder`] nor [`HyperBall`] own a thread pool: each
+/// method performing a computation, such as [`run`](Self::run), takes a
+/// reference to a [`ThreadPool`]. Thus, a pool created by the user can be
+/// shared with other parallel algorithms, such as [parallel
+/// visits](crate::algo::visits::Parallel), and its lifetime is entirely under
+/// the user's control; different calls may even use different pools.
+///
+/// ```
+/// # use dsi_progress_logger::no_logging;
+/// # use sux::prelude::*;
+/// # use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
+/// # use webgraph_algo::{algo::hyperball::HyperBallBuilder, prelude::breadth_first};
+/// # use webgraph_algo::utils::{hyper_log_log::HyperLogLogBuilder, SliceCounterArray};
+/// # fn main() -> anyhow::Result<()> {
+/// let graph = Left(VecGraph::from_arc_list([(0, 1), (1, 2)]));
+/// let mut builder = EliasFanoBuilder::new(4, 2);
+/// for cumul in [0, 1, 2, 2] {
+///     builder.push(cumul);
+/// }
+/// let cumulative = builder.build_with_seq_and_dict();
+///
+/// let thread_pool = rayon::ThreadPoolBuilder::new().num_threads(2).build()?;
+///
+/// let logic = HyperLogLogBuilder::new(3).log_2_num_reg(6).build()?;
+/// let mut hyperball = HyperBallBuilder::new(
+///     &graph,
+///     &cumulative,
+///     SliceCounterArray::new(logic.clone(), 3)?,
+///     SliceCounterArray::new(logic, 3)?,
+/// )
+/// .build(no_logging![]);
+/// hyperball.run_until_done(&thread_pool, no_logging![])?;
+///
+/// // The same pool can be used by other algorithms
+/// let mut visit = breadth_first::ParFairNoPred::new(&graph, 1);
+/// assert_eq!(visit.distances(0, &thread_pool, no_logging![]), vec![0, 1, 2]);
+/// # Ok(())
+/// # }
+/// ```
 pub struct HyperBall<
     'a,
     G1: RandomAccessGraph + Sync,
@@ -479,16 +1149,27 @@ pub struct HyperBall<
     graph: &'a G1,
     /// The transpose of [`Self::graph`], if any.
     transposed: Option<&'a G2>,
+    /// The transpose of [`Self::graph`] built in memory, if any.
+    transpose_in_memory: Option<CsrGraph>,
     /// An optional slice of nonegative node weights.
-    weight: Option<&'a [usize]>,
+    weight: Option<Cow<'a, [usize]>>,
+    /// The seed used to generate the elements of weighted nodes, if any.
+    seed: Option<u64>,
+    /// The precision of the centrality accumulators.
+    centrality_precision: Precision,
     /// The base number of nodes per task. TODO.
     granularity: usize,
+    /// Whether to adapt [`Self::granularity`] at each iteration.
+    adaptive_granularity: bool,
     /// The previous state.
     curr_state: A,
     /// The next state.
     next_state: A,
     /// `true` if the computation is over.
     completed: bool,
+    /// `true` if the state was restored from a checkpoint and the next call
+    /// to [`run`](Self::run) must not reinitialize it.
+    resumed: bool,
     /// The neighbourhood fuction.
     neighbourhood_function: Vec<f64>,
     /// The value computed by the last iteration.
@@ -498,6 +1179,8 @@ pub struct HyperBall<
     relative_increment: f64,
     /// Context used in a single iteration.
     iteration_context: IterationContext<'a, G1, D>,
+    /// Statistics about the last iteration.
+    last_iteration_stats: IterationStats,
     _marker: std::marker::PhantomData<L>,
 }
 
@@ -533,8 +1216,16 @@ where
     ) -> Result<()> {
         let upper_bound = std::cmp::min(upper_bound, self.graph.num_nodes());
 
-        self.init(thread_pool, pl)
-            .with_context(|| "Could not initialize approximator")?;
+        if self.resumed {
+            self.resumed = false;
+            pl.info(format_args!(
+                "Resuming approximation from iteration {}",
+                self.iteration_context.iteration
+            ));
+        } else {
+            self.init(thread_pool, pl)
+                .with_context(|| "Could not initialize approximator")?;
+        }
 
         pl.item_name("iteration");
         pl.expected_updates(None);
@@ -543,7 +1234,7 @@ where
             upper_bound, threshold
         ));
 
-        for i in 0..upper_bound {
+        for i in self.iteration_context.iteration..upper_bound {
             self.iterate(thread_pool, &mut pl.clone())
                 .with_context(|| format!("Could not perform iteration {}", i + 1))?;
 
@@ -563,7 +1254,10 @@ where
             }
 
             if let Some(t) = threshold {
-                if i > 3 && self.relative_increment < (1.0 + t) {
+                if i > 3
+                    && !self.iteration_context.reachable_only
+                    && self.relative_increment < (1.0 + t)
+                {
                     pl.info(format_args!("Terminating approximation after {} iteration(s) by relative bound on the neighbourhood function", i + 1));
                     break;
                 }
@@ -575,6 +1269,78 @@ where
         Ok(())
     }
 
+    /// Runs HyperBall using a custom stopping condition.
+    ///
+    /// After each iteration, `stop` is called with the [statistics of the
+    /// iteration](IterationStats): if it returns `true`, the computation is
+    /// stopped. The computation is stopped anyway when no counters are
+    /// modified, or when `upper_bound` iterations have been performed.
+    ///
+    /// # Arguments
+    ///
+    /// * `upper_bound`: an upper bound to the number of iterations.
+    ///
+    /// * `stop`: a function deciding whether to stop the computation after
+    ///   each iteration.
+    ///
+    /// * `thread_pool`: The thread pool to use for parallel computation.
+    ///
+    /// * `pl`: A progress logger.
+    pub fn run_with(
+        &mut self,
+        upper_bound: usize,
+        mut stop: impl FnMut(&IterationStats) -> bool,
+        thread_pool: &ThreadPool,
+        pl: &mut impl ProgressLog,
+    ) -> Result<()> {
+        let upper_bound = std::cmp::min(upper_bound, self.graph.num_nodes());
+
+        if self.resumed {
+            self.resumed = false;
+            pl.info(format_args!(
+                "Resuming approximation from iteration {}",
+                self.iteration_context.iteration
+            ));
+        } else {
+            self.init(thread_pool, pl)
+                .with_context(|| "Could not initialize approximator")?;
+        }
+
+        pl.item_name("iteration");
+        pl.expected_updates(None);
+        pl.start(format!(
+            "Running Hyperball for a maximum of {} iterations with a custom stopping condition",
+            upper_bound
+        ));
+
+        for i in self.iteration_context.iteration..upper_bound {
+            self.iterate(thread_pool, &mut pl.clone())
+                .with_context(|| format!("Could not perform iteration {}", i + 1))?;
+
+            pl.update();
+
+            if self.last_iteration_stats.modified_counters == 0 {
+                pl.info(format_args!(
+                    "Terminating appoximation after {} iteration(s) by stabilisation",
+                    i + 1
+                ));
+                break;
+            }
+
+            if stop(&self.last_iteration_stats) {
+                pl.info(format_args!(
+                    "Terminating approximation after {} iteration(s) by custom stopping condition",
+                    i + 1
+                ));
+                break;
+            }
+        }
+
+        pl.done();
+
+        Ok(())
+    }
+
     /// Runs HyperBall until no counters are modified.
     ///
     /// # Arguments
@@ -615,27 +1381,121 @@ where
 
     #[inline(always)]
     fn ensure_iteration(&self) -> Result<()> {
+        // On a graph without nodes there is nothing to iterate on
         ensure!(
-            self.iteration_context.iteration > 0,
+            self.iteration_context.iteration > 0 || self.graph.num_nodes() == 0,
             "HyperBall was not run. Please call HyperBall::run before accessing computed fields"
         );
         Ok(())
     }
 
+    #[inline(always)]
+    fn ensure_neighbourhood_function(&self) -> Result<()> {
+        self.ensure_iteration()?;
+        ensure!(
+            !self.iteration_context.reachable_only,
+            "The neighbourhood function is not computed by reachable-only runs. Do not use HyperBallBuilder::reachable_only while building HyperBall to compute it"
+        );
+        Ok(())
+    }
+
     /// Returns the neighbourhood function computed by this instance.
     pub fn neighbourhood_function(&self) -> Result<Vec<f64>> {
-        self.ensure_iteration()?;
+        self.ensure_neighbourhood_function()?;
         Ok(self.neighbourhood_function.clone())
     }
 
+    /// Returns the increments of the neighbourhood function computed by this
+    /// instance.
+    ///
+    /// The element of index *i* is the (estimated) number of pairs of nodes at
+    /// distance exactly *i*, that is, the difference between the values of
+    /// the neighbourhood function at *i* and *i* – 1 (the element of index 0
+    /// is the value of the neighbourhood function at 0).
+    pub fn neighbourhood_function_deltas(&self) -> Result<Vec<f64>> {
+        self.ensure_neighbourhood_function()?;
+        Ok(self
+            .neighbourhood_function
+            .iter()
+            .scan(0.0, |prev, &value| {
+                let delta = value - *prev;
+                *prev = value;
+                Some(delta)
+            })
+            .collect())
+    }
+
+    /// Returns the effective diameter at the specified fraction computed by
+    /// this instance.
+    ///
+    /// The effective diameter at fraction α is the (interpolated) smallest
+    /// distance at which the neighbourhood function reaches α times its final
+    /// value. The interpolation is linear between the two integer distances
+    /// bracketing the value, as in the Java implementation of
+    /// `NeighbourhoodFunction.effectiveDiameter`.
+    ///
+    /// # Arguments
+    /// * `fraction`: the fraction α of pairs, in the interval (0..1].
+    pub fn effective_diameter(&self, fraction: f64) -> Result<f64> {
+        self.ensure_neighbourhood_function()?;
+        effective_diameter(fraction, &self.neighbourhood_function)
+    }
+
+    /// Returns the spid (shortest-paths index of dispersion) computed by this
+    /// instance.
+    ///
+    /// The spid is the ratio between the variance and the mean of the
+    /// distance distribution, that is, of the distances between pairs of
+    /// distinct nodes `x`, `y` such that `y` is reachable from `x`. The
+    /// distribution is derived from the [increments of the neighbourhood
+    /// function](Self::neighbourhood_function_deltas). Social networks have
+    /// usually a spid smaller than one, whereas web graphs have a spid larger
+    /// than one.
+    pub fn spid(&self) -> Result<f64> {
+        self.ensure_neighbourhood_function()?;
+        spid(&self.neighbourhood_function)
+    }
+
+    /// Returns the harmonic diameter computed by this instance.
+    ///
+    /// The harmonic diameter is the harmonic mean of the distances between
+    /// all pairs of distinct nodes, where the distance between a node and a
+    /// node that is not reachable from it is infinite. It is derived from the
+    /// [increments of the neighbourhood
+    /// function](Self::neighbourhood_function_deltas) as *n*(*n* – 1) divided
+    /// by the sum over positive distances *d* of the number of pairs at
+    /// distance *d* divided by *d*. If no pair of distinct nodes is connected,
+    /// the harmonic diameter is infinite.
+    pub fn harmonic_diameter(&self) -> Result<f64> {
+        self.ensure_neighbourhood_function()?;
+        harmonic_diameter(self.graph.num_nodes(), &self.neighbourhood_function)
+    }
+
+    /// Returns the median distance computed by this instance.
+    ///
+    /// The median distance is the (interpolated) 50th percentile of the
+    /// distances between all pairs of distinct nodes, where the distance
+    /// between a node and a node that is not reachable from it is infinite.
+    /// It is derived from the [increments of the neighbourhood
+    /// function](Self::neighbourhood_function_deltas) using the same linear
+    /// interpolation of the [effective diameter](Self::effective_diameter).
+    ///
+    /// If less than half of the pairs of distinct nodes are connected (e.g.,
+    /// in heavily disconnected graphs) the median distance would be infinite,
+    /// and an error is returned.
+    pub fn median_distance(&self) -> Result<f64> {
+        self.ensure_neighbourhood_function()?;
+        median_distance(self.graph.num_nodes(), &self.neighbourhood_function)
+    }
+
     /// Returns the sum of distances computed by this instance if requested.
     pub fn sum_of_distances(&self) -> Result<Vec<f64>> {
         self.ensure_iteration()?;
         if let Some(distances) = &self.iteration_context.sum_of_dists {
             // TODO these are COPIES
-            Ok(distances.lock().unwrap().to_vec())
+            Ok(distances.lock().unwrap().to_f64s())
         } else {
-            bail!("Sum of distances were not requested. Use builder.with_sum_of_distances(true) while building HyperBall to compute them")
+            bail!("Sum of distances was not requested. Use HyperBallBuilder::with_sum_of_distances(true) while building HyperBall to compute them")
         }
     }
 
@@ -643,9 +1503,33 @@ where
     pub fn harmonic_centralities(&self) -> Result<Vec<f64>> {
         self.ensure_iteration()?;
         if let Some(distances) = &self.iteration_context.sum_of_inv_dists {
-            Ok(distances.lock().unwrap().to_vec())
+            Ok(distances.lock().unwrap().to_f64s())
+        } else {
+            bail!("Sum of inverse distances was not requested. Use HyperBallBuilder::with_sum_of_inverse_distances(true) while building HyperBall to compute them")
+        }
+    }
+
+    /// Computes and returns the normalized harmonic centralities from the sum
+    /// of inverse distances computed by this instance.
+    ///
+    /// The normalized harmonic centrality of a node is its [harmonic
+    /// centrality](Self::harmonic_centralities) divided by *n* – 1, where *n*
+    /// is the number of nodes of the graph, so that it lies in [0 . . 1].
+    /// The normalized harmonic centrality of isolated nodes, and of all nodes
+    /// of a graph with a single node, is zero.
+    pub fn harmonic_centrality_normalized(&self) -> Result<Vec<f64>> {
+        self.ensure_iteration()?;
+        if let Some(distances) = &self.iteration_context.sum_of_inv_dists {
+            let denominator = self.graph.num_nodes().saturating_sub(1).max(1) as f64;
+            Ok(distances
+                .lock()
+                .unwrap()
+                .to_f64s()
+                .into_iter()
+                .map(|d| d / denominator)
+                .collect())
         } else {
-            bail!("Sum of inverse distances were not requested. Use builder.with_sum_of_inverse_distances(true) while building HyperBall to compute them")
+            bail!("Sum of inverse distances was not requested. Use HyperBallBuilder::with_sum_of_inverse_distances(true) while building HyperBall to compute normalized harmonic centrality")
         }
     }
 
@@ -657,30 +1541,75 @@ where
         self.ensure_iteration()?;
         let d = self.iteration_context.discounted_centralities.get(index);
         if let Some(distaces) = d {
-            Ok(distaces.lock().unwrap().to_vec())
+            Ok(distaces.lock().unwrap().to_f64s())
         } else {
             bail!("Discount centrality of index {} does not exist", index)
         }
     }
 
     /// Computes and returns the closeness centralities from the sum of distances computed by this instance.
+    ///
+    /// The closeness centrality of a node is the reciprocal of the sum of
+    /// distances from the node. The closeness centrality of nodes that cannot
+    /// reach other nodes (e.g., isolated nodes, or the only node of a graph
+    /// with a single node, even if it has a loop) is by definition zero.
     pub fn closeness_centrality(&self) -> Result<Vec<f64>> {
         self.ensure_iteration()?;
         if let Some(distances) = &self.iteration_context.sum_of_dists {
             Ok(distances
                 .lock()
                 .unwrap()
-                .iter()
-                .map(|&d| if d == 0.0 { 0.0 } else { d.recip() })
+                .to_f64s()
+                .into_iter()
+                .map(|d| if d == 0.0 { 0.0 } else { d.recip() })
+                .collect())
+        } else {
+            bail!("Sum of distances was not requested. Use HyperBallBuilder::with_sum_of_distances(true) while building HyperBall to compute closeness centrality")
+        }
+    }
+
+    /// Computes and returns the normalized closeness centralities from the
+    /// sum of distances computed by this instance.
+    ///
+    /// The normalized closeness centrality of a node is *r* – 1 divided by
+    /// the sum of distances from the node, where *r* is the estimated number
+    /// of nodes reachable from the node (including the node itself). In this
+    /// way, centralities of nodes in components of different sizes are
+    /// comparable. Since the number of reachable nodes is an estimate, the
+    /// result is capped to 1. The normalized closeness centrality of nodes
+    /// that cannot reach other nodes is zero.
+    pub fn closeness_centrality_normalized(&self) -> Result<Vec<f64>> {
+        self.ensure_iteration()?;
+        if let Some(distances) = &self.iteration_context.sum_of_dists {
+            let logic = self.curr_state.logic();
+            Ok(distances
+                .lock()
+                .unwrap()
+                .to_f64s()
+                .into_iter()
+                .enumerate()
+                .map(|(node, d)| {
+                    if d == 0.0 {
+                        0.0
+                    } else {
+                        let count = logic.count(self.curr_state.get_backend(node));
+                        f64::min((count - 1.0).max(0.0) / d, 1.0)
+                    }
+                })
                 .collect())
         } else {
-            bail!("Sum of distances were not requested. Use builder.with_sum_of_distances(true) while building HyperBall to compute closeness centrality")
+            bail!("Sum of distances was not requested. Use HyperBallBuilder::with_sum_of_distances(true) while building HyperBall to compute normalized closeness centrality")
         }
     }
 
     /// Computes and returns the lin centralities from the sum of distances computed by this instance.
     ///
-    /// Note that lin's index for isolated nodes is by (our) definition one (it's smaller than any other node).
+    /// The Lin centrality of a node is the square of the number *r* of nodes
+    /// reachable from the node divided by the sum of distances from the node.
+    /// Note that lin's index for nodes that cannot reach other nodes (e.g.,
+    /// isolated nodes, or the only node of a graph with a single node, even
+    /// if it has a loop) is by (our) definition one (it's smaller than any
+    /// other node).
     pub fn lin_centrality(&self) -> Result<Vec<f64>> {
         self.ensure_iteration()?;
         if let Some(distances) = &self.iteration_context.sum_of_dists {
@@ -688,9 +1617,10 @@ where
             Ok(distances
                 .lock()
                 .unwrap()
-                .iter()
+                .to_f64s()
+                .into_iter()
                 .enumerate()
-                .map(|(node, &d)| {
+                .map(|(node, d)| {
                     if d == 0.0 {
                         1.0
                     } else {
@@ -700,11 +1630,18 @@ where
                 })
                 .collect())
         } else {
-            bail!("Sum of distances were not requested. Use builder.with_sum_of_distances(true) while building HyperBall to compute lin centrality")
+            bail!("Sum of distances was not requested. Use HyperBallBuilder::with_sum_of_distances(true) while building HyperBall to compute lin centrality")
         }
     }
 
-    /// Computes and returns the nieminen centralities from the sum of distances computed by this instance.
+    /// Computes and returns the Nieminen centralities from the sum of distances computed by this instance.
+    ///
+    /// The Nieminen centrality of a node is the square of the number *r* of
+    /// nodes reachable from the node minus the sum of distances from the
+    /// node. For nodes that cannot reach other nodes (e.g., isolated nodes,
+    /// or the only node of a graph with a single node, even if it has a
+    /// loop) *r* is one and the sum of distances is zero, so their Nieminen
+    /// centrality is exactly one, independently of the estimate of *r*.
     pub fn nieminen_centrality(&self) -> Result<Vec<f64>> {
         self.ensure_iteration()?;
         if let Some(distances) = &self.iteration_context.sum_of_dists {
@@ -712,15 +1649,20 @@ where
             Ok(distances
                 .lock()
                 .unwrap()
-                .iter()
+                .to_f64s()
+                .into_iter()
                 .enumerate()
-                .map(|(node, &d)| {
-                    let count = logic.count(self.curr_state.get_backend(node));
-                    (count * count) - d
+                .map(|(node, d)| {
+                    if d == 0.0 {
+                        1.0
+                    } else {
+                        let count = logic.count(self.curr_state.get_backend(node));
+                        (count * count) - d
+                    }
                 })
                 .collect())
         } else {
-            bail!("Sum of distances were not requested. Use builder.with_sum_of_distances(true) while building HyperBall to compute lin centrality")
+            bail!("Sum of distances was not requested. Use HyperBallBuilder::with_sum_of_distances(true) while building HyperBall to compute Nieminen centrality")
         }
     }
 
@@ -743,10 +1685,114 @@ where
     /// `hyperball.reachable_nodes().unwrap()[i]` is equal to `hyperball.reachable_nodes_from(i).unwrap()`.
     pub fn reachable_nodes(&self) -> Result<Vec<f64>> {
         self.ensure_iteration()?;
-        let logic = self.curr_state.logic();
-        Ok((0..self.graph.num_nodes())
-            .map(|n| logic.count(self.curr_state.get_backend(n)))
-            .collect())
+        Ok(self.curr_state.counts())
+    }
+
+    /// Returns the nodes whose counters have been modified during the last
+    /// iteration, in increasing order.
+    ///
+    /// If no iteration has been performed since the last initialization, an
+    /// empty vector is returned.
+    pub fn last_modified_nodes(&self) -> Vec<usize> {
+        let ic = &self.iteration_context;
+        if ic.iteration == 0 {
+            return Vec::new();
+        }
+        (0..self.graph.num_nodes())
+            .filter(|&node| ic.curr_modified[node])
+            .collect()
+    }
+
+    /// Returns statistics about the last iteration performed.
+    ///
+    /// If no iteration has been performed since the last initialization,
+    /// the [default](IterationStats::default) statistics are returned.
+    pub fn last_iteration_stats(&self) -> IterationStats {
+        self.last_iteration_stats
+    }
+}
+
+impl<
+        G1: RandomAccessGraph + Sync,
+        G2: RandomAccessGraph + Sync,
+        D: Succ<Input = usize, Output = usize>,
+        L: MergeCounterLogic<Item = G1::Label> + Sync + std::fmt::Display,
+        A: CounterArrayMut<L>,
+    > HyperBall<'_, G1, G2, D, L, A>
+{
+    /// Saves the current state of the computation to a checkpoint.
+    ///
+    /// The checkpoint contains the counter arrays, the neighbourhood function
+    /// and the centralities computed so far, and can be used to resume the
+    /// computation with [`HyperBallBuilder::resume_from`]. Checkpoints are
+    /// written in native endianness.
+    ///
+    /// # Arguments
+    /// * `path`: the path of the checkpoint.
+    pub fn save_state<W: Word>(&self, path: impl AsRef<Path>) -> Result<()>
+    where
+        A: AsRef<[W]>,
+    {
+        let path = path.as_ref();
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("Could not create checkpoint {}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+        let num_nodes = self.graph.num_nodes();
+        let ic = &self.iteration_context;
+
+        checkpoint::write_header::<W>(
+            &mut writer,
+            num_nodes,
+            &self.curr_state.logic().to_string(),
+        )?;
+        checkpoint::write_settings(
+            &mut writer,
+            ic.reachable_only,
+            self.weight.as_deref(),
+            self.seed,
+            self.centrality_precision,
+        )?;
+        checkpoint::write_u64(&mut writer, ic.iteration as u64)?;
+        checkpoint::write_bool(&mut writer, self.completed)?;
+        checkpoint::write_bool(&mut writer, ic.systolic)?;
+        checkpoint::write_bool(&mut writer, ic.local)?;
+        checkpoint::write_bool(&mut writer, ic.pre_local)?;
+        checkpoint::write_u64(&mut writer, ic.modified_counters.load(Ordering::Relaxed))?;
+        checkpoint::write_f64(&mut writer, self.last)?;
+        checkpoint::write_f64(&mut writer, self.relative_increment)?;
+        checkpoint::write_f64s(&mut writer, &self.neighbourhood_function)?;
+        checkpoint::write_bitvec(&mut writer, &ic.curr_modified, num_nodes)?;
+        checkpoint::write_bitvec(&mut writer, &ic.next_modified, num_nodes)?;
+        checkpoint::write_bitvec(&mut writer, &ic.must_be_checked, num_nodes)?;
+        checkpoint::write_bitvec(&mut writer, &ic.next_must_be_checked, num_nodes)?;
+        checkpoint::write_usizes(&mut writer, &ic.local_checklist)?;
+        checkpoint::write_usizes(&mut writer, &ic.local_next_must_be_checked.lock().unwrap())?;
+        checkpoint::write_optional_f64s(
+            &mut writer,
+            ic.sum_of_dists
+                .as_ref()
+                .map(|d| d.lock().unwrap().to_f64s())
+                .as_deref(),
+        )?;
+        checkpoint::write_optional_f64s(
+            &mut writer,
+            ic.sum_of_inv_dists
+                .as_ref()
+                .map(|d| d.lock().unwrap().to_f64s())
+                .as_deref(),
+        )?;
+        checkpoint::write_u64(&mut writer, ic.discounted_centralities.len() as u64)?;
+        for centralities in ic.discounted_centralities.iter() {
+            checkpoint::write_f64s(&mut writer, &centralities.lock().unwrap().to_f64s())?;
+        }
+        checkpoint::write_words::<W>(&mut writer, self.curr_state.as_ref())?;
+        checkpoint::write_words::<W>(&mut writer, self.next_state.as_ref())?;
+
+        writer
+            .flush()
+            .with_context(|| format!("Could not write checkpoint {}", path.display()))?;
+
+        Ok(())
     }
 }
 
@@ -781,8 +1827,9 @@ where
 
         // If less than one fourth of the nodes have been modified, and we have
         // the transpose, it is time to pass to a systolic computation
-        ic.systolic =
-            self.transposed.is_some() && ic.iteration > 0 && modified_counters < num_nodes / 4;
+        ic.systolic = (self.transposed.is_some() || self.transpose_in_memory.is_some())
+            && ic.iteration > 0
+            && modified_counters < num_nodes / 4;
 
         // Non-systolic computations add up the values of all counter
         //
@@ -852,7 +1899,7 @@ where
         let mut granularity = ic.arc_granularity;
         let num_threads = thread_pool.current_num_threads();
 
-        if num_threads > 1 && !ic.local {
+        if self.adaptive_granularity && num_threads > 1 && !ic.local {
             if ic.iteration > 0 {
                 granularity = f64::min(
                     std::cmp::max(1, num_nodes as usize / num_threads) as _,
@@ -871,21 +1918,36 @@ where
         pl.item_name("arc");
         pl.expected_updates(if ic.local { None } else { Some(num_arcs as _) });
         pl.start("Starting parallel execution");
+        let start = Instant::now();
         {
             let next_state_sync = self.next_state.as_sync_array();
             thread_pool.broadcast(|c| {
-                Self::parallel_task(
-                    self.graph,
-                    self.transposed,
-                    &self.curr_state,
-                    &next_state_sync,
-                    ic,
-                    c,
-                )
+                if let Some(transpose) = &self.transpose_in_memory {
+                    Self::parallel_task(
+                        self.graph,
+                        Some(transpose),
+                        &self.curr_state,
+                        &next_state_sync,
+                        ic,
+                        c,
+                    )
+                } else {
+                    Self::parallel_task(
+                        self.graph,
+                        self.transposed,
+                        &self.curr_state,
+                        &next_state_sync,
+                        ic,
+                        c,
+                    )
+                }
             });
         }
 
-        pl.done_with_count(ic.visited_arcs.load(Ordering::Relaxed) as usize);
+        let elapsed = start.elapsed();
+
+        let visited_arcs = ic.visited_arcs.load(Ordering::Relaxed);
+        pl.done_with_count(visited_arcs as usize);
         let modified_counters = ic.modified_counters.load(Ordering::Relaxed);
 
         pl.info(format_args!(
@@ -902,6 +1964,17 @@ where
             std::mem::swap(&mut ic.must_be_checked, &mut ic.next_must_be_checked);
         }
 
+        if ic.reachable_only {
+            ic.iteration += 1;
+            self.last_iteration_stats = IterationStats {
+                iteration: ic.iteration,
+                visited_arcs,
+                modified_counters,
+                elapsed,
+            };
+            return Ok(());
+        }
+
         let mut current_nf_mut = ic.current_nf.lock().unwrap();
         self.last = *current_nf_mut;
         // We enforce monotonicity--non-monotonicity can only be caused by
@@ -934,6 +2007,13 @@ where
 
         ic.iteration += 1;
 
+        self.last_iteration_stats = IterationStats {
+            iteration: ic.iteration,
+            visited_arcs,
+            modified_counters,
+            elapsed,
+        };
+
         Ok(())
     }
 
@@ -960,7 +2040,8 @@ where
             .ceil() as usize;
         let do_centrality = ic.sum_of_dists.is_some()
             || ic.sum_of_inv_dists.is_some()
-            || !ic.discount_functions.is_empty();
+            || !ic.discount_functions.is_empty()
+            || !ic.streaming_discount_functions.is_empty();
         let node_upper_limit = if ic.local {
             ic.local_checklist.len()
         } else {
@@ -1044,14 +2125,14 @@ where
                     // compute the neighbourhood function cumulating actual values, and not deltas) or
                     // if the counter was actually modified (as we're going to cumulate the neighbourhood
                     // function delta, or at least some centrality).
-                    if !ic.systolic || counter_modified {
+                    if !ic.reachable_only && (!ic.systolic || counter_modified) {
                         post = logic.count(next_counter.as_ref())
                     }
-                    if !ic.systolic {
+                    if !ic.reachable_only && !ic.systolic {
                         neighbourhood_function_delta += post;
                     }
 
-                    if counter_modified && (ic.systolic || do_centrality) {
+                    if counter_modified && !ic.reachable_only && (ic.systolic || do_centrality) {
                         let pre = logic.count(prev_counter);
                         if ic.systolic {
                             neighbourhood_function_delta += -pre;
@@ -1064,11 +2145,11 @@ where
                             if delta > 0.0 {
                                 if let Some(distances) = &ic.sum_of_dists {
                                     let new_value = delta * (ic.iteration + 1) as f64;
-                                    distances.lock().unwrap()[node] += new_value;
+                                    distances.lock().unwrap().add(node, new_value);
                                 }
                                 if let Some(distances) = &ic.sum_of_inv_dists {
                                     let new_value = delta / (ic.iteration + 1) as f64;
-                                    distances.lock().unwrap()[node] += new_value;
+                                    distances.lock().unwrap().add(node, new_value);
                                 }
                                 for (func, distances) in ic
                                     .discount_functions
@@ -1076,7 +2157,10 @@ where
                                     .zip(ic.discounted_centralities.iter())
                                 {
                                     let new_value = delta * func(ic.iteration + 1);
-                                    distances.lock().unwrap()[node] += new_value;
+                                    distances.lock().unwrap().add(node, new_value);
+                                }
+                                for (func, sink) in ic.streaming_discount_functions.iter() {
+                                    sink(node, delta * func(ic.iteration + 1));
                                 }
                             }
                         }
@@ -1151,10 +2235,18 @@ where
         pl.info(format_args!("Initializing registers"));
         if let Some(w) = &self.weight {
             pl.info(format_args!("Loading weights"));
+            let seed = self.seed;
             for (i, &node_weight) in w.iter().enumerate() {
                 let mut counter = self.curr_state.get_counter_mut(i);
-                for _ in 0..node_weight {
-                    counter.add(&random());
+                if let Some(seed) = seed {
+                    // Each node has its own generator, so that the elements
+                    // of a node do not depend on the other nodes
+                    let mut rng = StdRng::seed_from_u64(
+                        seed ^ (i as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15),
+                    );
+                    counter.add_all((0..node_weight).map(|_| rng.gen::<usize>()));
+                } else {
+                    counter.add_all((0..node_weight).map(|_| random::<usize>()));
                 }
             }
         } else {
@@ -1164,6 +2256,7 @@ where
         }
 
         self.completed = false;
+        self.resumed = false;
 
         let ic = &mut self.iteration_context;
         ic.iteration = 0;
@@ -1171,17 +2264,18 @@ where
         ic.local = false;
         ic.pre_local = false;
         ic.reset(self.granularity);
+        self.last_iteration_stats = IterationStats::default();
 
         pl.info(format_args!("Initializing distances"));
         if let Some(distances) = &ic.sum_of_dists {
-            distances.lock().unwrap().fill(f64::ZERO);
+            distances.lock().unwrap().reset();
         }
         if let Some(distances) = &ic.sum_of_inv_dists {
-            distances.lock().unwrap().fill(f64::ZERO);
+            distances.lock().unwrap().reset();
         }
         pl.info(format_args!("Initializing centralities"));
         for centralities in ic.discounted_centralities.iter() {
-            centralities.lock().unwrap().fill(0.0);
+            centralities.lock().unwrap().reset();
         }
 
         self.last = self.graph.num_nodes() as f64;
@@ -1198,6 +2292,114 @@ where
     }
 }
 
+/// Computes the effective diameter at the specified fraction of a
+/// neighbourhood function.
+///
+/// # Arguments
+/// * `fraction`: the fraction of pairs, in the interval (0..1].
+/// * `nf`: the neighbourhood function.
+fn effective_diameter(fraction: f64, nf: &[f64]) -> Result<f64> {
+    ensure!(
+        fraction > 0.0 && fraction <= 1.0,
+        "The fraction should be in the interval (0..1]. Got {}",
+        fraction
+    );
+    let &final_value = nf
+        .last()
+        .with_context(|| "The neighbourhood function is empty")?;
+
+    let mut d = 0;
+    while nf[d] / final_value < fraction {
+        d += 1;
+    }
+
+    if d == 0 {
+        return Ok(0.0);
+    }
+
+    Ok((d - 1) as f64 + (fraction * final_value - nf[d - 1]) / (nf[d] - nf[d - 1]))
+}
+
+/// Computes the spid (the ratio between the variance and the mean of the
+/// distance distribution) of a neighbourhood function.
+///
+/// Pairs at distance zero are excluded from the distribution.
+///
+/// # Arguments
+/// * `nf`: the neighbourhood function.
+fn spid(nf: &[f64]) -> Result<f64> {
+    let mut pairs = 0.0;
+    let mut sum = 0.0;
+    let mut sum_of_squares = 0.0;
+    for (d, values) in nf.windows(2).enumerate() {
+        let d = (d + 1) as f64;
+        let delta = values[1] - values[0];
+        pairs += delta;
+        sum += delta * d;
+        sum_of_squares += delta * d * d;
+    }
+    ensure!(
+        pairs > 0.0,
+        "The neighbourhood function contains no pairs of distinct nodes"
+    );
+
+    let mean = sum / pairs;
+    let variance = sum_of_squares / pairs - mean * mean;
+    Ok(variance / mean)
+}
+
+/// Computes the median distance of a graph with the given number of nodes
+/// from its neighbourhood function.
+///
+/// Pairs at distance zero are excluded, and unreachable pairs are at
+/// infinite distance.
+///
+/// # Arguments
+/// * `num_nodes`: the number of nodes of the graph.
+/// * `nf`: the neighbourhood function.
+fn median_distance(num_nodes: usize, nf: &[f64]) -> Result<f64> {
+    let &base = nf
+        .first()
+        .with_context(|| "The neighbourhood function is empty")?;
+    ensure!(num_nodes > 1, "The graph has less than two nodes");
+    let half = (num_nodes as f64 * (num_nodes - 1) as f64) / 2.0;
+
+    let mut d = 1;
+    while d < nf.len() && nf[d] - base < half {
+        d += 1;
+    }
+    ensure!(
+        d < nf.len(),
+        "Less than half of the pairs of distinct nodes are connected ({} out of {}): the median distance is infinite",
+        nf[nf.len() - 1] - base,
+        2.0 * half
+    );
+
+    let prev = nf[d - 1] - base;
+    Ok((d - 1) as f64 + (half - prev) / (nf[d] - base - prev))
+}
+
+/// Computes the harmonic diameter of a graph with the given number of nodes
+/// from its neighbourhood function.
+///
+/// Pairs at distance zero are excluded.
+///
+/// # Arguments
+/// * `num_nodes`: the number of nodes of the graph.
+/// * `nf`: the neighbourhood function.
+fn harmonic_diameter(num_nodes: usize, nf: &[f64]) -> Result<f64> {
+    ensure!(!nf.is_empty(), "The neighbourhood function is empty");
+    let sum_of_inverse_distances: f64 = nf
+        .windows(2)
+        .enumerate()
+        .map(|(d, values)| (values[1] - values[0]) / (d + 1) as f64)
+        .sum();
+    if sum_of_inverse_distances == 0.0 {
+        return Ok(f64::INFINITY);
+    }
+    Ok((num_nodes * num_nodes.saturating_sub(1)) as f64 / sum_of_inverse_distances)
+}
+
 #[cfg(test)]
 mod test {
     use std::hash::{BuildHasherDefault, DefaultHasher};
@@ -1208,6 +2410,8 @@ mod test {
     use epserde::deser::{Deserialize, Flags};
     use mmap_rs::MmapMut;
     use webgraph::{
+        graphs::vec_graph::VecGraph,
+        labels::Left,
         prelude::{BvGraph, DCF},
         traits::SequentialLabeling,
     };
@@ -1305,4 +2509,202 @@ mod test {
 
         Ok(())
     }
+
+    #[cfg_attr(feature = "slow_tests", test)]
+    #[cfg_attr(not(feature = "slow_tests"), allow(dead_code))]
+    fn test_fixed_granularity() -> Result<()> {
+        let basename = "tests/graphs/cnr-2000";
+
+        let graph = BvGraph::with_basename(basename).load()?;
+        let transpose = BvGraph::with_basename(basename.to_owned() + "-t").load()?;
+        let cumulative = DCF::load_mmap(basename.to_owned() + ".dcf", Flags::empty())?;
+
+        let num_nodes = graph.num_nodes();
+        let granularity = 1000;
+
+        let hyper_log_log = HyperLogLogBuilder::new(num_nodes)
+            .log_2_num_reg(6)
+            .build()?;
+
+        let mut hyperball = HyperBallBuilder::with_transpose(
+            &graph,
+            &transpose,
+            cumulative.as_ref(),
+            SliceCounterArray::new(hyper_log_log.clone(), num_nodes)?,
+            SliceCounterArray::new(hyper_log_log, num_nodes)?,
+        )
+        .arc_granularity(granularity)
+        .adaptive_granularity(false)
+        .build(no_logging![]);
+
+        let threads = threads![4];
+        hyperball.init(&threads, no_logging![])?;
+
+        let mut modified_counters = num_nodes as u64;
+        while modified_counters != 0 {
+            hyperball.iterate(&threads, no_logging![])?;
+            assert_eq!(hyperball.iteration_context.arc_granularity, granularity);
+
+            modified_counters = hyperball
+                .iteration_context
+                .modified_counters
+                .load(Ordering::Relaxed);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_effective_diameter() -> Result<()> {
+        let nf = [4.0, 8.0, 12.0, 16.0];
+
+        assert_eq!(effective_diameter(1.0, &nf)?, 3.0);
+        assert_eq!(effective_diameter(0.5, &nf)?, 1.0);
+        assert_eq!(effective_diameter(0.25, &nf)?, 0.0);
+        assert_eq!(effective_diameter(0.625, &nf)?, 1.5);
+        assert!((effective_diameter(0.9, &nf)? - 2.6).abs() < 1e-9);
+
+        assert!(effective_diameter(0.0, &nf).is_err());
+        assert!(effective_diameter(1.5, &nf).is_err());
+        assert!(effective_diameter(0.5, &[]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_spid() -> Result<()> {
+        let graph = Left(VecGraph::from_arc_list([
+            (0, 1),
+            (1, 2),
+            (2, 3),
+            (3, 0),
+            (1, 4),
+            (4, 5),
+            (6, 5),
+        ]));
+        let num_nodes = graph.num_nodes();
+
+        // Brute-force distance distribution
+        let mut distances = Vec::new();
+        let mut pairs_at_distance = vec![num_nodes as f64];
+        for src in 0..num_nodes {
+            let mut distance = vec![usize::MAX; num_nodes];
+            let mut queue = std::collections::VecDeque::from([src]);
+            distance[src] = 0;
+            while let Some(node) = queue.pop_front() {
+                for succ in graph.successors(node) {
+                    if distance[succ] == usize::MAX {
+                        distance[succ] = distance[node] + 1;
+                        queue.push_back(succ);
+                        distances.push(distance[succ] as f64);
+                        if pairs_at_distance.len() <= distance[succ] {
+                            pairs_at_distance.resize(distance[succ] + 1, 0.0);
+                        }
+                        pairs_at_distance[distance[succ]] += 1.0;
+                    }
+                }
+            }
+        }
+        let mean = distances.iter().sum::<f64>() / distances.len() as f64;
+        let variance =
+            distances.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / distances.len() as f64;
+
+        let nf: Vec<f64> = pairs_at_distance
+            .iter()
+            .scan(0.0, |sum, &pairs| {
+                *sum += pairs;
+                Some(*sum)
+            })
+            .collect();
+
+        assert!((spid(&nf)? - variance / mean).abs() < 1e-9);
+        assert!(spid(&[4.0]).is_err());
+        assert!(spid(&[]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_median_distance() -> Result<()> {
+        // A directed cycle with 5 nodes: 5 pairs at each distance from 1 to 4
+        assert_eq!(median_distance(5, &[5.0, 10.0, 15.0, 20.0, 25.0])?, 2.0);
+        // A clique with 5 nodes
+        assert_eq!(median_distance(5, &[5.0, 25.0])?, 0.5);
+
+        let graph = Left(VecGraph::from_arc_list([
+            (0, 1),
+            (1, 0),
+            (1, 2),
+            (2, 1),
+            (2, 3),
+            (3, 2),
+            (3, 4),
+            (4, 3),
+            (1, 5),
+            (5, 1),
+            (5, 6),
+            (6, 5),
+        ]));
+        let num_nodes = graph.num_nodes();
+
+        // Brute-force distances between pairs of distinct nodes
+        let mut distances = Vec::new();
+        let mut pairs_at_distance = vec![num_nodes as f64];
+        for src in 0..num_nodes {
+            let mut distance = vec![usize::MAX; num_nodes];
+            let mut queue = std::collections::VecDeque::from([src]);
+            distance[src] = 0;
+            while let Some(node) = queue.pop_front() {
+                for succ in graph.successors(node) {
+                    if distance[succ] == usize::MAX {
+                        distance[succ] = distance[node] + 1;
+                        queue.push_back(succ);
+                        distances.push(distance[succ]);
+                        if pairs_at_distance.len() <= distance[succ] {
+                            pairs_at_distance.resize(distance[succ] + 1, 0.0);
+                        }
+                        pairs_at_distance[distance[succ]] += 1.0;
+                    }
+                }
+            }
+        }
+        assert_eq!(distances.len(), num_nodes * (num_nodes - 1));
+        distances.sort();
+        let median = distances[(distances.len() - 1) / 2];
+
+        let nf: Vec<f64> = pairs_at_distance
+            .iter()
+            .scan(0.0, |sum, &pairs| {
+                *sum += pairs;
+                Some(*sum)
+            })
+            .collect();
+
+        // The interpolated median lies between the brute-force median and
+        // the previous distance
+        let interpolated = median_distance(num_nodes, &nf)?;
+        assert!(interpolated > (median - 1) as f64);
+        assert!(interpolated <= median as f64);
+
+        // A graph with 4 nodes and a single arc
+        assert!(median_distance(4, &[4.0, 5.0]).is_err());
+        assert!(median_distance(4, &[]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_harmonic_diameter() -> Result<()> {
+        // A clique with 5 nodes
+        assert_eq!(harmonic_diameter(5, &[5.0, 25.0])?, 1.0);
+        // A directed path with 4 nodes: 3 pairs at distance 1, 2 at
+        // distance 2, and 1 at distance 3
+        let expected = 12.0 / (3.0 + 2.0 / 2.0 + 1.0 / 3.0);
+        assert!((harmonic_diameter(4, &[4.0, 7.0, 9.0, 10.0])? - expected).abs() < 1e-9);
+        // No connected pairs
+        assert_eq!(harmonic_diameter(3, &[3.0])?, f64::INFINITY);
+        assert!(harmonic_diameter(3, &[]).is_err());
+
+        Ok(())
+    }
 }
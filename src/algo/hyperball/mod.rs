@@ -1,6 +1,7 @@
 //! Module containing implementations and builders for the HyperBall algorithm
 //! in addition to releated utilities.
 
+mod checkpoint;
 mod hyperball_impl;
 
-pub use hyperball_impl::{HyperBall, HyperBallBuilder};
+pub use hyperball_impl::{HyperBall, HyperBallBuilder, IterationStats, Precision};
@@ -0,0 +1,130 @@
+//! Algorithms used to compute the k-core decomposition of a graph.
+//!
+//! # Examples
+//! ```
+//! use dsi_progress_logger::no_logging;
+//! use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
+//! use webgraph_algo::prelude::k_core;
+//!
+//! // A triangle with a pendant node
+//! let arcs = [(0, 1), (1, 2), (2, 0), (2, 3)];
+//! let graph = Left(VecGraph::from_arc_list(arcs));
+//! let transpose = Left(VecGraph::from_arc_list(arcs.map(|(u, v)| (v, u))));
+//!
+//! let cores = k_core::core_numbers(&graph, &transpose, no_logging![]);
+//!
+//! assert_eq!(cores, vec![2, 2, 2, 1]);
+//! ```
+
+use dsi_progress_logger::ProgressLog;
+use webgraph::traits::RandomAccessGraph;
+
+/// Computes the core number of every node using Batagelj and Zaversnik's
+/// algorithm.
+///
+/// The core number of a node `v` is the largest `k` such that `v` belongs to
+/// a subgraph in which all nodes have degree at least `k`.
+///
+/// The decomposition is computed on the undirected version of the graph, in
+/// which the degree of a node is the sum of its indegree and its outdegree.
+/// Thus, each arc is counted as an undirected edge: if the graph is symmetric,
+/// every edge is counted twice. Self-loops are ignored.
+///
+/// The algorithm keeps nodes sorted by current degree using a bin sort, and
+/// runs in time linear in the number of nodes and arcs using four arrays of
+/// `usize` of size the number of nodes.
+///
+/// # Arguments
+/// * `graph`: the graph.
+/// * `transpose`: the transposed of `graph`.
+/// * `pl`: a progress logger.
+pub fn core_numbers(
+    graph: impl RandomAccessGraph,
+    transpose: impl RandomAccessGraph,
+    pl: &mut impl ProgressLog,
+) -> Vec<usize> {
+    let num_nodes = graph.num_nodes();
+    assert_eq!(
+        transpose.num_nodes(),
+        num_nodes,
+        "the transpose should have the same number of nodes of the graph ({}). Got {}.",
+        num_nodes,
+        transpose.num_nodes()
+    );
+
+    pl.item_name("node");
+    pl.expected_updates(Some(num_nodes));
+    pl.start("Computing core numbers...");
+
+    // Degrees in the undirected version of the graph, ignoring self-loops
+    let mut degree: Vec<usize> = (0..num_nodes)
+        .map(|node| {
+            graph
+                .successors(node)
+                .into_iter()
+                .chain(transpose.successors(node))
+                .filter(|&succ| succ != node)
+                .count()
+        })
+        .collect();
+    let max_degree = degree.iter().copied().max().unwrap_or(0);
+
+    // bin[d] is the starting position in vert of the nodes of degree d
+    let mut bin = vec![0; max_degree + 1];
+    for &d in &degree {
+        bin[d] += 1;
+    }
+    let mut start = 0;
+    for b in bin.iter_mut() {
+        let count = *b;
+        *b = start;
+        start += count;
+    }
+
+    // vert contains the nodes sorted by degree, and pos is its inverse
+    let mut vert = vec![0; num_nodes];
+    let mut pos = vec![0; num_nodes];
+    for (node, &d) in degree.iter().enumerate() {
+        pos[node] = bin[d];
+        vert[pos[node]] = node;
+        bin[d] += 1;
+    }
+    // Restore the starting positions
+    for d in (1..=max_degree).rev() {
+        bin[d] = bin[d - 1];
+    }
+    if let Some(b) = bin.first_mut() {
+        *b = 0;
+    }
+
+    for i in 0..num_nodes {
+        let node = vert[i];
+        for succ in graph
+            .successors(node)
+            .into_iter()
+            .chain(transpose.successors(node))
+        {
+            if succ == node || degree[succ] <= degree[node] {
+                continue;
+            }
+            // Move succ to the start of its bin and shrink the bin
+            let succ_degree = degree[succ];
+            let succ_pos = pos[succ];
+            let first_pos = bin[succ_degree];
+            let first = vert[first_pos];
+            if succ != first {
+                pos[succ] = first_pos;
+                vert[succ_pos] = first;
+                pos[first] = succ_pos;
+                vert[first_pos] = succ;
+            }
+            bin[succ_degree] += 1;
+            degree[succ] -= 1;
+        }
+        pl.light_update();
+    }
+
+    pl.done();
+
+    degree
+}
@@ -7,12 +7,34 @@ pub mod exact_sum_sweep;
 pub mod sccs;
 
 mod acyclicity;
-pub use acyclicity::acyclicity;
+pub use acyclicity::{acyclicity, find_cycle};
 mod top_sort;
-pub use top_sort::top_sort;
+pub use top_sort::{top_sort, top_sort_kahn, try_top_sort};
 
 pub mod hyperball;
 
+pub mod betweenness;
+
+pub mod closeness;
+
+pub mod components;
+
+pub mod diameter;
+
+pub mod eccentricity;
+
+pub mod clustering;
+
+pub mod k_core;
+
+pub mod neighbourhood;
+
+pub mod pagerank;
+
+pub mod stats;
+
+pub mod triangles;
+
 /// Traits used to interact with the implemented algorithms.
 pub mod traits {
     use super::*;
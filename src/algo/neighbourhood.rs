@@ -0,0 +1,106 @@
+//! Algorithms used to compute the neighbourhood function of a graph.
+//!
+//! [HyperBall](crate::algo::hyperball) provides an approximation of the
+//! neighbourhood function of large graphs; the algorithms in this module
+//! perform a breadth-first visit from every node, and are thus suitable only
+//! for small graphs, for example to validate approximations.
+//!
+//! # Examples
+//! ```
+//! use dsi_progress_logger::no_logging;
+//! use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
+//! use webgraph_algo::{prelude::neighbourhood, threads};
+//!
+//! let graph = Left(VecGraph::from_arc_list([(0, 1), (1, 2), (2, 3)]));
+//!
+//! let nf = neighbourhood::exact(&graph, &threads![], no_logging![]);
+//!
+//! assert_eq!(nf, vec![4.0, 7.0, 9.0, 10.0]);
+//! ```
+
+use crate::algo::visits::{
+    breadth_first::{EventPred, Seq},
+    Sequential,
+};
+use dsi_progress_logger::{no_logging, ProgressLog};
+use no_break::NoBreak;
+use rayon::{prelude::*, ThreadPool};
+use std::ops::ControlFlow::Continue;
+use webgraph::traits::RandomAccessGraph;
+
+/// Computes the exact neighbourhood function of a graph.
+///
+/// The neighbourhood function is returned as a vector whose element of index
+/// *d* is the number of ordered pairs of nodes (*x*, *y*) such that the
+/// distance from *x* to *y* is at most *d*. Thus, the first element is the
+/// number of nodes, and the last element, of index the maximum finite
+/// distance, is the number of pairs (*x*, *y*) such that *y* is reachable
+/// from *x*. This is the same format used by
+/// [`HyperBall::neighbourhood_function`](crate::algo::hyperball::HyperBall::neighbourhood_function).
+/// The neighbourhood function of a graph with no nodes is empty.
+///
+/// Sources are processed in parallel by [sequential breadth-first
+/// visits](Seq), each thread counting the pairs found by its visits at each
+/// distance in a private vector, and the vectors are summed at the end.
+///
+/// # Arguments
+/// * `graph`: the graph.
+/// * `thread_pool`: The thread pool to use for parallel computation.
+/// * `pl`: A progress logger.
+pub fn exact(
+    graph: impl RandomAccessGraph + Sync,
+    thread_pool: &ThreadPool,
+    pl: &mut impl ProgressLog,
+) -> Vec<f64> {
+    let num_nodes = graph.num_nodes();
+    pl.item_name("node");
+    pl.expected_updates(Some(num_nodes));
+    pl.start("Computing the neighbourhood function...");
+
+    // pairs_at_distance[d] is the number of pairs at distance exactly d
+    let pairs_at_distance = thread_pool.install(|| {
+        (0..num_nodes)
+            .into_par_iter()
+            .fold(
+                || (Seq::new(&graph), Vec::<u64>::new()),
+                |(mut visit, mut pairs_at_distance), src| {
+                    visit.reset();
+                    visit
+                        .visit(
+                            src,
+                            |event| {
+                                if let EventPred::Unknown { distance, .. } = event {
+                                    if pairs_at_distance.len() <= distance {
+                                        pairs_at_distance.resize(distance + 1, 0);
+                                    }
+                                    pairs_at_distance[distance] += 1;
+                                }
+                                Continue(())
+                            },
+                            no_logging![],
+                        )
+                        .continue_value_no_break();
+                    (visit, pairs_at_distance)
+                },
+            )
+            .map(|(_, pairs_at_distance)| pairs_at_distance)
+            .reduce(Vec::new, |mut acc, partial| {
+                if acc.len() < partial.len() {
+                    acc.resize(partial.len(), 0);
+                }
+                acc.iter_mut().zip(partial).for_each(|(a, p)| *a += p);
+                acc
+            })
+    });
+
+    pl.update_with_count(num_nodes);
+    pl.done();
+
+    pairs_at_distance
+        .into_iter()
+        .scan(0, |pairs, pairs_at_distance| {
+            *pairs += pairs_at_distance;
+            Some(*pairs as f64)
+        })
+        .collect()
+}
@@ -0,0 +1,127 @@
+//! Algorithms used to compute the PageRank of a graph's nodes.
+//!
+//! # Examples
+//! ```
+//! use dsi_progress_logger::no_logging;
+//! use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
+//! use webgraph_algo::{prelude::pagerank, threads};
+//!
+//! let arcs = [(0, 1), (1, 2), (2, 0)];
+//! let graph = Left(VecGraph::from_arc_list(arcs));
+//! let transpose = Left(VecGraph::from_arc_list(arcs.map(|(u, v)| (v, u))));
+//!
+//! let rank = pagerank::compute(&graph, &transpose, 0.85, 1e-9, 100, &threads![], no_logging![]);
+//!
+//! for r in rank {
+//!     assert!((r - 1.0 / 3.0).abs() < 1e-9);
+//! }
+//! ```
+
+use crate::utils::check_transposed;
+use dsi_progress_logger::ProgressLog;
+use rayon::{prelude::*, ThreadPool};
+use webgraph::traits::RandomAccessGraph;
+
+/// Computes the PageRank of all nodes by power iteration.
+///
+/// At each iteration, the rank of a node is the sum of the ranks of its
+/// predecessors divided by their outdegree, multiplied by the damping factor,
+/// plus a uniform teleportation term. The rank of dangling nodes (nodes with
+/// outdegree zero) is distributed uniformly among all nodes. The computation
+/// starts from the uniform distribution, and stops when the L1 norm of the
+/// difference between successive rank vectors is smaller than `tolerance`, or
+/// after `max_iter` iterations.
+///
+/// The progress logger is updated, and the current residual is logged, at the
+/// end of each iteration.
+///
+/// # Arguments
+/// * `graph`: the graph.
+/// * `transpose`: the transposed of `graph`.
+/// * `damping`: the damping factor, in the interval [0 . . 1).
+/// * `tolerance`: the threshold on the L1 norm of the difference between
+///   successive rank vectors.
+/// * `max_iter`: the maximum number of iterations.
+/// * `thread_pool`: The thread pool to use for parallel computation.
+/// * `pl`: A progress logger.
+pub fn compute(
+    graph: impl RandomAccessGraph + Sync,
+    transpose: impl RandomAccessGraph + Sync,
+    damping: f64,
+    tolerance: f64,
+    max_iter: usize,
+    thread_pool: &ThreadPool,
+    pl: &mut impl ProgressLog,
+) -> Vec<f64> {
+    let num_nodes = graph.num_nodes();
+    assert_eq!(
+        transpose.num_nodes(),
+        num_nodes,
+        "the transpose should have the same number of nodes of the graph ({}). Got {}.",
+        num_nodes,
+        transpose.num_nodes()
+    );
+    assert!(
+        (0.0..1.0).contains(&damping),
+        "the damping factor should be in [0 . . 1). Got {}.",
+        damping
+    );
+    debug_assert!(check_transposed(&graph, &transpose));
+
+    pl.item_name("iteration");
+    pl.expected_updates(Some(max_iter));
+    pl.start("Computing PageRank...");
+
+    if num_nodes == 0 {
+        pl.done();
+        return Vec::new();
+    }
+
+    let n = num_nodes as f64;
+    let outdegree: Vec<usize> = thread_pool.install(|| {
+        (0..num_nodes)
+            .into_par_iter()
+            .map(|node| graph.outdegree(node))
+            .collect()
+    });
+    let mut rank = vec![1.0 / n; num_nodes];
+    let mut next_rank = vec![0.0; num_nodes];
+
+    for _ in 0..max_iter {
+        let residual = thread_pool.install(|| {
+            let dangling_mass: f64 = (0..num_nodes)
+                .into_par_iter()
+                .filter(|&node| outdegree[node] == 0)
+                .map(|node| rank[node])
+                .sum();
+            let base = (1.0 - damping) / n + damping * dangling_mass / n;
+
+            next_rank
+                .par_iter_mut()
+                .enumerate()
+                .map(|(node, next)| {
+                    let incoming: f64 = transpose
+                        .successors(node)
+                        .into_iter()
+                        .map(|pred| rank[pred] / outdegree[pred] as f64)
+                        .sum();
+                    *next = base + damping * incoming;
+                    (*next - rank[node]).abs()
+                })
+                .sum::<f64>()
+        });
+
+        std::mem::swap(&mut rank, &mut next_rank);
+
+        pl.info(format_args!("Residual: {}", residual));
+        pl.update();
+
+        if residual < tolerance {
+            break;
+        }
+    }
+
+    pl.done();
+
+    rank
+}
@@ -0,0 +1,66 @@
+use super::StronglyConnectedComponents;
+use webgraph::{graphs::vec_graph::VecGraph, traits::RandomAccessGraph};
+
+/// Computes the condensation of a graph.
+///
+/// The condensation has a node for each strongly connected component, and an
+/// arc from component `a` to component `b` if `a` and `b` are distinct and
+/// there is at least one arc in `graph` from a node of `a` to a node of `b`.
+/// Thus, the condensation is acyclic and has no multiple arcs.
+///
+/// The returned graph is unlabeled: wrap it in a
+/// [`Left`](webgraph::labels::Left) projection to use it as a
+/// [`RandomAccessGraph`].
+///
+/// # Arguments
+/// * `graph`: the graph.
+/// * `scc`: the strongly connected components of `graph`.
+///
+/// # Examples
+/// ```
+/// use dsi_progress_logger::no_logging;
+/// use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
+/// use webgraph::traits::{RandomAccessLabeling, SequentialLabeling};
+/// use webgraph_algo::prelude::sccs::*;
+///
+/// let graph = Left(VecGraph::from_arc_list([(0, 1), (1, 0), (1, 2), (0, 2)]));
+/// let scc = tarjan(&graph, no_logging![]);
+/// let condensation = Left(condensation(&graph, &scc));
+///
+/// assert_eq!(condensation.num_nodes(), 2);
+/// assert_eq!(condensation.num_arcs(), 1);
+/// ```
+pub fn condensation(
+    graph: impl RandomAccessGraph,
+    scc: &impl StronglyConnectedComponents,
+) -> VecGraph<()> {
+    let num_components = scc.num_components();
+    let components = scc.components();
+
+    let mut condensation = VecGraph::new();
+    if num_components > 0 {
+        condensation.add_node(num_components - 1);
+    }
+
+    // The last component from which an arc to a given component was added
+    let mut last_source = vec![usize::MAX; num_components];
+    let mut targets = Vec::new();
+
+    for (component, nodes) in scc.components_iter().enumerate() {
+        for node in nodes {
+            for succ in graph.successors(node) {
+                let succ_component = components[succ];
+                if succ_component != component && last_source[succ_component] != component {
+                    last_source[succ_component] = component;
+                    targets.push(succ_component);
+                }
+            }
+        }
+        targets.sort_unstable();
+        for target in targets.drain(..) {
+            condensation.add_arc(component, target);
+        }
+    }
+
+    condensation
+}
@@ -19,17 +19,46 @@ pub fn kosaraju(
     transpose: impl RandomAccessGraph,
     pl: &mut impl ProgressLog,
 ) -> BasicSccs {
-    let num_nodes = graph.num_nodes();
+    let top_sort = top_sort(&graph, pl);
+    kosaraju_with_order(transpose, &top_sort, pl)
+}
+
+/// Computes the strongly connected components of a graph using Kosaraju's
+/// algorithm and a precomputed order.
+///
+/// The order must be the reverse of the order in which nodes are finished
+/// by a depth-first visit of the graph, as returned by
+/// [`top_sort`](crate::algo::top_sort); this function performs just the
+/// second pass of the algorithm, that is, the visits of the transpose.
+///
+/// # Arguments
+/// * `transpose`: the transposed of the graph.
+/// * `order`: the reverse of the depth-first finish order of the graph.
+/// * `pl`: a progress logger.
+///
+/// # Panics
+/// Panics if the length of `order` is not the number of nodes of the graph.
+pub fn kosaraju_with_order(
+    transpose: impl RandomAccessGraph,
+    order: &[usize],
+    pl: &mut impl ProgressLog,
+) -> BasicSccs {
+    let num_nodes = transpose.num_nodes();
+    assert_eq!(
+        order.len(),
+        num_nodes,
+        "the order should contain {} nodes",
+        num_nodes
+    );
     pl.item_name("node");
     pl.expected_updates(Some(num_nodes));
     pl.start("Computing strongly connected components...");
 
-    let top_sort = top_sort(&graph, pl);
     let mut number_of_components = 0;
     let mut visit = SeqNoPred::new(&transpose);
     let mut components = vec![0; num_nodes].into_boxed_slice();
 
-    for &node in &top_sort {
+    for &node in order {
         visit
             .visit(
                 node,
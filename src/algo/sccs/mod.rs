@@ -32,6 +32,16 @@ pub use symm_seq::*;
 mod symm_par;
 pub use symm_par::*;
 
+mod weakly_connected;
+pub use weakly_connected::*;
+
+mod parallel;
+pub use parallel::*;
+
+mod condensation;
+pub use condensation::*;
+
+use crate::utils::math;
 use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
 use webgraph::algo::llp;
 
@@ -60,6 +70,45 @@ pub trait StronglyConnectedComponents {
         sizes.into_boxed_slice()
     }
 
+    /// Returns the index of the component with the largest number of nodes.
+    ///
+    /// If several components have the largest size, the one with the smallest
+    /// index is returned.
+    ///
+    /// # Panics
+    /// Panics if there are no components.
+    fn largest_component(&self) -> usize {
+        math::argmax(&self.compute_sizes()).expect("there should be at least one component")
+    }
+
+    /// Returns the nodes of a component in increasing order.
+    ///
+    /// # Arguments
+    /// * `component`: the index of the component.
+    fn component_nodes(&self, component: usize) -> Vec<usize> {
+        self.components()
+            .iter()
+            .enumerate()
+            .filter(|&(_, &c)| c == component)
+            .map(|(node, _)| node)
+            .collect()
+    }
+
+    /// Returns an iterator over the components, each given by the vector of
+    /// its nodes in increasing order.
+    ///
+    /// The `i`-th item is the vector of nodes of component `i`. The grouping
+    /// is computed in linear time when this method is called, so it is more
+    /// efficient than calling [`component_nodes`](Self::component_nodes) on
+    /// each component.
+    fn components_iter(&self) -> impl Iterator<Item = Vec<usize>> {
+        let mut nodes = vec![Vec::new(); self.num_components()];
+        for (node, &component) in self.components().iter().enumerate() {
+            nodes[component].push(node);
+        }
+        nodes.into_iter()
+    }
+
     /// Renumbers by decreasing size the components of this set.
     ///
     /// After a call to this method, the sizes of strongly connected components
@@ -80,6 +129,51 @@ pub trait StronglyConnectedComponents {
     }
 }
 
+/// Returns whether two strongly connected components decompositions induce the
+/// same partition of the nodes, independently of the numbering of the
+/// components.
+///
+/// The check builds a bijection between the component indices of `a` and `b`
+/// as they are met scanning the nodes in increasing order, and fails as soon
+/// as a node contradicts the bijection.
+///
+/// # Arguments
+/// * `a`: the first decomposition.
+/// * `b`: the second decomposition.
+///
+/// # Examples
+/// ```
+/// use webgraph_algo::prelude::sccs::*;
+///
+/// let a = BasicSccs::new(2, vec![0, 0, 1].into_boxed_slice());
+/// let b = BasicSccs::new(2, vec![1, 1, 0].into_boxed_slice());
+/// let c = BasicSccs::new(2, vec![0, 1, 1].into_boxed_slice());
+///
+/// assert!(same_partition(&a, &b));
+/// assert!(!same_partition(&a, &c));
+/// ```
+pub fn same_partition(
+    a: &impl StronglyConnectedComponents,
+    b: &impl StronglyConnectedComponents,
+) -> bool {
+    if a.num_components() != b.num_components() || a.components().len() != b.components().len() {
+        return false;
+    }
+
+    let mut a_to_b = vec![usize::MAX; a.num_components()];
+    let mut b_to_a = vec![usize::MAX; b.num_components()];
+    for (&ca, &cb) in a.components().iter().zip(b.components()) {
+        if a_to_b[ca] == usize::MAX && b_to_a[cb] == usize::MAX {
+            a_to_b[ca] = cb;
+            b_to_a[cb] = ca;
+        } else if a_to_b[ca] != cb || b_to_a[cb] != ca {
+            return false;
+        }
+    }
+
+    true
+}
+
 /// A standard implementation of the `StronglyConnectedComponents` trait.
 pub struct BasicSccs {
     num_components: usize,
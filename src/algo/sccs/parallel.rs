@@ -0,0 +1,209 @@
+use super::BasicSccs;
+use crate::{
+    algo::visits::{
+        breadth_first::{EventNoPred, ParFairNoPred},
+        Parallel,
+    },
+    utils::check_transposed,
+};
+use dsi_progress_logger::{no_logging, ProgressLog};
+use no_break::NoBreak;
+use rayon::{prelude::*, ThreadPool};
+use std::{
+    ops::ControlFlow::Continue,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use sync_cell_slice::SyncSlice;
+use webgraph::traits::RandomAccessGraph;
+
+const VISIT_GRANULARITY: usize = 32;
+
+/// The color of nodes that have already been assigned to a component.
+const DONE: usize = usize::MAX;
+
+/// Computes the strongly connected components of a graph in parallel using a
+/// forward-backward divide-and-conquer approach.
+///
+/// Nodes are partitioned in subsets, each identified by a color, such that
+/// every strongly connected component is contained in a subset. Initially,
+/// there is a single subset containing all nodes. Each subset is processed as
+/// follows:
+///
+/// 1. nodes with no predecessors or no successors in the subset (ignoring
+///    self-loops) are trimmed, that is, assigned to a component by themselves,
+///    until no such node is left;
+/// 2. a pivot is chosen among the remaining nodes, and the sets of nodes of the
+///    subset reachable from the pivot (forward set) and reaching the pivot
+///    (backward set) are computed by [parallel breadth-first
+///    visits](ParFairNoPred) on `graph` and `transpose`, respectively;
+/// 3. the intersection of the two sets is the component of the pivot, and the
+///    rest of the subset is split in three new subsets: the nodes only in the
+///    forward set, the nodes only in the backward set, and the nodes in
+///    neither set.
+///
+/// Subsets are processed one at a time, but trimming, the choice of the pivot
+/// and the visits are parallel.
+///
+/// # Arguments
+/// * `graph`: the graph.
+/// * `transpose`: the transposed of `graph`.
+/// * `thread_pool`: The thread pool to use for parallel computation.
+/// * `pl`: A progress logger.
+pub fn parallel_scc(
+    graph: impl RandomAccessGraph + Sync,
+    transpose: impl RandomAccessGraph + Sync,
+    thread_pool: &ThreadPool,
+    pl: &mut impl ProgressLog,
+) -> BasicSccs {
+    let num_nodes = graph.num_nodes();
+    assert_eq!(
+        transpose.num_nodes(),
+        num_nodes,
+        "the transpose should have the same number of nodes of the graph ({}). Got {}.",
+        num_nodes,
+        transpose.num_nodes()
+    );
+    debug_assert!(
+        check_transposed(&graph, &transpose),
+        "the transpose should be the transpose of the graph"
+    );
+
+    pl.item_name("node");
+    pl.expected_updates(Some(num_nodes));
+    pl.start("Computing strongly connected components...");
+
+    let color: Box<[AtomicUsize]> = (0..num_nodes).map(|_| AtomicUsize::new(0)).collect();
+    let mut components = vec![0; num_nodes].into_boxed_slice();
+    let components_sync = components.as_sync_slice();
+    let number_of_components = AtomicUsize::new(0);
+    let mut next_color = 1;
+
+    let mut forward_visit = ParFairNoPred::new(&graph, VISIT_GRANULARITY);
+    let mut backward_visit = ParFairNoPred::new(&transpose, VISIT_GRANULARITY);
+
+    // Subsets still to be processed, with their color
+    let mut subsets = vec![(0, (0..num_nodes).collect::<Vec<_>>())];
+
+    while let Some((subset_color, mut nodes)) = subsets.pop() {
+        // Trim nodes that are trivially components by themselves. Removing a
+        // node that forms a component by itself does not change the other
+        // components, so nodes can be trimmed concurrently.
+        loop {
+            let before = nodes.len();
+            nodes = thread_pool.install(|| {
+                nodes
+                    .into_par_iter()
+                    .filter(|&node| {
+                        let in_subset = |succ: usize| {
+                            succ != node && color[succ].load(Ordering::Relaxed) == subset_color
+                        };
+                        if graph.successors(node).into_iter().any(in_subset)
+                            && transpose.successors(node).into_iter().any(in_subset)
+                        {
+                            return true;
+                        }
+                        color[node].store(DONE, Ordering::Relaxed);
+                        let component = number_of_components.fetch_add(1, Ordering::Relaxed);
+                        // Safety: each node is assigned to a component exactly once
+                        unsafe { components_sync[node].set(component) };
+                        false
+                    })
+                    .collect()
+            });
+            pl.update_with_count(before - nodes.len());
+            if nodes.len() == before {
+                break;
+            }
+        }
+
+        if nodes.is_empty() {
+            continue;
+        }
+
+        // Nodes with high indegree and outdegree are likely to belong to
+        // large components
+        let pivot = thread_pool.install(|| {
+            nodes
+                .par_iter()
+                .copied()
+                .max_by_key(|&node| graph.outdegree(node) * transpose.outdegree(node))
+                .unwrap()
+        });
+
+        let forward_color = next_color;
+        let backward_color = next_color + 1;
+        next_color += 2;
+        let pivot_component = number_of_components.fetch_add(1, Ordering::Relaxed);
+
+        forward_visit
+            .par_visit_filtered(
+                pivot,
+                |event| {
+                    if let EventNoPred::Unknown { curr, .. } = event {
+                        color[curr].store(forward_color, Ordering::Relaxed);
+                    }
+                    Continue(())
+                },
+                |args| color[args.curr].load(Ordering::Relaxed) == subset_color,
+                thread_pool,
+                no_logging![],
+            )
+            .continue_value_no_break();
+
+        backward_visit
+            .par_visit_filtered(
+                pivot,
+                |event| {
+                    if let EventNoPred::Unknown { curr, .. } = event {
+                        if color[curr].load(Ordering::Relaxed) == forward_color {
+                            color[curr].store(DONE, Ordering::Relaxed);
+                            // Safety: each node is assigned to a component
+                            // exactly once
+                            unsafe { components_sync[curr].set(pivot_component) };
+                        } else {
+                            color[curr].store(backward_color, Ordering::Relaxed);
+                        }
+                    }
+                    Continue(())
+                },
+                |args| {
+                    let c = color[args.curr].load(Ordering::Relaxed);
+                    c == subset_color || c == forward_color
+                },
+                thread_pool,
+                no_logging![],
+            )
+            .continue_value_no_break();
+
+        // The visits only know nodes of the subset, so resetting them costs
+        // as much as processing the subset, rather than the whole graph
+        forward_visit.reset_nodes(&nodes);
+        backward_visit.reset_nodes(&nodes);
+
+        let (mut forward, mut backward, mut neither) = (vec![], vec![], vec![]);
+        let mut component_size = 0;
+        for node in nodes {
+            match color[node].load(Ordering::Relaxed) {
+                DONE => component_size += 1,
+                c if c == forward_color => forward.push(node),
+                c if c == backward_color => backward.push(node),
+                _ => neither.push(node),
+            }
+        }
+        pl.update_with_count(component_size);
+
+        for (c, subset) in [
+            (forward_color, forward),
+            (backward_color, backward),
+            (subset_color, neither),
+        ] {
+            if !subset.is_empty() {
+                subsets.push((c, subset));
+            }
+        }
+    }
+
+    pl.done();
+
+    BasicSccs::new(number_of_components.into_inner(), components)
+}
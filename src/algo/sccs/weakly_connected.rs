@@ -0,0 +1,67 @@
+use super::BasicSccs;
+use dsi_progress_logger::ProgressLog;
+use std::collections::VecDeque;
+use webgraph::traits::RandomAccessGraph;
+
+/// Computes the weakly connected components of a graph.
+///
+/// Weakly connected components are the connected components of the graph
+/// obtained by ignoring the direction of arcs: they are computed by
+/// breadth-first visits in which the successors of a node are its successors
+/// in `graph` together with its successors in `transpose`. In particular, a
+/// node with no incoming or outgoing arcs forms a component by itself.
+///
+/// # Arguments
+/// * `graph`: the graph.
+/// * `transpose`: the transposed of `graph`.
+/// * `pl`: a progress logger.
+pub fn weakly_connected(
+    graph: impl RandomAccessGraph,
+    transpose: impl RandomAccessGraph,
+    pl: &mut impl ProgressLog,
+) -> BasicSccs {
+    let num_nodes = graph.num_nodes();
+    assert_eq!(
+        transpose.num_nodes(),
+        num_nodes,
+        "the transpose should have the same number of nodes of the graph ({}). Got {}.",
+        num_nodes,
+        transpose.num_nodes()
+    );
+
+    pl.item_name("node");
+    pl.expected_updates(Some(num_nodes));
+    pl.start("Computing weakly connected components...");
+
+    let mut components = vec![usize::MAX; num_nodes].into_boxed_slice();
+    let mut number_of_components = 0;
+    let mut queue = VecDeque::new();
+
+    for root in 0..num_nodes {
+        if components[root] != usize::MAX {
+            continue;
+        }
+        components[root] = number_of_components;
+        queue.push_back(root);
+
+        while let Some(node) = queue.pop_front() {
+            pl.light_update();
+            for succ in graph
+                .successors(node)
+                .into_iter()
+                .chain(transpose.successors(node))
+            {
+                if components[succ] == usize::MAX {
+                    components[succ] = number_of_components;
+                    queue.push_back(succ);
+                }
+            }
+        }
+
+        number_of_components += 1;
+    }
+
+    pl.done();
+
+    BasicSccs::new(number_of_components, components)
+}
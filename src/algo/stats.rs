@@ -0,0 +1,218 @@
+//! Algorithms used to compute simple statistics of a graph.
+//!
+//! # Examples
+//! ```
+//! use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
+//! use webgraph_algo::{prelude::stats, threads};
+//!
+//! let graph = Left(VecGraph::from_arc_list([(0, 1), (0, 2), (1, 2)]));
+//! let transpose = Left(VecGraph::from_arc_list([(1, 0), (2, 0), (2, 1)]));
+//!
+//! let (outdegrees, indegrees) = stats::degree_distribution(&graph, &transpose, &threads![]);
+//!
+//! assert_eq!(outdegrees, vec![1, 1, 1]);
+//! assert_eq!(indegrees, vec![1, 1, 1]);
+//! ```
+
+use crate::utils::check_transposed;
+use rayon::{prelude::*, ThreadPool};
+use webgraph::traits::RandomAccessGraph;
+
+/// Returns the histogram of the outdegrees of a graph.
+///
+/// Each thread accumulates the degrees of its nodes in a private histogram
+/// that grows as needed, and histograms are summed at the end, so the
+/// result is sized to the maximum degree plus one.
+fn degree_histogram(graph: impl RandomAccessGraph + Sync, thread_pool: &ThreadPool) -> Vec<u64> {
+    thread_pool.install(|| {
+        (0..graph.num_nodes())
+            .into_par_iter()
+            .fold(Vec::new, |mut histogram, node| {
+                let degree = graph.outdegree(node);
+                if degree >= histogram.len() {
+                    histogram.resize(degree + 1, 0);
+                }
+                histogram[degree] += 1;
+                histogram
+            })
+            .reduce(Vec::new, |mut acc, mut partial| {
+                if acc.len() < partial.len() {
+                    std::mem::swap(&mut acc, &mut partial);
+                }
+                acc.iter_mut().zip(partial).for_each(|(a, p)| *a += p);
+                acc
+            })
+    })
+}
+
+/// Computes the outdegree and indegree distributions of a graph.
+///
+/// Returns a pair of histograms: the element of index `d` of the first
+/// (second) vector is the number of nodes with outdegree (indegree) `d`.
+/// Each vector is sized to the maximum corresponding degree plus one, so
+/// its last element is always nonzero, and it is empty if the graph has no
+/// nodes.
+///
+/// # Arguments
+/// * `graph`: the graph.
+/// * `transpose`: the transpose of `graph`.
+/// * `thread_pool`: The thread pool to use for parallel computation.
+pub fn degree_distribution(
+    graph: impl RandomAccessGraph + Sync,
+    transpose: impl RandomAccessGraph + Sync,
+    thread_pool: &ThreadPool,
+) -> (Vec<u64>, Vec<u64>) {
+    debug_assert!(check_transposed(&graph, &transpose));
+
+    (
+        degree_histogram(graph, thread_pool),
+        degree_histogram(transpose, thread_pool),
+    )
+}
+
+/// Computes the reciprocity of a graph.
+///
+/// The reciprocity is the fraction of arcs `(u, v)` for which the arc `(v, u)`
+/// also exists; in particular, it is one if and only if the graph is
+/// symmetric. It is computed by intersecting, for each node, its successors
+/// in `graph` and in `transpose`, that is, its successors and its
+/// predecessors. Loops are their own reverse, so they are always counted as
+/// reciprocal.
+///
+/// If the graph has no arcs, it is symmetric and one is returned.
+///
+/// # Arguments
+/// * `graph`: the graph.
+/// * `transpose`: the transpose of `graph`.
+/// * `thread_pool`: The thread pool to use for parallel computation.
+pub fn reciprocity(
+    graph: impl RandomAccessGraph + Sync,
+    transpose: impl RandomAccessGraph + Sync,
+    thread_pool: &ThreadPool,
+) -> f64 {
+    debug_assert!(check_transposed(&graph, &transpose));
+
+    let (num_arcs, num_reciprocal) = thread_pool.install(|| {
+        (0..graph.num_nodes())
+            .into_par_iter()
+            .fold(
+                || (0_u64, 0_u64, Vec::new()),
+                |(mut num_arcs, mut num_reciprocal, mut preds), node| {
+                    preds.clear();
+                    preds.extend(transpose.successors(node));
+                    preds.sort_unstable();
+                    for succ in graph.successors(node) {
+                        num_arcs += 1;
+                        if preds.binary_search(&succ).is_ok() {
+                            num_reciprocal += 1;
+                        }
+                    }
+                    (num_arcs, num_reciprocal, preds)
+                },
+            )
+            .map(|(num_arcs, num_reciprocal, _)| (num_arcs, num_reciprocal))
+            .reduce(|| (0, 0), |(a0, r0), (a1, r1)| (a0 + a1, r0 + r1))
+    });
+
+    if num_arcs == 0 {
+        1.0
+    } else {
+        num_reciprocal as f64 / num_arcs as f64
+    }
+}
+
+/// Summary statistics of a graph.
+///
+/// See [`summary`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GraphSummary {
+    /// The number of nodes.
+    pub num_nodes: usize,
+    /// The number of arcs.
+    pub num_arcs: u64,
+    /// The density, that is, the number of arcs divided by the number of
+    /// possible arcs between distinct nodes, or zero if the graph has less
+    /// than two nodes.
+    pub density: f64,
+    /// The maximum outdegree, or zero if the graph has no nodes.
+    pub max_out_degree: usize,
+    /// The maximum indegree, or zero if the graph has no nodes.
+    pub max_in_degree: usize,
+    /// The number of loops, that is, of arcs from a node to itself.
+    pub num_self_loops: u64,
+    /// The number of sources, that is, of nodes with indegree zero.
+    pub num_sources: usize,
+    /// The number of sinks, that is, of nodes with outdegree zero.
+    pub num_sinks: usize,
+}
+
+/// Computes summary statistics of a graph.
+///
+/// All statistics are computed in a single parallel pass over the nodes.
+/// The density is the number of arcs *m* divided by *n*(*n* – 1), where *n*
+/// is the number of nodes, so it can be larger than one if the graph has
+/// loops.
+///
+/// # Arguments
+/// * `graph`: the graph.
+/// * `transpose`: the transpose of `graph`.
+/// * `thread_pool`: The thread pool to use for parallel computation.
+pub fn summary(
+    graph: impl RandomAccessGraph + Sync,
+    transpose: impl RandomAccessGraph + Sync,
+    thread_pool: &ThreadPool,
+) -> GraphSummary {
+    debug_assert!(check_transposed(&graph, &transpose));
+
+    let num_nodes = graph.num_nodes();
+    let zero = GraphSummary {
+        num_nodes,
+        num_arcs: 0,
+        density: 0.0,
+        max_out_degree: 0,
+        max_in_degree: 0,
+        num_self_loops: 0,
+        num_sources: 0,
+        num_sinks: 0,
+    };
+
+    let mut summary = thread_pool.install(|| {
+        (0..num_nodes)
+            .into_par_iter()
+            .fold(
+                || zero,
+                |mut summary, node| {
+                    let outdegree = graph.outdegree(node);
+                    let indegree = transpose.outdegree(node);
+                    summary.num_arcs += outdegree as u64;
+                    summary.max_out_degree = summary.max_out_degree.max(outdegree);
+                    summary.max_in_degree = summary.max_in_degree.max(indegree);
+                    summary.num_self_loops += graph
+                        .successors(node)
+                        .into_iter()
+                        .filter(|&succ| succ == node)
+                        .count() as u64;
+                    summary.num_sources += (indegree == 0) as usize;
+                    summary.num_sinks += (outdegree == 0) as usize;
+                    summary
+                },
+            )
+            .reduce(
+                || zero,
+                |a, b| GraphSummary {
+                    num_arcs: a.num_arcs + b.num_arcs,
+                    max_out_degree: a.max_out_degree.max(b.max_out_degree),
+                    max_in_degree: a.max_in_degree.max(b.max_in_degree),
+                    num_self_loops: a.num_self_loops + b.num_self_loops,
+                    num_sources: a.num_sources + b.num_sources,
+                    num_sinks: a.num_sinks + b.num_sinks,
+                    ..a
+                },
+            )
+    });
+
+    if num_nodes > 1 {
+        summary.density = summary.num_arcs as f64 / (num_nodes as f64 * (num_nodes - 1) as f64);
+    }
+    summary
+}
@@ -1,7 +1,15 @@
-use crate::{algo::visits::depth_first::*, algo::visits::Sequential};
+use crate::{
+    algo::find_cycle, algo::visits::depth_first::*, algo::visits::Sequential,
+    utils::check_transposed,
+};
+use anyhow::{ensure, Result};
 use dsi_progress_logger::ProgressLog;
 use no_break::NoBreak;
-use std::ops::ControlFlow::Continue;
+use rayon::{prelude::*, ThreadPool};
+use std::{
+    ops::ControlFlow::Continue,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 use webgraph::traits::RandomAccessGraph;
 
 /// Returns the node of the graph in topological-sort order, if the graph is acyclic.
@@ -35,3 +43,115 @@ pub fn top_sort(graph: impl RandomAccessGraph, pl: &mut impl ProgressLog) -> Box
     // SAFETY: we write in each element of top_sort
     unsafe { topol_sort.assume_init() }
 }
+
+/// Returns the nodes of the graph in topological-sort order if the graph is
+/// acyclic, or a cycle of the graph otherwise.
+///
+/// Differently from [`top_sort`], which returns an order even if the graph
+/// is cyclic, this function makes it impossible to use by mistake an order
+/// that is not topological. The cycle is returned as by [`find_cycle`].
+///
+/// # Arguments
+/// * `graph`: the graph.
+/// * `pl`: A progress logger.
+///
+/// # Examples
+/// ```
+/// use dsi_progress_logger::no_logging;
+/// use webgraph::{labels::Left, prelude::VecGraph};
+/// use webgraph_algo::algo::try_top_sort;
+///
+/// let graph = Left(VecGraph::from_arc_list([(0, 2), (1, 0)]));
+/// assert_eq!(try_top_sort(&graph, no_logging![]), Ok(vec![1, 0, 2].into_boxed_slice()));
+///
+/// let graph = Left(VecGraph::from_arc_list([(0, 1), (1, 0)]));
+/// assert_eq!(try_top_sort(&graph, no_logging![]), Err(vec![0, 1]));
+/// ```
+pub fn try_top_sort(
+    graph: impl RandomAccessGraph,
+    pl: &mut impl ProgressLog,
+) -> Result<Box<[usize]>, Vec<usize>> {
+    match find_cycle(&graph, pl) {
+        Some(cycle) => Err(cycle),
+        None => Ok(top_sort(&graph, pl)),
+    }
+}
+
+/// Returns the nodes of an acyclic graph in topological-sort order using a
+/// parallel version of Kahn's algorithm.
+///
+/// The indegrees of the nodes are computed using the transpose, and the
+/// nodes with indegree zero form the first layer. Then, the layers are
+/// processed one at a time: the successors of the nodes of the current layer
+/// are scanned in parallel, decreasing their indegree, and the nodes whose
+/// indegree becomes zero form the next layer. The concatenation of the layers
+/// is a topological sort.
+///
+/// Differently from [`top_sort`], the graph is not visited in depth-first
+/// order, so the result is in general different, and it depends on the
+/// number of threads.
+///
+/// # Arguments
+/// * `graph`: the graph.
+/// * `transpose`: the transpose of `graph`.
+/// * `thread_pool`: The thread pool to use for parallel computation.
+/// * `pl`: A progress logger.
+///
+/// # Errors
+/// Returns an error if the graph is not acyclic, in which case some nodes
+/// never reach indegree zero.
+pub fn top_sort_kahn(
+    graph: impl RandomAccessGraph + Sync,
+    transpose: impl RandomAccessGraph + Sync,
+    thread_pool: &ThreadPool,
+    pl: &mut impl ProgressLog,
+) -> Result<Box<[usize]>> {
+    debug_assert!(check_transposed(&graph, &transpose));
+    let num_nodes = graph.num_nodes();
+    pl.item_name("node");
+    pl.expected_updates(Some(num_nodes));
+    pl.start("Computing topological sort with Kahn's algorithm");
+
+    let mut topol_sort = Vec::with_capacity(num_nodes);
+
+    let indegrees: Vec<AtomicUsize> = thread_pool.install(|| {
+        (0..num_nodes)
+            .into_par_iter()
+            .map(|node| AtomicUsize::new(transpose.outdegree(node)))
+            .collect()
+    });
+
+    let mut layer: Vec<usize> = thread_pool.install(|| {
+        (0..num_nodes)
+            .into_par_iter()
+            .filter(|&node| indegrees[node].load(Ordering::Relaxed) == 0)
+            .collect()
+    });
+
+    while !layer.is_empty() {
+        let next_layer = thread_pool.install(|| {
+            layer
+                .par_iter()
+                .flat_map_iter(|&node| {
+                    graph.successors(node).into_iter().filter(|&succ| {
+                        // Only the last predecessor adds the node
+                        indegrees[succ].fetch_sub(1, Ordering::Relaxed) == 1
+                    })
+                })
+                .collect()
+        });
+        pl.update_with_count(layer.len());
+        topol_sort.append(&mut layer);
+        layer = next_layer;
+    }
+
+    pl.done();
+
+    ensure!(
+        topol_sort.len() == num_nodes,
+        "The graph is not acyclic: {} nodes are part of or reachable from a cycle",
+        num_nodes - topol_sort.len()
+    );
+
+    Ok(topol_sort.into_boxed_slice())
+}
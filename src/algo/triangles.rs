@@ -0,0 +1,157 @@
+//! Algorithms used to count the triangles of a graph.
+//!
+//! # Examples
+//! ```
+//! use dsi_progress_logger::no_logging;
+//! use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
+//! use webgraph_algo::{prelude::triangles, threads};
+//!
+//! // A triangle with a pendant node, as a symmetric graph
+//! let graph = Left(VecGraph::from_arc_list([
+//!     (0, 1), (1, 0), (1, 2), (2, 1), (2, 0), (0, 2), (2, 3), (3, 2),
+//! ]));
+//!
+//! let (global, per_node) = triangles::count(&graph, &threads![], no_logging![]);
+//!
+//! assert_eq!(global, 1);
+//! assert_eq!(per_node, vec![1, 1, 1, 0]);
+//! ```
+
+use crate::utils::check_symmetric;
+use dsi_progress_logger::ProgressLog;
+use rayon::{prelude::*, ThreadPool};
+use std::sync::atomic::{AtomicU64, Ordering};
+use webgraph::traits::RandomAccessGraph;
+
+/// Counts the triangles of a symmetric graph.
+///
+/// Returns the number of triangles of the graph and, for each node, the number
+/// of triangles it belongs to. Multiple arcs and self-loops are ignored.
+///
+/// The computation uses the node-iterator algorithm: arcs are oriented from the
+/// endpoint with smaller degree to the endpoint with larger degree (ties are
+/// broken by node index), and for each oriented arc the forward neighbors of
+/// its endpoints are intersected. In this way every triangle is found exactly
+/// once. Nodes are processed in parallel.
+///
+/// # Arguments
+/// * `graph`: the graph. It must be symmetric.
+/// * `thread_pool`: The thread pool to use for parallel computation.
+/// * `pl`: A progress logger.
+pub fn count(
+    graph: impl RandomAccessGraph + Sync,
+    thread_pool: &ThreadPool,
+    pl: &mut impl ProgressLog,
+) -> (u64, Vec<u64>) {
+    debug_assert!(check_symmetric(&graph));
+
+    let num_nodes = graph.num_nodes();
+    pl.item_name("node");
+    pl.expected_updates(Some(num_nodes));
+    pl.start("Counting triangles...");
+
+    let (global, per_node, _) = thread_pool.install(|| {
+        count_from_neighbors(num_nodes, |node, neighbors| {
+            neighbors.extend(graph.successors(node))
+        })
+    });
+
+    pl.update_with_count(num_nodes);
+    pl.done();
+
+    (global, per_node)
+}
+
+/// Counts the triangles of the undirected graph whose neighbors are
+/// enumerated by `neighbors`, returning the number of triangles, the number of
+/// triangles of each node, and the degree of each node.
+///
+/// The computation runs in the current thread pool.
+///
+/// # Arguments
+/// * `num_nodes`: the number of nodes of the graph.
+/// * `neighbors`: a function appending to a vector the neighbors of a node;
+///   the neighbors may contain duplicates and the node itself, which will be
+///   ignored.
+pub(crate) fn count_from_neighbors(
+    num_nodes: usize,
+    neighbors: impl Fn(usize, &mut Vec<usize>) + Sync,
+) -> (u64, Vec<u64>, Vec<usize>) {
+    let degree: Vec<usize> = (0..num_nodes)
+        .into_par_iter()
+        .map_init(Vec::new, |buffer, node| {
+            undirected_neighbors(&neighbors, node, buffer);
+            buffer.len()
+        })
+        .collect();
+    let per_node: Vec<AtomicU64> = (0..num_nodes).map(|_| AtomicU64::new(0)).collect();
+
+    let global = (0..num_nodes)
+        .into_par_iter()
+        .map_init(
+            || (Vec::new(), Vec::new()),
+            |(forward, other), node| {
+                forward_neighbors(&neighbors, &degree, node, forward);
+                let mut triangles = 0_u64;
+                for &succ in forward.iter() {
+                    forward_neighbors(&neighbors, &degree, succ, other);
+                    for_each_common(forward.as_slice(), other.as_slice(), |third| {
+                        triangles += 1;
+                        per_node[node].fetch_add(1, Ordering::Relaxed);
+                        per_node[succ].fetch_add(1, Ordering::Relaxed);
+                        per_node[third].fetch_add(1, Ordering::Relaxed);
+                    });
+                }
+                triangles
+            },
+        )
+        .sum::<u64>();
+
+    (
+        global,
+        per_node.into_iter().map(AtomicU64::into_inner).collect(),
+        degree,
+    )
+}
+
+/// Fills `buffer` with the sorted neighbors of `node`, without duplicates and
+/// without `node` itself.
+fn undirected_neighbors(
+    neighbors: &impl Fn(usize, &mut Vec<usize>),
+    node: usize,
+    buffer: &mut Vec<usize>,
+) {
+    buffer.clear();
+    neighbors(node, buffer);
+    buffer.retain(|&succ| succ != node);
+    buffer.sort_unstable();
+    buffer.dedup();
+}
+
+/// Fills `buffer` with the sorted neighbors of `node` that follow it in the
+/// degree ordering, without duplicates.
+fn forward_neighbors(
+    neighbors: &impl Fn(usize, &mut Vec<usize>),
+    degree: &[usize],
+    node: usize,
+    buffer: &mut Vec<usize>,
+) {
+    undirected_neighbors(neighbors, node, buffer);
+    buffer.retain(|&succ| (degree[node], node) < (degree[succ], succ));
+}
+
+/// Calls `f` on each element common to two sorted slices.
+pub(crate) fn for_each_common(a: &[usize], b: &[usize], mut f: impl FnMut(usize)) {
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                f(a[i]);
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use crate::utils::check_transposed;
+use dsi_progress_logger::ProgressLog;
+use parallel_frontier::prelude::{Frontier, ParallelIterator};
+use rayon::{prelude::*, ThreadPool};
+use std::{
+    ops::ControlFlow::{Break, Continue},
+    sync::atomic::Ordering,
+};
+use sux::bits::AtomicBitVec;
+use webgraph::traits::RandomAccessGraph;
+
+/// Parallel bidirectional breadth-first visits.
+///
+/// This visit computes the distance between a pair of nodes by expanding
+/// alternately a breadth-first visit from the source on the graph and a
+/// breadth-first visit from the destination on the transpose. At each step the
+/// smaller frontier is expanded, probing the set of nodes known to the other
+/// visit: the visit stops as soon as the two visits meet.
+///
+/// The visit uses two bits per node to remember known nodes and two queues of
+/// `usize` representing the frontiers of the two visits.
+///
+/// # Examples
+///
+/// ```
+/// use webgraph_algo::algo::visits::breadth_first;
+/// use webgraph_algo::threads;
+/// use dsi_progress_logger::no_logging;
+/// use webgraph::graphs::vec_graph::VecGraph;
+/// use webgraph::labels::proj::Left;
+///
+/// let graph = Left(VecGraph::from_arc_list([(0, 1), (1, 2), (2, 0), (1, 3)]));
+/// let transpose = Left(VecGraph::from_arc_list([(1, 0), (2, 1), (0, 2), (3, 1)]));
+/// let mut visit = breadth_first::BiDir::new(&graph, &transpose, 1);
+///
+/// assert_eq!(visit.shortest_distance(0, 3, &threads![], no_logging![]), Some(2));
+/// assert_eq!(visit.shortest_distance(3, 0, &threads![], no_logging![]), None);
+/// assert_eq!(visit.shortest_distance(2, 2, &threads![], no_logging![]), Some(0));
+/// ```
+pub struct BiDir<G1: RandomAccessGraph, G2: RandomAccessGraph> {
+    graph: G1,
+    transpose: G2,
+    granularity: usize,
+    forward_visited: AtomicBitVec,
+    backward_visited: AtomicBitVec,
+}
+
+impl<G1: RandomAccessGraph + Sync, G2: RandomAccessGraph + Sync> BiDir<G1, G2> {
+    /// Creates a parallel bidirectional breadth-first visit.
+    ///
+    /// # Arguments
+    /// * `graph`: the graph to visit.
+    /// * `transpose`: the transpose of `graph`.
+    /// * `granularity`: the number of nodes per chunk. High granularity reduces
+    ///   overhead, but may lead to decreased performance on graphs with a
+    ///   skewed outdegree distribution.
+    pub fn new(graph: G1, transpose: G2, granularity: usize) -> Self {
+        let num_nodes = graph.num_nodes();
+        assert_eq!(
+            transpose.num_nodes(),
+            num_nodes,
+            "the transpose should have the same number of nodes of the graph ({}). Got {}.",
+            num_nodes,
+            transpose.num_nodes()
+        );
+        debug_assert!(
+            check_transposed(&graph, &transpose),
+            "the transpose should be the transpose of the graph"
+        );
+        Self {
+            graph,
+            transpose,
+            granularity,
+            forward_visited: AtomicBitVec::new(num_nodes),
+            backward_visited: AtomicBitVec::new(num_nodes),
+        }
+    }
+
+    /// Returns the length of a shortest path from `src` to `dst`, or [`None`]
+    /// if `dst` is not reachable from `src`.
+    ///
+    /// The progress logger will be updated each time a level of one of the
+    /// two visits has been processed.
+    ///
+    /// # Arguments
+    /// * `src`: the source node.
+    /// * `dst`: the destination node.
+    /// * `thread_pool`: The thread pool to use for parallel computation.
+    /// * `pl`: A progress logger.
+    pub fn shortest_distance(
+        &mut self,
+        src: usize,
+        dst: usize,
+        thread_pool: &ThreadPool,
+        pl: &mut impl ProgressLog,
+    ) -> Option<usize> {
+        if src == dst {
+            return Some(0);
+        }
+
+        thread_pool.install(|| {
+            self.forward_visited.fill(false, Ordering::Relaxed);
+            self.backward_visited.fill(false, Ordering::Relaxed);
+        });
+
+        // We do not provide a capacity in the hope of allocating dynamically
+        // space as the frontiers grow.
+        let mut forward_frontier = Frontier::with_threads(thread_pool, None);
+        let mut backward_frontier = Frontier::with_threads(thread_pool, None);
+        let mut next_frontier = Frontier::with_threads(thread_pool, None);
+
+        thread_pool.install(|| {
+            forward_frontier.push(src);
+            backward_frontier.push(dst);
+        });
+        self.forward_visited.set(src, true, Ordering::Relaxed);
+        self.backward_visited.set(dst, true, Ordering::Relaxed);
+
+        let mut forward_distance = 0;
+        let mut backward_distance = 0;
+
+        while !forward_frontier.is_empty() && !backward_frontier.is_empty() {
+            // We expand the smaller frontier, probing the visited set of the
+            // other visit
+            let forward = forward_frontier.len() <= backward_frontier.len();
+            let (frontier, visited, other_visited) = if forward {
+                (
+                    &forward_frontier,
+                    &self.forward_visited,
+                    &self.backward_visited,
+                )
+            } else {
+                (
+                    &backward_frontier,
+                    &self.backward_visited,
+                    &self.forward_visited,
+                )
+            };
+
+            let met = thread_pool.install(|| {
+                frontier
+                    .par_iter()
+                    .chunks(self.granularity)
+                    .try_for_each(|chunk| {
+                        chunk.into_iter().try_for_each(|&node| {
+                            let mut expand = |succ: usize| {
+                                if other_visited.get(succ, Ordering::Relaxed) {
+                                    return Break(());
+                                }
+                                if !visited.swap(succ, true, Ordering::Relaxed) {
+                                    next_frontier.push(succ);
+                                }
+                                Continue(())
+                            };
+                            if forward {
+                                self.graph
+                                    .successors(node)
+                                    .into_iter()
+                                    .try_for_each(&mut expand)
+                            } else {
+                                self.transpose
+                                    .successors(node)
+                                    .into_iter()
+                                    .try_for_each(&mut expand)
+                            }
+                        })
+                    })
+                    .is_break()
+            });
+            pl.update_with_count(frontier.len());
+
+            if forward {
+                forward_distance += 1;
+                std::mem::swap(&mut forward_frontier, &mut next_frontier);
+            } else {
+                backward_distance += 1;
+                std::mem::swap(&mut backward_frontier, &mut next_frontier);
+            }
+            next_frontier.clear();
+
+            if met {
+                // The two visits did not meet at the previous level, so this
+                // is the length of a shortest path.
+                return Some(forward_distance + backward_distance);
+            }
+        }
+
+        None
+    }
+}
@@ -17,6 +17,73 @@ pub use par_fair::*;
 mod par_low_mem;
 pub use par_low_mem::*;
 
+mod bidir;
+pub use bidir::*;
+
+use rayon::ThreadPool;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The work done by a thread during instrumented parallel visits (see, e.g.,
+/// [`ParFairBase::instrument`] and [`ParLowMem::instrument`]).
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub struct ThreadWork {
+    /// The number of nodes whose successors have been enumerated by the
+    /// thread.
+    pub nodes: usize,
+    /// The number of arcs enumerated by the thread.
+    pub arcs: usize,
+}
+
+/// Per-thread counters of the work done by a parallel visit, indexed by the
+/// index of the thread in the thread pool of the visit.
+#[derive(Debug, Default)]
+struct WorkCounters {
+    enabled: bool,
+    counters: Vec<(AtomicUsize, AtomicUsize)>,
+}
+
+impl WorkCounters {
+    /// Makes room for the counters of the threads of `thread_pool`, if the
+    /// counters are enabled.
+    fn prepare(&mut self, thread_pool: &ThreadPool) {
+        if self.enabled && self.counters.len() < thread_pool.current_num_threads() {
+            self.counters
+                .resize_with(thread_pool.current_num_threads(), Default::default);
+        }
+    }
+
+    /// Records that the current thread has enumerated the `arcs` successors
+    /// of a node.
+    ///
+    /// Work done by threads outside of `thread_pool`, as in the case of
+    /// deterministic visits, is attributed to the thread of index zero.
+    #[inline(always)]
+    fn record(&self, thread_pool: &ThreadPool, arcs: usize) {
+        if self.enabled {
+            let (nodes_counter, arcs_counter) =
+                &self.counters[thread_pool.current_thread_index().unwrap_or(0)];
+            nodes_counter.fetch_add(1, Ordering::Relaxed);
+            arcs_counter.fetch_add(arcs, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the work done by each thread.
+    fn thread_work(&self) -> Vec<ThreadWork> {
+        self.counters
+            .iter()
+            .map(|(nodes, arcs)| ThreadWork {
+                nodes: nodes.load(Ordering::Relaxed),
+                arcs: arcs.load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+
+    /// Clears the counters.
+    fn clear(&mut self) {
+        self.counters.clear();
+    }
+}
+
 /// Types of callback events generated during breadth-first visits
 /// keeping track of parent nodes.
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -1,12 +1,17 @@
 use crate::algo::visits::{breadth_first::*, Parallel};
 use dsi_progress_logger::ProgressLog;
+use no_break::NoBreak;
 use parallel_frontier::prelude::{Frontier, ParallelIterator};
 use rayon::{prelude::*, ThreadPool};
 use std::{
     ops::ControlFlow::{self, Continue},
-    sync::atomic::Ordering,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
 };
 use sux::bits::AtomicBitVec;
+use sync_cell_slice::SyncSlice;
 use webgraph::traits::RandomAccessGraph;
 
 /// Fair parallel breadth-first visits.
@@ -28,7 +33,8 @@ use webgraph::traits::RandomAccessGraph;
 ///
 /// Each type of visit uses incrementally more space:
 /// * [`ParFairNoPred`] uses one bit per node to remember known nodes and a
-///   queue of `usize` representing nodes;
+///   queue of pairs of `usize` representing nodes and the roots from which
+///   they have been reached;
 /// * [`ParFairPred`] uses one bit per node to remember known nodes and a queue
 ///   of pairs of `usize` representing nodes and their parents.
 ///
@@ -47,6 +53,18 @@ use webgraph::traits::RandomAccessGraph;
 /// have been processed. This granularity is very low, but it provides more
 /// realiable results.
 ///
+/// # Deterministic visits
+///
+/// The order in which nodes at the same distance are visited depends on the
+/// scheduling of the threads, and so does, in the case of [`ParFairPred`], the
+/// predecessor reported for each node. Enabling the
+/// [`deterministic`](ParFairBase::deterministic) flag, each frontier is sorted
+/// before being expanded and its nodes are processed in increasing order by
+/// the calling thread, so the sequence of events is the same for every thread
+/// pool (in particular, the predecessor of a node is the smallest node at the
+/// previous distance having it as successor). This mode is meant for
+/// debugging, as only the sorting of the frontiers is parallelized.
+///
 /// # Examples
 ///
 /// Let's compute the distances from 0:
@@ -88,7 +106,9 @@ use webgraph::traits::RandomAccessGraph;
 pub struct ParFairBase<G: RandomAccessGraph, const PRED: bool = false> {
     graph: G,
     granularity: usize,
+    deterministic: bool,
     visited: AtomicBitVec,
+    work: WorkCounters,
 }
 
 /// A fair parallel breadth-first visit that keeps track of its predecessors.
@@ -111,72 +131,436 @@ impl<G: RandomAccessGraph, const P: bool> ParFairBase<G, P> {
         Self {
             graph,
             granularity,
+            deterministic: false,
             visited: AtomicBitVec::new(num_nodes),
+            work: WorkCounters::default(),
+        }
+    }
+
+    /// Sets whether the visit is [deterministic](ParFairBase#deterministic-visits).
+    ///
+    /// # Arguments
+    /// * `deterministic`: if true, frontiers are sorted and processed
+    ///   sequentially, so that callbacks are invoked in the same order
+    ///   independently of the number of threads.
+    pub fn deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    /// Returns the number of nodes per chunk used by the visit.
+    ///
+    /// The number of threads is not a property of the visit, as it is
+    /// determined by the thread pool passed to each visit method.
+    pub fn granularity(&self) -> usize {
+        self.granularity
+    }
+
+    /// Sets whether the visit keeps track of the work done by each thread.
+    ///
+    /// The work can be retrieved with [`thread_work`](Self::thread_work), and
+    /// it is useful to diagnose load imbalance (e.g., on graphs with a skewed
+    /// outdegree distribution) when tuning the granularity.
+    ///
+    /// # Arguments
+    /// * `instrument`: if true, each thread counts the nodes and arcs it
+    ///   processes.
+    pub fn instrument(mut self, instrument: bool) -> Self {
+        self.work.enabled = instrument;
+        self
+    }
+
+    /// Returns the work done by each thread since the visit was created or
+    /// last [reset](Parallel::reset), indexed by the index of the thread in the
+    /// thread pool.
+    ///
+    /// The vector is empty unless the visit is
+    /// [instrumented](Self::instrument). In deterministic visits frontiers
+    /// are processed by the calling thread, and all work is attributed to the
+    /// thread of index zero.
+    pub fn thread_work(&self) -> Vec<ThreadWork> {
+        self.work.thread_work()
+    }
+
+    /// Resets the visit status of the given nodes only, making it possible to
+    /// reuse the visit.
+    ///
+    /// This method is equivalent to a full reset if `nodes` contains all
+    /// the nodes known to the visit, but its cost is proportional to the
+    /// length of `nodes` rather than to the number of nodes of the graph,
+    /// which makes it preferable when performing many small visits.
+    ///
+    /// # Arguments
+    /// * `nodes`: the nodes whose visit status will be reset.
+    pub fn reset_nodes(&mut self, nodes: &[usize]) {
+        for &node in nodes {
+            self.visited.set(node, false, Ordering::Relaxed);
         }
     }
 }
 
-impl<G: RandomAccessGraph + Sync> Parallel<EventNoPred> for ParFairBase<G, false> {
-    fn par_visit_filtered<
+impl<G: RandomAccessGraph + Sync> ParFairNoPred<G> {
+    /// Visits the graph starting from several roots at once.
+    ///
+    /// All roots are placed in the initial frontier at distance zero, so the
+    /// distance reported for each node is the minimum distance from any
+    /// root, and the [`root`](EventNoPred::Unknown::root) field of
+    /// [`EventNoPred::Unknown`] events is the root from which the node has
+    /// been reached first (ties at the same distance are broken arbitrarily).
+    ///
+    /// An [`EventNoPred::Init`] event is generated for each root that is not
+    /// already known and is accepted by the filter before the visit starts,
+    /// and an [`EventNoPred::Done`] event for each such root at the end of the
+    /// visit.
+    ///
+    /// # Arguments
+    /// * `roots`: The nodes to start the visit from.
+    /// * `callback`: The callback function.
+    /// * `filter`: A filter function that will be called on each node to
+    ///   determine whether it should be visited or not.
+    /// * `thread_pool`: The thread pool to use for parallel computation.
+    /// * `pl`: A progress logger.
+    pub fn par_visit_from_nodes<
         E: Send,
         C: Fn(EventNoPred) -> ControlFlow<E, ()> + Sync,
         F: Fn(FilterArgsNoPred) -> bool + Sync,
     >(
         &mut self,
-        root: usize,
+        roots: impl IntoIterator<Item = usize>,
         callback: C,
         filter: F,
         thread_pool: &ThreadPool,
         pl: &mut impl ProgressLog,
     ) -> ControlFlow<E, ()> {
-        if self.visited.get(root, Ordering::Relaxed)
-            || !filter(FilterArgsNoPred {
-                curr: root,
-                root,
-                distance: 0,
-            })
-        {
-            return Continue(());
+        self.work.prepare(thread_pool);
+        Self::par_visit_graph(
+            &self.graph,
+            &self.visited,
+            &self.work,
+            self.granularity,
+            self.deterministic,
+            roots,
+            callback,
+            filter,
+            thread_pool,
+            pl,
+        )
+    }
+
+    /// Returns the distance from `root` to `target`, or [`None`] if `target`
+    /// is not reachable from `root`.
+    ///
+    /// The visit is [reset](Parallel::reset) before starting, and it is
+    /// stopped as soon as `target` is extracted from the frontier: threads
+    /// stop processing their chunks cooperatively, so the visit does work
+    /// proportional to the nodes at distance at most the distance of `target`
+    /// from `root`.
+    ///
+    /// # Arguments
+    /// * `root`: the root of the visit.
+    /// * `target`: the node to look for.
+    /// * `thread_pool`: The thread pool to use for parallel computation.
+    /// * `pl`: A progress logger.
+    pub fn visit_until(
+        &mut self,
+        root: usize,
+        target: usize,
+        thread_pool: &ThreadPool,
+        pl: &mut impl ProgressLog,
+    ) -> Option<usize> {
+        Parallel::<EventNoPred>::reset(self);
+        self.par_visit(
+            root,
+            |event| match event {
+                EventNoPred::Unknown { curr, distance, .. } if curr == target => {
+                    ControlFlow::Break(distance)
+                }
+                _ => Continue(()),
+            },
+            thread_pool,
+            pl,
+        )
+        .break_value()
+    }
+
+    /// Returns the distance from `root` of each node, or -1 if the node is not
+    /// reachable from `root`.
+    ///
+    /// The visit is [reset](Parallel::reset) before starting.
+    ///
+    /// # Arguments
+    /// * `root`: the root of the visit.
+    /// * `thread_pool`: The thread pool to use for parallel computation.
+    /// * `pl`: A progress logger.
+    pub fn distances(
+        &mut self,
+        root: usize,
+        thread_pool: &ThreadPool,
+        pl: &mut impl ProgressLog,
+    ) -> Vec<i64> {
+        let mut distances = vec![-1; self.graph.num_nodes()];
+        let distances_sync = distances.as_sync_slice();
+
+        Parallel::<EventNoPred>::reset(self);
+        self.par_visit(
+            root,
+            |event| {
+                if let EventNoPred::Unknown { curr, distance, .. } = event {
+                    // Safety: each node is discovered exactly once
+                    unsafe { distances_sync[curr].set(distance as i64) };
+                }
+                Continue(())
+            },
+            thread_pool,
+            pl,
+        )
+        .continue_value_no_break();
+
+        distances
+    }
+
+    /// Visits the subgraph induced by a subset of nodes from the specified
+    /// node.
+    ///
+    /// Successors outside the subset are skipped entirely: they are not
+    /// marked as known and they generate no events, so the visit behaves as
+    /// if it were performed on the induced subgraph, which however need not
+    /// be materialized. If `root` is not in the subset, no visit is performed.
+    ///
+    /// Nodes known from previous visits will be ignored, unless
+    /// [`reset`](Parallel::reset) is called first.
+    ///
+    /// # Arguments
+    /// * `root`: The node to start the visit from.
+    /// * `in_subset`: a function returning whether a node is in the subset.
+    /// * `callback`: The callback function.
+    /// * `thread_pool`: The thread pool to use for parallel computation.
+    /// * `pl`: A progress logger.
+    pub fn visit_subset<E: Send, C: Fn(EventNoPred) -> ControlFlow<E, ()> + Sync>(
+        &mut self,
+        root: usize,
+        in_subset: &(impl Fn(usize) -> bool + Sync),
+        callback: C,
+        thread_pool: &ThreadPool,
+        pl: &mut impl ProgressLog,
+    ) -> ControlFlow<E, ()> {
+        self.par_visit_filtered(
+            root,
+            callback,
+            |FilterArgsNoPred { curr, .. }| in_subset(curr),
+            thread_pool,
+            pl,
+        )
+    }
+
+    /// Visits the graph from the specified node and returns the number of
+    /// nodes at each distance from it.
+    ///
+    /// The returned vector has at index `d` the number of nodes at distance
+    /// `d` from `root` that have been visited, so its length minus one is the
+    /// eccentricity of `root` in the visited subgraph. If the visit is
+    /// interrupted, the [`Break`](ControlFlow::Break) value of the callback is
+    /// returned instead.
+    ///
+    /// Nodes known from previous visits will be ignored, unless
+    /// [`reset`](Parallel::reset) is called first; in particular, if `root` is
+    /// already known the returned vector is empty.
+    ///
+    /// # Arguments
+    /// * `root`: The node to start the visit from.
+    /// * `callback`: The callback function.
+    /// * `filter`: A filter function that will be called on each node to
+    ///   determine whether it should be visited or not.
+    /// * `thread_pool`: The thread pool to use for parallel computation.
+    /// * `pl`: A progress logger.
+    pub fn visit_with_level_sizes<
+        E: Send,
+        C: Fn(EventNoPred) -> ControlFlow<E, ()> + Sync,
+        F: Fn(FilterArgsNoPred) -> bool + Sync,
+    >(
+        &mut self,
+        root: usize,
+        callback: C,
+        filter: F,
+        thread_pool: &ThreadPool,
+        pl: &mut impl ProgressLog,
+    ) -> ControlFlow<E, Vec<usize>> {
+        // Levels are visited one at a time, so we just need to count the
+        // nodes of the current level, and to store the count when the first
+        // node of the next level is found.
+        let level_sizes = Mutex::new(Vec::new());
+        let curr_level = AtomicUsize::new(0);
+        let curr_count = AtomicUsize::new(0);
+
+        self.par_visit_filtered(
+            root,
+            |event| {
+                if let EventNoPred::Unknown { distance, .. } = event {
+                    if distance != curr_level.load(Ordering::Acquire) {
+                        let mut level_sizes = level_sizes.lock().unwrap();
+                        if distance != curr_level.load(Ordering::Acquire) {
+                            level_sizes.push(curr_count.swap(0, Ordering::Relaxed));
+                            curr_level.store(distance, Ordering::Release);
+                        }
+                    }
+                    curr_count.fetch_add(1, Ordering::Relaxed);
+                }
+                callback(event)
+            },
+            filter,
+            thread_pool,
+            pl,
+        )?;
+
+        let mut level_sizes = level_sizes.into_inner().unwrap();
+        let last_count = curr_count.into_inner();
+        if last_count > 0 {
+            level_sizes.push(last_count);
         }
+        Continue(level_sizes)
+    }
 
-        // We do not provide a capacity in the hope of allocating dynamically
-        // space as the frontiers grow.
-        let mut curr_frontier = Frontier::with_threads(thread_pool, None);
-        let mut next_frontier = Frontier::with_threads(thread_pool, None);
+    /// Visits the graph from the specified node backwards, that is, following
+    /// arcs in the opposite direction, using the provided transpose.
+    ///
+    /// The visit shares the set of known nodes with forward visits, so nodes
+    /// known from previous forward or backward visits will be ignored, unless
+    /// [`reset`](Parallel::reset) is called first. This makes it possible to
+    /// alternate forward and backward visits without allocating a second
+    /// visit. The distances in the events are distances *to* `root`.
+    ///
+    /// # Arguments
+    /// * `root`: The node to start the visit from.
+    /// * `transpose`: the transpose of the graph of the visit.
+    /// * `callback`: The callback function.
+    /// * `filter`: A filter function that will be called on each node to
+    ///   determine whether it should be visited or not.
+    /// * `thread_pool`: The thread pool to use for parallel computation.
+    /// * `pl`: A progress logger.
+    pub fn visit_backward<
+        E: Send,
+        C: Fn(EventNoPred) -> ControlFlow<E, ()> + Sync,
+        F: Fn(FilterArgsNoPred) -> bool + Sync,
+    >(
+        &mut self,
+        root: usize,
+        transpose: impl RandomAccessGraph + Sync,
+        callback: C,
+        filter: F,
+        thread_pool: &ThreadPool,
+        pl: &mut impl ProgressLog,
+    ) -> ControlFlow<E, ()> {
+        assert_eq!(
+            transpose.num_nodes(),
+            self.graph.num_nodes(),
+            "the transpose should have the same number of nodes of the graph"
+        );
+        self.work.prepare(thread_pool);
+        Self::par_visit_graph(
+            &transpose,
+            &self.visited,
+            &self.work,
+            self.granularity,
+            self.deterministic,
+            [root],
+            callback,
+            filter,
+            thread_pool,
+            pl,
+        )
+    }
 
-        thread_pool.install(|| {
-            curr_frontier.push(root);
-        });
+    /// Visits `graph` from the specified roots using `visited` as set of
+    /// known nodes.
+    ///
+    /// Roots that are already known or rejected by the filter are skipped;
+    /// the initial frontier contains, for each remaining root, the pair made
+    /// of the root and itself, and the frontiers are then expanded either in
+    /// parallel or, if `deterministic` is true, with
+    /// [`par_visit_sorted`](Self::par_visit_sorted).
+    #[allow(clippy::too_many_arguments)]
+    fn par_visit_graph<
+        E: Send,
+        C: Fn(EventNoPred) -> ControlFlow<E, ()> + Sync,
+        F: Fn(FilterArgsNoPred) -> bool + Sync,
+    >(
+        graph: &(impl RandomAccessGraph + Sync),
+        visited: &AtomicBitVec,
+        work: &WorkCounters,
+        granularity: usize,
+        deterministic: bool,
+        roots: impl IntoIterator<Item = usize>,
+        callback: C,
+        filter: F,
+        thread_pool: &ThreadPool,
+        pl: &mut impl ProgressLog,
+    ) -> ControlFlow<E, ()> {
+        let mut started = Vec::new();
+        for root in roots {
+            if visited.get(root, Ordering::Relaxed)
+                || !filter(FilterArgsNoPred {
+                    curr: root,
+                    root,
+                    distance: 0,
+                })
+            {
+                continue;
+            }
+            callback(EventNoPred::Init { root })?;
+            visited.set(root, true, Ordering::Relaxed);
+            started.push(root);
+        }
 
-        callback(EventNoPred::Init { root })?;
-        self.visited.set(root, true, Ordering::Relaxed);
-        let mut distance = 0;
+        if deterministic {
+            let frontier = started.iter().map(|&root| (root, root)).collect();
+            Self::par_visit_sorted(
+                graph,
+                visited,
+                work,
+                frontier,
+                &callback,
+                &filter,
+                thread_pool,
+                pl,
+            )?;
+        } else {
+            // We do not provide a capacity in the hope of allocating
+            // dynamically space as the frontiers grow.
+            let mut curr_frontier = Frontier::with_threads(thread_pool, None);
+            let mut next_frontier = Frontier::with_threads(thread_pool, None);
 
-        while !curr_frontier.is_empty() {
-            let distance_plus_one = distance + 1;
             thread_pool.install(|| {
-                curr_frontier
-                    .par_iter()
-                    .chunks(self.granularity)
-                    .try_for_each(|chunk| {
-                        chunk.into_iter().try_for_each(|&curr| {
-                            callback(EventNoPred::Unknown {
-                                curr,
-                                root,
-                                distance,
-                            })?;
-                            self.graph
-                                .successors(curr)
-                                .into_iter()
-                                .try_for_each(|succ| {
+                for &root in &started {
+                    curr_frontier.push((root, root));
+                }
+            });
+
+            let mut distance = 0;
+
+            while !curr_frontier.is_empty() {
+                let distance_plus_one = distance + 1;
+                thread_pool.install(|| {
+                    curr_frontier
+                        .par_iter()
+                        .chunks(granularity)
+                        .try_for_each(|chunk| {
+                            chunk.into_iter().try_for_each(|&(curr, root)| {
+                                callback(EventNoPred::Unknown {
+                                    curr,
+                                    root,
+                                    distance,
+                                })?;
+                                let mut arcs = 0;
+                                graph.successors(curr).into_iter().try_for_each(|succ| {
+                                    arcs += 1;
                                     let curr = succ;
                                     if filter(FilterArgsNoPred {
                                         curr,
                                         root,
                                         distance: distance_plus_one,
                                     }) {
-                                        if !self.visited.swap(succ, true, Ordering::Relaxed) {
-                                            next_frontier.push(succ);
+                                        if !visited.swap(succ, true, Ordering::Relaxed) {
+                                            next_frontier.push((curr, root));
                                         } else {
                                             callback(EventNoPred::Known { curr, root })?;
                                         }
@@ -184,23 +568,108 @@ impl<G: RandomAccessGraph + Sync> Parallel<EventNoPred> for ParFairBase<G, false
 
                                     Continue(())
                                 })?;
+                                work.record(thread_pool, arcs);
 
-                            Continue(())
+                                Continue(())
+                            })
                         })
-                    })
-            })?;
-            pl.update_with_count(curr_frontier.len());
-            distance += 1;
-            // Swap the frontiers
-            std::mem::swap(&mut curr_frontier, &mut next_frontier);
-            // Clear the frontier we will fill in the next iteration
-            next_frontier.clear();
+                })?;
+                pl.update_with_count(curr_frontier.len());
+                distance += 1;
+                // Swap the frontiers
+                std::mem::swap(&mut curr_frontier, &mut next_frontier);
+                // Clear the frontier we will fill in the next iteration
+                next_frontier.clear();
+            }
         }
 
-        callback(EventNoPred::Done { root })?;
+        for root in started {
+            callback(EventNoPred::Done { root })?;
+        }
+
+        Continue(())
+    }
+
+    /// Visits `graph` from a frontier of pairs made of a node and the root
+    /// from which it has been reached, sorting each frontier and processing
+    /// it sequentially.
+    ///
+    /// [`Init`](EventNoPred::Init) and [`Done`](EventNoPred::Done) events are
+    /// left to [`par_visit_graph`](Self::par_visit_graph).
+    fn par_visit_sorted<E: Send>(
+        graph: &(impl RandomAccessGraph + Sync),
+        visited: &AtomicBitVec,
+        work: &WorkCounters,
+        mut frontier: Vec<(usize, usize)>,
+        callback: impl Fn(EventNoPred) -> ControlFlow<E, ()>,
+        filter: impl Fn(FilterArgsNoPred) -> bool,
+        thread_pool: &ThreadPool,
+        pl: &mut impl ProgressLog,
+    ) -> ControlFlow<E, ()> {
+        let mut distance = 0;
+
+        while !frontier.is_empty() {
+            thread_pool.install(|| frontier.par_sort_unstable());
+            let mut next_frontier = Vec::new();
+            for &(curr, root) in &frontier {
+                callback(EventNoPred::Unknown {
+                    curr,
+                    root,
+                    distance,
+                })?;
+                let mut arcs = 0;
+                for succ in graph.successors(curr) {
+                    arcs += 1;
+                    if filter(FilterArgsNoPred {
+                        curr: succ,
+                        root,
+                        distance: distance + 1,
+                    }) {
+                        if !visited.swap(succ, true, Ordering::Relaxed) {
+                            next_frontier.push((succ, root));
+                        } else {
+                            callback(EventNoPred::Known { curr: succ, root })?;
+                        }
+                    }
+                }
+                work.record(thread_pool, arcs);
+            }
+            pl.update_with_count(frontier.len());
+            distance += 1;
+            frontier = next_frontier;
+        }
 
         Continue(())
     }
+}
+
+impl<G: RandomAccessGraph + Sync> Parallel<EventNoPred> for ParFairBase<G, false> {
+    fn par_visit_filtered<
+        E: Send,
+        C: Fn(EventNoPred) -> ControlFlow<E, ()> + Sync,
+        F: Fn(FilterArgsNoPred) -> bool + Sync,
+    >(
+        &mut self,
+        root: usize,
+        callback: C,
+        filter: F,
+        thread_pool: &ThreadPool,
+        pl: &mut impl ProgressLog,
+    ) -> ControlFlow<E, ()> {
+        self.work.prepare(thread_pool);
+        Self::par_visit_graph(
+            &self.graph,
+            &self.visited,
+            &self.work,
+            self.granularity,
+            self.deterministic,
+            [root],
+            callback,
+            filter,
+            thread_pool,
+            pl,
+        )
+    }
 
     fn par_visit_all_filtered<
         E: Send,
@@ -222,6 +691,105 @@ impl<G: RandomAccessGraph + Sync> Parallel<EventNoPred> for ParFairBase<G, false
 
     fn reset(&mut self) {
         self.visited.fill(false, Ordering::Relaxed);
+        self.work.clear();
+    }
+}
+
+impl<G: RandomAccessGraph + Sync> ParFairPred<G> {
+    /// Returns the parent of each node in a breadth-first visit tree rooted
+    /// at `root`.
+    ///
+    /// The visit is [reset](Parallel::reset) before starting. The parent of
+    /// the root and of the nodes that are not reachable from the root is
+    /// [`None`]. Following parents from a reachable node yields a shortest
+    /// path from the node to the root, in reverse.
+    ///
+    /// # Arguments
+    /// * `root`: the root of the visit.
+    /// * `thread_pool`: The thread pool to use for parallel computation.
+    /// * `pl`: A progress logger.
+    pub fn parents(
+        &mut self,
+        root: usize,
+        thread_pool: &ThreadPool,
+        pl: &mut impl ProgressLog,
+    ) -> Vec<Option<usize>> {
+        let mut parents = vec![None; self.graph.num_nodes()];
+        let parents_sync = parents.as_sync_slice();
+
+        Parallel::<EventPred>::reset(self);
+        self.par_visit(
+            root,
+            |event| {
+                if let EventPred::Unknown { curr, pred, .. } = event {
+                    if curr != root {
+                        // Safety: each node is discovered exactly once
+                        unsafe { parents_sync[curr].set(Some(pred)) };
+                    }
+                }
+                Continue(())
+            },
+            thread_pool,
+            pl,
+        )
+        .continue_value_no_break();
+
+        parents
+    }
+
+    /// Visits the graph from the specified node, sorting each frontier and
+    /// processing it sequentially.
+    fn par_visit_sorted<E: Send>(
+        &mut self,
+        root: usize,
+        callback: impl Fn(EventPred) -> ControlFlow<E, ()>,
+        filter: impl Fn(FilterArgsPred) -> bool,
+        thread_pool: &ThreadPool,
+        pl: &mut impl ProgressLog,
+    ) -> ControlFlow<E, ()> {
+        callback(EventPred::Init { root })?;
+        self.visited.set(root, true, Ordering::Relaxed);
+        let mut frontier = vec![(root, root)];
+        let mut distance = 0;
+
+        while !frontier.is_empty() {
+            thread_pool.install(|| frontier.par_sort_unstable());
+            let mut next_frontier = Vec::new();
+            for &(curr, pred) in &frontier {
+                callback(EventPred::Unknown {
+                    curr,
+                    pred,
+                    root,
+                    distance,
+                })?;
+                let mut arcs = 0;
+                for succ in self.graph.successors(curr) {
+                    arcs += 1;
+                    if filter(FilterArgsPred {
+                        curr: succ,
+                        pred: curr,
+                        root,
+                        distance: distance + 1,
+                    }) {
+                        if !self.visited.swap(succ, true, Ordering::Relaxed) {
+                            next_frontier.push((succ, curr));
+                        } else {
+                            callback(EventPred::Known {
+                                curr: succ,
+                                pred: curr,
+                                root,
+                            })?;
+                        }
+                    }
+                }
+                self.work.record(thread_pool, arcs);
+            }
+            pl.update_with_count(frontier.len());
+            distance += 1;
+            frontier = next_frontier;
+        }
+
+        callback(EventPred::Done { root })
     }
 }
 
@@ -249,6 +817,11 @@ impl<G: RandomAccessGraph + Sync> Parallel<EventPred> for ParFairBase<G, true> {
             return Continue(());
         }
 
+        self.work.prepare(thread_pool);
+        if self.deterministic {
+            return self.par_visit_sorted(root, callback, filter, thread_pool, pl);
+        }
+
         // We do not provide a capacity in the hope of allocating dynamically
         // space as the frontiers grow.
         let mut curr_frontier = Frontier::with_threads(thread_pool, None);
@@ -276,10 +849,12 @@ impl<G: RandomAccessGraph + Sync> Parallel<EventPred> for ParFairBase<G, true> {
                                 root,
                                 distance,
                             })?;
+                            let mut arcs = 0;
                             self.graph
                                 .successors(curr)
                                 .into_iter()
                                 .try_for_each(|succ| {
+                                    arcs += 1;
                                     let (curr, pred) = (succ, curr);
                                     if filter(FilterArgsPred {
                                         curr,
@@ -296,6 +871,7 @@ impl<G: RandomAccessGraph + Sync> Parallel<EventPred> for ParFairBase<G, true> {
 
                                     Continue(())
                                 })?;
+                            self.work.record(thread_pool, arcs);
 
                             Continue(())
                         })
@@ -334,5 +910,6 @@ impl<G: RandomAccessGraph + Sync> Parallel<EventPred> for ParFairBase<G, true> {
 
     fn reset(&mut self) {
         self.visited.fill(false, Ordering::Relaxed);
+        self.work.clear();
     }
 }
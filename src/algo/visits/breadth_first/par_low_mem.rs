@@ -1,5 +1,5 @@
 use crate::algo::visits::{
-    breadth_first::{EventPred, FilterArgsPred},
+    breadth_first::{EventPred, FilterArgsPred, ThreadWork, WorkCounters},
     Parallel,
 };
 use dsi_progress_logger::ProgressLog;
@@ -69,6 +69,7 @@ pub struct ParLowMem<G: RandomAccessGraph> {
     graph: G,
     granularity: usize,
     visited: AtomicBitVec,
+    work: WorkCounters,
 }
 
 impl<G: RandomAccessGraph> ParLowMem<G> {
@@ -85,8 +86,41 @@ impl<G: RandomAccessGraph> ParLowMem<G> {
             graph,
             granularity,
             visited: AtomicBitVec::new(num_nodes),
+            work: WorkCounters::default(),
         }
     }
+
+    /// Returns the number of nodes per chunk used by the visit.
+    ///
+    /// The number of threads is not a property of the visit, as it is
+    /// determined by the thread pool passed to each visit method.
+    pub fn granularity(&self) -> usize {
+        self.granularity
+    }
+
+    /// Sets whether the visit keeps track of the work done by each thread.
+    ///
+    /// The work can be retrieved with [`thread_work`](Self::thread_work), and
+    /// it is useful to diagnose load imbalance (e.g., on graphs with a skewed
+    /// outdegree distribution) when tuning the granularity.
+    ///
+    /// # Arguments
+    /// * `instrument`: if true, each thread counts the nodes and arcs it
+    ///   processes.
+    pub fn instrument(mut self, instrument: bool) -> Self {
+        self.work.enabled = instrument;
+        self
+    }
+
+    /// Returns the work done by each thread since the visit was created or
+    /// last [reset](Parallel::reset), indexed by the index of the thread in the
+    /// thread pool.
+    ///
+    /// The vector is empty unless the visit is
+    /// [instrumented](Self::instrument).
+    pub fn thread_work(&self) -> Vec<ThreadWork> {
+        self.work.thread_work()
+    }
 }
 
 impl<G: RandomAccessGraph + Sync> Parallel<EventPred> for ParLowMem<G> {
@@ -113,6 +147,8 @@ impl<G: RandomAccessGraph + Sync> Parallel<EventPred> for ParLowMem<G> {
             return Continue(());
         }
 
+        self.work.prepare(thread_pool);
+
         // We do not provide a capacity in the hope of allocating dyinamically
         // space as the frontiers grow.
         let mut curr_frontier = Frontier::with_threads(thread_pool, None);
@@ -139,10 +175,12 @@ impl<G: RandomAccessGraph + Sync> Parallel<EventPred> for ParLowMem<G> {
                     .chunks(self.granularity)
                     .try_for_each(|chunk| {
                         chunk.into_iter().try_for_each(|&node| {
+                            let mut arcs = 0;
                             self.graph
                                 .successors(node)
                                 .into_iter()
                                 .try_for_each(|succ| {
+                                    arcs += 1;
                                     let (curr, pred) = (succ, node);
                                     if filter(FilterArgsPred {
                                         curr,
@@ -164,7 +202,10 @@ impl<G: RandomAccessGraph + Sync> Parallel<EventPred> for ParLowMem<G> {
                                     }
 
                                     Continue(())
-                                })
+                                })?;
+                            self.work.record(thread_pool, arcs);
+
+                            Continue(())
                         })
                     })
             })?;
@@ -201,5 +242,6 @@ impl<G: RandomAccessGraph + Sync> Parallel<EventPred> for ParLowMem<G> {
 
     fn reset(&mut self) {
         self.visited.fill(false, Ordering::Relaxed);
+        self.work.clear();
     }
 }
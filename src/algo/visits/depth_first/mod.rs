@@ -12,6 +12,9 @@
 mod seq;
 pub use seq::*;
 
+mod order;
+pub use order::*;
+
 /// Types of callback events generated during depth-first visits
 /// not keeping track of parent nodes.
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -0,0 +1,159 @@
+use sux::bits::BitVec;
+use webgraph::traits::{RandomAccessGraph, RandomAccessLabeling};
+
+type SuccIter<'a, G> = <<G as RandomAccessLabeling>::Labels<'a> as IntoIterator>::IntoIter;
+
+/// A lazy iterator over the nodes of a graph in depth-first preorder.
+///
+/// See [`preorder`].
+pub struct PreOrder<'a, G: RandomAccessGraph> {
+    graph: &'a G,
+    /// The iterators on the successors of the nodes on the visit path.
+    stack: Vec<SuccIter<'a, G>>,
+    known: BitVec,
+    /// The next candidate root.
+    next_root: usize,
+}
+
+/// Returns a lazy iterator over the nodes of a graph in depth-first
+/// preorder.
+///
+/// The visit is performed on the whole graph, starting from nodes in
+/// increasing order, and nodes are returned in the same order in which
+/// [`Previsit`](super::EventPred::Previsit) events are generated by
+/// [`visit_all`](crate::algo::visits::Sequential::visit_all) on a
+/// [sequential depth-first visit](super::SeqPred).
+///
+/// # Arguments
+/// * `graph`: an immutable reference to the graph to visit.
+///
+/// # Examples
+/// ```
+/// use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
+/// use webgraph_algo::algo::visits::depth_first;
+///
+/// let graph = Left(VecGraph::from_arc_list([(0, 2), (2, 1), (0, 3), (4, 3)]));
+///
+/// assert_eq!(
+///     depth_first::preorder(&graph).collect::<Vec<_>>(),
+///     vec![0, 2, 1, 3, 4]
+/// );
+/// ```
+pub fn preorder<G: RandomAccessGraph>(graph: &G) -> PreOrder<'_, G> {
+    PreOrder {
+        graph,
+        stack: Vec::with_capacity(16),
+        known: BitVec::new(graph.num_nodes()),
+        next_root: 0,
+    }
+}
+
+impl<G: RandomAccessGraph> Iterator for PreOrder<'_, G> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let known = &mut self.known;
+        while let Some(iter) = self.stack.last_mut() {
+            if let Some(succ) = iter.find(|&succ| !known[succ]) {
+                known.set(succ, true);
+                self.stack.push(self.graph.successors(succ).into_iter());
+                return Some(succ);
+            }
+            self.stack.pop();
+        }
+
+        while self.next_root < self.graph.num_nodes() {
+            let root = self.next_root;
+            self.next_root += 1;
+            if !known[root] {
+                known.set(root, true);
+                self.stack.push(self.graph.successors(root).into_iter());
+                return Some(root);
+            }
+        }
+
+        None
+    }
+}
+
+/// A lazy iterator over the nodes of a graph in depth-first postorder.
+///
+/// See [`postorder`].
+pub struct PostOrder<'a, G: RandomAccessGraph> {
+    graph: &'a G,
+    /// The nodes on the visit path, with the iterators on their successors.
+    stack: Vec<(usize, SuccIter<'a, G>)>,
+    known: BitVec,
+    /// The next candidate root.
+    next_root: usize,
+}
+
+/// Returns a lazy iterator over the nodes of a graph in depth-first
+/// postorder.
+///
+/// The visit is performed on the whole graph, starting from nodes in
+/// increasing order, and nodes are returned in the same order in which
+/// [`Postvisit`](super::EventPred::Postvisit) events are generated by
+/// [`visit_all`](crate::algo::visits::Sequential::visit_all) on a
+/// [sequential depth-first visit](super::SeqPred). In particular, if the
+/// graph is acyclic, the reverse of the postorder is a topological sort.
+///
+/// # Arguments
+/// * `graph`: an immutable reference to the graph to visit.
+///
+/// # Examples
+/// ```
+/// use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
+/// use webgraph_algo::algo::visits::depth_first;
+///
+/// let graph = Left(VecGraph::from_arc_list([(0, 2), (2, 1), (0, 3), (4, 3)]));
+///
+/// assert_eq!(
+///     depth_first::postorder(&graph).collect::<Vec<_>>(),
+///     vec![1, 2, 3, 0, 4]
+/// );
+/// ```
+pub fn postorder<G: RandomAccessGraph>(graph: &G) -> PostOrder<'_, G> {
+    PostOrder {
+        graph,
+        stack: Vec::with_capacity(16),
+        known: BitVec::new(graph.num_nodes()),
+        next_root: 0,
+    }
+}
+
+impl<G: RandomAccessGraph> Iterator for PostOrder<'_, G> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let known = &mut self.known;
+        loop {
+            if let Some((node, iter)) = self.stack.last_mut() {
+                if let Some(succ) = iter.find(|&succ| !known[succ]) {
+                    known.set(succ, true);
+                    self.stack
+                        .push((succ, self.graph.successors(succ).into_iter()));
+                    continue;
+                }
+                let node = *node;
+                self.stack.pop();
+                return Some(node);
+            }
+
+            // Find the next root
+            loop {
+                if self.next_root == self.graph.num_nodes() {
+                    return None;
+                }
+                let root = self.next_root;
+                self.next_root += 1;
+                if !known[root] {
+                    known.set(root, true);
+                    self.stack
+                        .push((root, self.graph.successors(root).into_iter()));
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Dijkstra visits.
+//!
+//! Dijkstra visits compute shortest distances from a root in graphs whose
+//! labels are nonnegative arc weights. Implementations must accept a callback
+//! function with argument [`EventPred`].
+
+mod seq;
+pub use seq::*;
+
+use std::ops::Add;
+
+/// Types usable as arc weights in Dijkstra visits.
+///
+/// Weights must be comparable and summable, and must have a zero.
+pub trait Weight: Copy + PartialOrd + Add<Output = Self> {
+    /// The zero weight.
+    const ZERO: Self;
+}
+
+macro_rules! impl_weight {
+    ($($ty:ty => $zero:expr),*) => {
+        $(
+            impl Weight for $ty {
+                const ZERO: Self = $zero;
+            }
+        )*
+    };
+}
+
+impl_weight!(
+    f32 => 0.0, f64 => 0.0,
+    u8 => 0, u16 => 0, u32 => 0, u64 => 0, usize => 0,
+    i8 => 0, i16 => 0, i32 => 0, i64 => 0, isize => 0
+);
+
+/// Types of callback events generated during Dijkstra visits.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventPred<W> {
+    /// This event should be used to set up state at the start of the visit.
+    ///
+    /// Note that this event will not happen if the visit is empty, that
+    /// is, if the root has been already visited.
+    Init {
+        /// The root of the current visit, that is, the first node that will
+        /// be visited.
+        root: usize,
+    },
+    /// The shortest distance from the root to the node has been determined:
+    /// we are traversing the last arc of a shortest path, unless all node
+    /// fields are equal to the root.
+    ///
+    /// Nodes are settled in nondecreasing order of distance.
+    Settled {
+        /// The current node.
+        curr: usize,
+        /// The predecessor of [curr](`EventPred::Settled::curr`) on a shortest
+        /// path from the root.
+        pred: usize,
+        /// The root of the current visit.
+        root: usize,
+        /// The shortest distance of the current node from the
+        /// [root](`EventPred::Settled::root`).
+        distance: W,
+    },
+    /// The visit has been completed.
+    ///
+    /// Note that this event will not happen if the visit is empty (that is, if
+    /// the root has already been visited) or if the visit is stopped by a
+    /// callback returning an error.
+    Done {
+        /// The root of the current visit.
+        root: usize,
+    },
+}
+
+impl<W> super::Event for EventPred<W> {
+    type FilterArgs = ();
+}
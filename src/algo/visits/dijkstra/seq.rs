@@ -0,0 +1,190 @@
+use super::{EventPred, Weight};
+use anyhow::{bail, Result};
+use dsi_progress_logger::ProgressLog;
+use std::{
+    cmp::Ordering,
+    collections::BinaryHeap,
+    ops::ControlFlow::{self, Continue},
+};
+use sux::bits::BitVec;
+use webgraph::traits::RandomAccessLabeling;
+
+/// An entry of the priority queue of a Dijkstra visit.
+///
+/// Entries are ordered by decreasing distance, so that the maximum of a
+/// [`BinaryHeap`] is the entry with minimum distance.
+struct HeapEntry<W> {
+    distance: W,
+    node: usize,
+    pred: usize,
+}
+
+impl<W: PartialOrd> PartialEq for HeapEntry<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<W: PartialOrd> Eq for HeapEntry<W> {}
+
+impl<W: PartialOrd> PartialOrd for HeapEntry<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: PartialOrd> Ord for HeapEntry<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Weights are checked to be comparable with zero, so distances are
+        // always comparable
+        other
+            .distance
+            .partial_cmp(&self.distance)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+/// A sequential Dijkstra visit.
+///
+/// The visit works on graphs whose labels are pairs made of a successor and
+/// the weight of the associated arc, as returned by a labeled [`VecGraph`], and
+/// computes the shortest distances from a root using a binary heap. Arcs are
+/// relaxed lazily: a node may appear several times in the heap, and it is
+/// settled the first time it is extracted.
+///
+/// Weights must be nonnegative: if an arc with a negative (or, in the case of
+/// floating-point weights, NaN) weight is met during the visit, the visit
+/// returns an error.
+///
+/// The progress logger will be updated each time a node is settled.
+///
+/// # Examples
+///
+/// ```
+/// use webgraph_algo::algo::visits::dijkstra::{self, EventPred};
+/// use dsi_progress_logger::no_logging;
+/// use webgraph::graphs::vec_graph::VecGraph;
+/// use std::ops::ControlFlow::Continue;
+/// use no_break::NoBreak;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let graph = VecGraph::from_labeled_arc_list([(0, 1, 4.0), (0, 2, 1.0), (2, 1, 2.0)]);
+/// let mut visit = dijkstra::Seq::new(&graph);
+/// let mut d = [f64::INFINITY; 3];
+/// visit.visit(
+///     0,
+///     |event| {
+///         if let EventPred::Settled { curr, distance, .. } = event {
+///             d[curr] = distance;
+///         }
+///         Continue(())
+///     },
+///     no_logging![]
+/// )?.continue_value_no_break();
+///
+/// assert_eq!(d, [0.0, 3.0, 1.0]);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`VecGraph`]: webgraph::graphs::vec_graph::VecGraph
+pub struct Seq<G: RandomAccessLabeling<Label = (usize, L)>, L: Weight> {
+    graph: G,
+    settled: BitVec,
+    heap: BinaryHeap<HeapEntry<L>>,
+}
+
+impl<G: RandomAccessLabeling<Label = (usize, L)>, L: Weight> Seq<G, L> {
+    /// Creates a new sequential Dijkstra visit.
+    ///
+    /// # Arguments
+    /// * `graph`: an immutable reference to the weighted graph to visit.
+    pub fn new(graph: G) -> Self {
+        let num_nodes = graph.num_nodes();
+        Self {
+            graph,
+            settled: BitVec::new(num_nodes),
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    /// Visits the graph from the specified node.
+    ///
+    /// Nodes settled by a previous visit will be ignored, unless
+    /// [`reset`](Self::reset) is called first.
+    ///
+    /// # Arguments
+    /// * `root`: The node to start the visit from.
+    /// * `callback`: The callback function.
+    /// * `pl`: A progress logger.
+    ///
+    /// # Errors
+    /// Returns an error if an arc with a negative weight is found.
+    pub fn visit<E, C: FnMut(EventPred<L>) -> ControlFlow<E, ()>>(
+        &mut self,
+        root: usize,
+        mut callback: C,
+        pl: &mut impl ProgressLog,
+    ) -> Result<ControlFlow<E, ()>> {
+        if self.settled[root] {
+            return Ok(Continue(()));
+        }
+
+        if let ControlFlow::Break(e) = callback(EventPred::Init { root }) {
+            return Ok(ControlFlow::Break(e));
+        }
+
+        self.heap.push(HeapEntry {
+            distance: L::ZERO,
+            node: root,
+            pred: root,
+        });
+
+        while let Some(HeapEntry {
+            distance,
+            node,
+            pred,
+        }) = self.heap.pop()
+        {
+            if self.settled[node] {
+                continue;
+            }
+            self.settled.set(node, true);
+
+            if let ControlFlow::Break(e) = callback(EventPred::Settled {
+                curr: node,
+                pred,
+                root,
+                distance,
+            }) {
+                self.heap.clear();
+                return Ok(ControlFlow::Break(e));
+            }
+
+            for (succ, weight) in self.graph.labels(node) {
+                if matches!(weight.partial_cmp(&L::ZERO), None | Some(Ordering::Less)) {
+                    self.heap.clear();
+                    bail!("The arc from {} to {} has a negative weight", node, succ);
+                }
+                if !self.settled[succ] {
+                    self.heap.push(HeapEntry {
+                        distance: distance + weight,
+                        node: succ,
+                        pred: node,
+                    });
+                }
+            }
+
+            pl.light_update();
+        }
+
+        Ok(callback(EventPred::Done { root }))
+    }
+
+    /// Resets the visit status, making it possible to reuse it.
+    pub fn reset(&mut self) {
+        self.heap.clear();
+        self.settled.fill(false);
+    }
+}
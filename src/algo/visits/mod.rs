@@ -51,6 +51,7 @@
 
 pub mod breadth_first;
 pub mod depth_first;
+pub mod dijkstra;
 
 use dsi_progress_logger::ProgressLog;
 use rayon::ThreadPool;
@@ -12,9 +12,20 @@ pub mod traits {
 /// all traits.
 pub mod prelude {
     use super::*;
+    pub use algo::betweenness;
+    pub use algo::closeness;
+    pub use algo::clustering;
+    pub use algo::components;
+    pub use algo::diameter;
+    pub use algo::eccentricity;
     pub use algo::exact_sum_sweep;
     pub use algo::hyperball;
+    pub use algo::k_core;
+    pub use algo::neighbourhood;
+    pub use algo::pagerank;
     pub use algo::sccs;
+    pub use algo::stats;
+    pub use algo::triangles;
     pub use algo::visits::breadth_first;
     pub use algo::visits::depth_first;
     pub use traits::*;
@@ -75,8 +75,8 @@ fn main() -> Result<()> {
                 bits,
                 result_bits,
             )
-            .sum_of_distances(true)
-            .sum_of_inverse_distances(true)
+            .with_sum_of_distances(true)
+            .with_sum_of_inverse_distances(true)
             .build(&mut main_pl);
             hyperball.run_until_done(&threads![], &mut main_pl)?;
         }
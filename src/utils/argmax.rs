@@ -1,3 +1,6 @@
+use rayon::{prelude::*, ThreadPool};
+use std::cmp::Ordering;
+
 /// Returns the index of the maximum value in a slice, or [`None`] if the slice
 /// is empty.
 ///
@@ -28,6 +31,38 @@ pub fn argmax<T: std::cmp::PartialOrd + Copy>(slice: &[T]) -> Option<usize> {
         .map(|m| m.0)
 }
 
+/// Returns the index of the maximum value in a slice, ignoring incomparable
+/// values such as NaN, or [`None`] if the slice contains no comparable value.
+///
+/// A value is considered incomparable if it is not comparable with itself, as
+/// it happens for floating-point NaN. All other values must be comparable with
+/// each other.
+///
+/// If the maximum appears several times, this methods returns the
+/// position of the first instance.
+///
+/// # Arguments
+/// * `slice`: the slice of elements.
+///
+/// # Examples
+/// ```
+/// # use webgraph_algo::utils::math::argmax_total;
+/// let v = vec![1.0, f64::NAN, 5.0, 2.0, f64::NAN, 5.0];
+/// let index = argmax_total(&v);
+/// assert_eq!(index, Some(2));
+///
+/// assert_eq!(argmax_total(&[f64::NAN, f64::NAN]), None);
+/// ```
+pub fn argmax_total<T: std::cmp::PartialOrd + Copy>(slice: &[T]) -> Option<usize> {
+    slice
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, v)| v.partial_cmp(v).is_some())
+        .max_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+        .map(|m| m.0)
+}
+
 /// Returns the index of the maximum value approved by a filter in a slice, or
 /// [`None`] if no element is approved by the filter.
 ///
@@ -89,3 +124,68 @@ pub fn argmax_filtered<
         })
         .map(|m| m.0)
 }
+
+/// Returns the index of the maximum value approved by a filter in a slice, or
+/// [`None`] if no element is approved by the filter, computed in parallel.
+///
+/// This method has the same semantics of [`argmax_filtered`]: in particular,
+/// the result does not depend on the number of threads.
+///
+/// # Panics
+///
+/// If a comparison returns [`None`].
+///
+/// # Arguments
+/// * `slice`: the slice of elements.
+///
+/// * `tie_break`: in case two elements of `slice` are the same, this slice
+///   is used as secondary order.
+///
+/// * `filter`: a closure that takes as arguments the index of the element and
+///   the element itself and returns true if the element is approved.
+///
+/// * `thread_pool`: The thread pool to use for parallel computation.
+///
+/// ```
+/// # use webgraph_algo::utils::math::par_argmax_filtered;
+/// # use webgraph_algo::threads;
+/// let v = vec![1, 2, 5, 2, 1, 2];
+/// let tie = vec![1, 2, 3, 4, 5, 2];
+/// let index = par_argmax_filtered(&v, &tie, |_, element| element < 4, &threads![]);
+/// // Tie break wins
+/// assert_eq!(index, Some(3));
+/// ```
+pub fn par_argmax_filtered<
+    T: std::cmp::PartialOrd + Copy + Send + Sync,
+    N: std::cmp::PartialOrd + Copy + Send + Sync,
+    F: Fn(usize, T) -> bool + Sync,
+>(
+    slice: &[T],
+    tie_break: &[N],
+    filter: F,
+    thread_pool: &ThreadPool,
+) -> Option<usize> {
+    thread_pool.install(|| {
+        slice
+            .par_iter()
+            .zip(tie_break.par_iter())
+            .enumerate()
+            .filter(|&(index, (&value, _))| filter(index, value))
+            .reduce_with(|a, b| {
+                let (index_a, (value_a, tie_a)) = a;
+                let (index_b, (value_b, tie_b)) = b;
+                // The same order of argmax_filtered, with the smallest index
+                // winning among equal elements
+                match value_a
+                    .partial_cmp(value_b)
+                    .unwrap()
+                    .then(tie_a.partial_cmp(tie_b).unwrap())
+                    .then(index_b.cmp(&index_a))
+                {
+                    Ordering::Less => b,
+                    _ => a,
+                }
+            })
+            .map(|m| m.0)
+    })
+}
@@ -27,6 +27,37 @@ pub fn argmin<T: std::cmp::PartialOrd + Copy>(slice: &[T]) -> Option<usize> {
         .map(|m| m.0)
 }
 
+/// Returns the index of the minimum value in a slice, ignoring incomparable
+/// values such as NaN, or [`None`] if the slice contains no comparable value.
+///
+/// A value is considered incomparable if it is not comparable with itself, as
+/// it happens for floating-point NaN. All other values must be comparable with
+/// each other.
+///
+/// If the minimum appears several times, this methods returns the
+/// position of the first instance.
+///
+/// # Arguments
+/// * `slice`: the slice of elements.
+///
+/// # Examples
+/// ```
+/// # use webgraph_algo::utils::math::argmin_total;
+/// let v = vec![f64::NAN, 3.0, 1.0, f64::NAN, 5.0, 1.0];
+/// let index = argmin_total(&v);
+/// assert_eq!(index, Some(2));
+///
+/// assert_eq!(argmin_total::<f32>(&[]), None);
+/// ```
+pub fn argmin_total<T: std::cmp::PartialOrd + Copy>(slice: &[T]) -> Option<usize> {
+    slice
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| v.partial_cmp(v).is_some())
+        .min_by(|a, b| a.1.partial_cmp(b.1).unwrap())
+        .map(|m| m.0)
+}
+
 /// Returns the index of the minimum value approved by a filter in a slice, or
 /// [`None`] if no element is approved by the filter.
 ///
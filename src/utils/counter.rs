@@ -41,6 +41,8 @@ impl<L: CounterLogic + Clone, BL: Borrow<L>, B: AsMut<L::Backend>> AsMut<L::Back
 
 impl<L: CounterLogic + Clone, BL: Borrow<L>, B: AsRef<L::Backend>> Counter<L>
     for DefaultCounter<L, BL, B>
+where
+    for<'b> Box<L::Backend>: From<&'b L::Backend>,
 {
     type OwnedCounter = DefaultCounter<L, L, Box<L::Backend>>;
 
@@ -52,20 +54,36 @@ impl<L: CounterLogic + Clone, BL: Borrow<L>, B: AsRef<L::Backend>> Counter<L>
     fn count(&self) -> f64 {
         self.logic.borrow().count(self.backend.as_ref())
     }
-    #[inline(always)]
+    /// Converts this counter into an owned counter with a copy of the logic
+    /// and of the backend.
+    ///
+    /// The resulting counter does not borrow anything, so it can outlive the
+    /// structure the counter was obtained from (e.g., a [counter
+    /// array](crate::utils::traits::CounterArray)), and it can be sent to
+    /// another thread if the logic and the backend can.
     fn into_owned(self) -> Self::OwnedCounter {
-        todo!()
+        DefaultCounter::new(
+            self.logic.borrow().clone(),
+            Box::from(self.backend.as_ref()),
+        )
     }
 }
 
 impl<L: CounterLogic + Clone, BL: Borrow<L>, B: AsRef<L::Backend> + AsMut<L::Backend>> CounterMut<L>
     for DefaultCounter<L, BL, B>
+where
+    for<'b> Box<L::Backend>: From<&'b L::Backend>,
 {
     #[inline(always)]
     fn add(&mut self, element: impl Borrow<L::Item>) {
         self.logic.borrow().add(self.backend.as_mut(), element)
     }
 
+    #[inline(always)]
+    fn add_all(&mut self, elements: impl IntoIterator<Item = impl Borrow<L::Item>>) {
+        self.logic.borrow().add_all(self.backend.as_mut(), elements)
+    }
+
     #[inline(always)]
     fn clear(&mut self) {
         self.logic.borrow().clear(self.backend.as_mut())
@@ -82,6 +100,8 @@ impl<
         BL: Borrow<L>,
         B: AsRef<L::Backend> + AsMut<L::Backend>,
     > MergeCounter<L> for DefaultCounter<L, BL, B>
+where
+    for<'b> Box<L::Backend>: From<&'b L::Backend>,
 {
     #[inline(always)]
     fn merge(&mut self, other: &L::Backend) {
@@ -0,0 +1,230 @@
+use crate::utils::{MmapSlice, TempMmapOptions};
+use anyhow::{ensure, Context, Result};
+use dsi_progress_logger::ProgressLog;
+use webgraph::traits::{
+    labels::IteratorImpl, RandomAccessGraph, RandomAccessLabeling, SequentialGraph,
+    SequentialLabeling,
+};
+
+/// A graph stored in compressed sparse row (CSR) format using two
+/// [`MmapSlice`]s.
+///
+/// The successors of all nodes are stored contiguously in a slice of `usize`,
+/// and a second slice of `usize` of length *n* + 1 contains the offset of the
+/// successors of each node. A graph with *n* nodes and *m* arcs thus
+/// requires (*n* + 1 + *m*) · `size_of::<usize>()` bytes.
+///
+/// # Examples
+///
+/// ```
+/// use dsi_progress_logger::no_logging;
+/// use webgraph::{graphs::vec_graph::VecGraph, labels::Left, traits::RandomAccessGraph};
+/// use webgraph_algo::utils::{CsrGraph, TempMmapOptions};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let graph = Left(VecGraph::from_arc_list([(0, 1), (0, 2), (1, 2)]));
+/// let transpose = CsrGraph::transpose(&graph, TempMmapOptions::Default, no_logging![])?;
+///
+/// assert_eq!(transpose.successors(2).collect::<Vec<_>>(), vec![0, 1]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct CsrGraph {
+    /// The offset of the successors of each node, followed by the number of
+    /// arcs.
+    offsets: MmapSlice<usize>,
+    /// The successors of all nodes.
+    successors: MmapSlice<usize>,
+}
+
+impl CsrGraph {
+    /// Builds the transpose of a graph in CSR format.
+    ///
+    /// The graph is scanned twice: once to compute the indegree of each node,
+    /// and once to fill the successor lists of the transpose, which are
+    /// sorted. During the construction an additional temporary slice of *n*
+    /// `usize` is necessary.
+    ///
+    /// # Arguments
+    /// * `graph`: the graph to transpose.
+    /// * `mmap_options`: the options to use for the [`MmapSlice`]s storing
+    ///   the transpose. They cannot be [`TempMmapOptions::PersistentFile`].
+    /// * `pl`: A progress logger.
+    pub fn transpose(
+        graph: impl RandomAccessGraph,
+        mmap_options: TempMmapOptions,
+        pl: &mut impl ProgressLog,
+    ) -> Result<Self> {
+        mmap_options.ensure_multi_slice()?;
+        let num_nodes = graph.num_nodes();
+
+        let mut offsets = MmapSlice::from_value(0, num_nodes + 1, mmap_options.clone())
+            .with_context(|| "Could not create the offsets of the transpose")?;
+
+        pl.item_name("node");
+        pl.expected_updates(Some(num_nodes));
+        pl.start("Computing indegrees...");
+        for node in 0..num_nodes {
+            for succ in graph.successors(node) {
+                offsets[succ + 1] += 1;
+            }
+            pl.light_update();
+        }
+        pl.done();
+
+        for node in 0..num_nodes {
+            offsets[node + 1] += offsets[node];
+        }
+
+        // The number of arcs is computed rather than trusted, as the graph
+        // might report it inaccurately
+        let mut successors = MmapSlice::from_value(0, offsets[num_nodes], mmap_options.clone())
+            .with_context(|| "Could not create the successors of the transpose")?;
+
+        let mut cursors = MmapSlice::from_vec(offsets[..num_nodes].to_vec(), mmap_options)
+            .with_context(|| "Could not create the temporary cursors of the transpose")?;
+
+        pl.expected_updates(Some(num_nodes));
+        pl.start("Filling successor lists...");
+        // Sources are scanned in increasing order, so successor lists are
+        // sorted
+        for node in 0..num_nodes {
+            for succ in graph.successors(node) {
+                successors[cursors[succ]] = node;
+                cursors[succ] += 1;
+            }
+            pl.light_update();
+        }
+        pl.done();
+
+        Ok(Self {
+            offsets,
+            successors,
+        })
+    }
+
+    /// Builds the symmetric closure of a graph in CSR format.
+    ///
+    /// The successors of a node in the result are the union of its
+    /// successors in `graph` and in `transpose`, sorted and without
+    /// duplicates. The graph and its transpose are scanned twice: once to
+    /// compute the outdegree of each node, and once to fill the successor
+    /// lists.
+    ///
+    /// # Arguments
+    /// * `graph`: the graph to symmetrize.
+    /// * `transpose`: the transpose of `graph`.
+    /// * `mmap_options`: the options to use for the [`MmapSlice`]s storing
+    ///   the result. They cannot be [`TempMmapOptions::PersistentFile`].
+    /// * `pl`: A progress logger.
+    pub fn symmetrize(
+        graph: impl RandomAccessGraph,
+        transpose: impl RandomAccessGraph,
+        mmap_options: TempMmapOptions,
+        pl: &mut impl ProgressLog,
+    ) -> Result<Self> {
+        mmap_options.ensure_multi_slice()?;
+        let num_nodes = graph.num_nodes();
+        ensure!(
+            transpose.num_nodes() == num_nodes,
+            "The graph has {} nodes, but its transpose has {} nodes",
+            num_nodes,
+            transpose.num_nodes()
+        );
+
+        // Writes in neighbours the sorted union of the successors and of the
+        // predecessors of a node
+        let neighbours_of = |node: usize, neighbours: &mut Vec<usize>| {
+            neighbours.clear();
+            neighbours.extend(graph.successors(node));
+            neighbours.extend(transpose.successors(node));
+            neighbours.sort_unstable();
+            neighbours.dedup();
+        };
+        let mut neighbours = Vec::new();
+
+        let mut offsets = MmapSlice::from_value(0, num_nodes + 1, mmap_options.clone())
+            .with_context(|| "Could not create the offsets of the symmetric graph")?;
+
+        pl.item_name("node");
+        pl.expected_updates(Some(num_nodes));
+        pl.start("Computing outdegrees...");
+        for node in 0..num_nodes {
+            neighbours_of(node, &mut neighbours);
+            offsets[node + 1] = offsets[node] + neighbours.len();
+            pl.light_update();
+        }
+        pl.done();
+
+        let mut successors = MmapSlice::from_value(0, offsets[num_nodes], mmap_options)
+            .with_context(|| "Could not create the successors of the symmetric graph")?;
+
+        pl.expected_updates(Some(num_nodes));
+        pl.start("Filling successor lists...");
+        for node in 0..num_nodes {
+            neighbours_of(node, &mut neighbours);
+            successors[offsets[node]..offsets[node + 1]].copy_from_slice(&neighbours);
+            pl.light_update();
+        }
+        pl.done();
+
+        Ok(Self {
+            offsets,
+            successors,
+        })
+    }
+}
+
+impl SequentialLabeling for CsrGraph {
+    type Label = usize;
+    type Lender<'node>
+        = IteratorImpl<'node, Self>
+    where
+        Self: 'node;
+
+    #[inline(always)]
+    fn num_nodes(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    #[inline(always)]
+    fn num_arcs_hint(&self) -> Option<u64> {
+        Some(self.num_arcs())
+    }
+
+    #[inline(always)]
+    fn iter_from(&self, from: usize) -> Self::Lender<'_> {
+        IteratorImpl {
+            labeling: self,
+            nodes: from..self.num_nodes(),
+        }
+    }
+}
+
+impl SequentialGraph for CsrGraph {}
+
+impl RandomAccessLabeling for CsrGraph {
+    type Labels<'succ>
+        = std::iter::Copied<std::slice::Iter<'succ, usize>>
+    where
+        Self: 'succ;
+
+    #[inline(always)]
+    fn num_arcs(&self) -> u64 {
+        self.successors.len() as u64
+    }
+
+    #[inline(always)]
+    fn labels(&self, node: usize) -> Self::Labels<'_> {
+        self.successors[self.offsets[node]..self.offsets[node + 1]]
+            .iter()
+            .copied()
+    }
+
+    #[inline(always)]
+    fn outdegree(&self, node: usize) -> usize {
+        self.offsets[node + 1] - self.offsets[node]
+    }
+}
+
+impl RandomAccessGraph for CsrGraph {}
@@ -0,0 +1,193 @@
+use super::{HashResult, HyperLogLog};
+use crate::prelude::*;
+use crate::utils::{MmapSlice, SliceCounterArray};
+use anyhow::{ensure, Context, Result};
+use common_traits::{CastableFrom, UpcastableInto};
+use rayon::{prelude::*, ThreadPool};
+use std::hash::{BuildHasher, Hash};
+use sux::traits::Word;
+
+impl<T, H, W: Word, S: AsRef<[W]>> SliceCounterArray<HyperLogLog<T, H, W>, W, S> {
+    /// Returns whether the counters of this array can be merged with the
+    /// counters of `other`.
+    ///
+    /// Two arrays are compatible if their logics have the same number of
+    /// registers per counter, the same register size, the same [exact
+    /// store](HyperLogLog#exact-counts) size, and are both either sparse or
+    /// dense. The number of counters is not taken into
+    /// consideration, and neither is the hasher, which cannot be checked.
+    ///
+    /// # Arguments
+    /// * `other`: the array to check.
+    pub fn compatible_with(
+        &self,
+        other: &SliceCounterArray<HyperLogLog<T, H, W>, W, impl AsRef<[W]>>,
+    ) -> bool {
+        self.check_compatible(other).is_ok()
+    }
+
+    /// Returns an error describing why the counters of this array cannot be
+    /// merged with the counters of `other`, if they cannot.
+    ///
+    /// See [`compatible_with`](Self::compatible_with).
+    fn check_compatible(
+        &self,
+        other: &SliceCounterArray<HyperLogLog<T, H, W>, W, impl AsRef<[W]>>,
+    ) -> Result<()> {
+        ensure!(
+            self.logic.log_2_num_registers == other.logic.log_2_num_registers,
+            "The arrays should have the same number of registers per counter. Got {} and {}",
+            1_usize << self.logic.log_2_num_registers,
+            1_usize << other.logic.log_2_num_registers
+        );
+        ensure!(
+            self.logic.register_size == other.logic.register_size,
+            "The arrays should have the same register size. Got {} and {}",
+            self.logic.register_size,
+            other.logic.register_size
+        );
+        ensure!(
+            self.logic.sparse == other.logic.sparse,
+            "The arrays should both be either sparse or dense"
+        );
+        ensure!(
+            self.logic.exact_capacity == other.logic.exact_capacity,
+            "The arrays should have the same exact store size. Got {} and {}",
+            self.logic.exact_capacity,
+            other.logic.exact_capacity
+        );
+        Ok(())
+    }
+}
+
+impl<
+        T: Hash,
+        H: BuildHasher + Clone,
+        W: Word + UpcastableInto<HashResult> + CastableFrom<HashResult>,
+        S: AsRef<[W]>,
+    > SliceCounterArray<HyperLogLog<T, H, W>, W, S>
+{
+    /// Returns the estimate of the number of distinct elements added to the
+    /// counter at the specified index, together with a confidence interval.
+    ///
+    /// The result is a triple `(estimate, lower, upper)`, where the interval
+    /// is *e* (1 ± *z* σ), *e* being the estimate and σ the [relative standard
+    /// deviation](HyperLogLog::rel_std) of the counters of the array. The
+    /// lower bound is clamped to zero. For example, if `z` is 2 the interval
+    /// contains the true cardinality with probability approximately 95%.
+    ///
+    /// If the count of the counter is exact, because of [exact
+    /// counts](HyperLogLog#exact-counts) or [sparse
+    /// mode](HyperLogLog#sparse-mode), the interval contains just the estimate.
+    ///
+    /// # Arguments
+    /// * `index`: the index of the counter.
+    /// * `z`: the number of standard deviations of the half-width of the
+    ///   interval.
+    pub fn estimate_with_ci(&self, index: usize, z: f64) -> (f64, f64, f64) {
+        let backend = self.get_backend(index);
+        let estimate = self.logic.count(backend);
+        if self.logic.is_exact(backend) {
+            return (estimate, estimate, estimate);
+        }
+        let delta = estimate * z * HyperLogLog::rel_std(self.logic.log_2_num_registers);
+        (estimate, (estimate - delta).max(0.0), estimate + delta)
+    }
+}
+
+impl<
+        T: Hash + Sync,
+        H: BuildHasher + Clone + Sync,
+        W: Word + UpcastableInto<HashResult> + CastableFrom<HashResult>,
+        S: AsRef<[W]> + AsMut<[W]> + Send,
+    > SliceCounterArray<HyperLogLog<T, H, W>, W, S>
+{
+    /// Merges in parallel each counter of `other` into the counter with the
+    /// same index of this array.
+    ///
+    /// The merge works directly on the backends, processing counters in
+    /// parallel with the same word-wise merge logic used by
+    /// [`merge`](MergeCounterLogic::merge). This is useful to combine arrays
+    /// computed independently, for example on different shards of a graph.
+    ///
+    /// The two arrays must be [compatible](Self::compatible_with) and have the
+    /// same number of counters; otherwise, an error is returned and this array
+    /// is left unchanged. The arrays must also use the same hasher, which
+    /// cannot be checked.
+    ///
+    /// # Arguments
+    /// * `other`: the array to merge into this array.
+    /// * `thread_pool`: The thread pool to use for parallel computation.
+    pub fn merge_from(
+        &mut self,
+        other: &SliceCounterArray<HyperLogLog<T, H, W>, W, impl AsRef<[W]> + Sync>,
+        thread_pool: &ThreadPool,
+    ) -> Result<()> {
+        self.check_compatible(other)?;
+        ensure!(
+            self.len() == other.len(),
+            "The arrays should have the same number of counters. Got {} and {}",
+            self.len(),
+            other.len()
+        );
+
+        let logic = &self.logic;
+        let backend_len = logic.words_per_counter;
+        thread_pool.install(|| {
+            self.backend
+                .as_mut()
+                .par_chunks_mut(backend_len)
+                .zip(other.backend.as_ref().par_chunks(backend_len))
+                .for_each_init(
+                    || logic.new_helper(),
+                    |helper, (dst, src)| logic.merge_with_helper(dst, src, helper),
+                )
+        });
+
+        Ok(())
+    }
+}
+
+impl<
+        T: Hash,
+        H: BuildHasher + Clone,
+        W: Word + UpcastableInto<HashResult> + CastableFrom<HashResult>,
+    > SliceCounterArray<HyperLogLog<T, H, W>, W, MmapSlice<W>>
+{
+    /// Creates a new array of [HyperLogLog counters](HyperLogLog) from the
+    /// concatenation of the register bytes of its counters.
+    ///
+    /// The bytes of each counter must be in the format returned by
+    /// [`HyperLogLog::register_bytes`], and the logic must have the same
+    /// geometry (number of registers and register size) and use the same
+    /// hasher of the logic used to export the counters; the latter cannot be
+    /// checked.
+    ///
+    /// # Arguments
+    /// * `logic`: the counter logic to use.
+    /// * `bytes`: the concatenation of the register bytes of the counters.
+    pub fn from_register_bytes(logic: HyperLogLog<T, H, W>, bytes: &[u8]) -> Result<Self> {
+        let num_registers = 1 << logic.log_2_num_registers;
+        ensure!(
+            bytes.len() % num_registers == 0,
+            "The number of bytes ({}) should be a multiple of the number of registers per counter ({})",
+            bytes.len(),
+            num_registers
+        );
+
+        let mut array = Self::new(logic, bytes.len() / num_registers)?;
+        let logic = &array.logic;
+        for (index, (backend, bytes)) in array
+            .backend
+            .chunks_mut(logic.words_per_counter)
+            .zip(bytes.chunks(num_registers))
+            .enumerate()
+        {
+            logic
+                .set_register_bytes(backend, bytes)
+                .with_context(|| format!("Invalid register bytes for counter {}", index))?;
+        }
+
+        Ok(array)
+    }
+}
@@ -0,0 +1,461 @@
+//! Empirical bias tables for the HyperLogLog++ bias correction.
+//!
+//! For each supported number of registers the table contains pairs of the form
+//! (mean raw estimate, mean bias) for 41 cardinalities evenly spaced between
+//! zero and five times the number of registers. The means were computed by
+//! simulation, feeding uniformly distributed 64-bit hashes to counters with
+//! 5-bit registers; the number of trials for each number of registers is
+//! 2·10⁷ divided by the maximum cardinality (but at least 200).
+
+/// The smallest base-2 logarithm of the number of registers with a bias table.
+const MIN_LOG_2_NUM_REGISTERS: usize = 4;
+
+/// The bias tables, indexed by the base-2 logarithm of the number of registers
+/// minus [`MIN_LOG_2_NUM_REGISTERS`].
+const BIAS_DATA: [&[(f64, f64)]; 9] = [
+    // 16 registers
+    &[
+        (10.77, 10.77),
+        (11.72, 9.72),
+        (12.74, 8.74),
+        (13.82, 7.82),
+        (14.96, 6.96),
+        (16.17, 6.17),
+        (17.44, 5.44),
+        (18.77, 4.77),
+        (20.15, 4.15),
+        (21.61, 3.61),
+        (23.11, 3.11),
+        (24.67, 2.67),
+        (26.28, 2.28),
+        (27.94, 1.94),
+        (29.63, 1.63),
+        (31.37, 1.37),
+        (33.14, 1.14),
+        (34.95, 0.95),
+        (36.77, 0.77),
+        (38.63, 0.63),
+        (40.51, 0.51),
+        (42.42, 0.42),
+        (44.34, 0.34),
+        (46.27, 0.27),
+        (48.22, 0.22),
+        (50.17, 0.17),
+        (52.12, 0.12),
+        (54.09, 0.09),
+        (56.06, 0.06),
+        (58.03, 0.03),
+        (60.02, 0.02),
+        (62.01, 0.01),
+        (64.00, 0.00),
+        (65.99, -0.01),
+        (67.98, -0.02),
+        (69.98, -0.02),
+        (71.94, -0.06),
+        (73.92, -0.08),
+        (75.92, -0.08),
+        (77.92, -0.08),
+        (79.92, -0.08),
+    ],
+    // 32 registers
+    &[
+        (22.30, 22.30),
+        (24.25, 20.25),
+        (26.31, 18.31),
+        (28.50, 16.50),
+        (30.80, 14.80),
+        (33.23, 13.23),
+        (35.77, 11.77),
+        (38.43, 10.43),
+        (41.20, 9.20),
+        (44.08, 8.08),
+        (47.07, 7.07),
+        (50.17, 6.17),
+        (53.35, 5.35),
+        (56.61, 4.61),
+        (59.96, 3.96),
+        (63.39, 3.39),
+        (66.89, 2.89),
+        (70.46, 2.46),
+        (74.09, 2.09),
+        (77.76, 1.76),
+        (81.47, 1.47),
+        (85.23, 1.23),
+        (89.03, 1.03),
+        (92.84, 0.84),
+        (96.70, 0.70),
+        (100.59, 0.59),
+        (104.49, 0.49),
+        (108.42, 0.42),
+        (112.34, 0.34),
+        (116.26, 0.26),
+        (120.20, 0.20),
+        (124.18, 0.18),
+        (128.17, 0.17),
+        (132.16, 0.16),
+        (136.13, 0.13),
+        (140.12, 0.12),
+        (144.14, 0.14),
+        (148.12, 0.12),
+        (152.12, 0.12),
+        (156.09, 0.09),
+        (160.08, 0.08),
+    ],
+    // 64 registers
+    &[
+        (45.38, 45.38),
+        (49.30, 41.30),
+        (53.46, 37.46),
+        (57.85, 33.85),
+        (62.49, 30.49),
+        (67.35, 27.35),
+        (72.45, 24.45),
+        (77.78, 21.78),
+        (83.33, 19.33),
+        (89.09, 17.09),
+        (95.01, 15.01),
+        (101.15, 13.15),
+        (107.47, 11.47),
+        (113.97, 9.97),
+        (120.63, 8.63),
+        (127.44, 7.44),
+        (134.37, 6.37),
+        (141.43, 5.43),
+        (148.62, 4.62),
+        (155.94, 3.94),
+        (163.34, 3.34),
+        (170.79, 2.79),
+        (178.35, 2.35),
+        (185.94, 1.94),
+        (193.63, 1.63),
+        (201.36, 1.36),
+        (209.10, 1.10),
+        (216.91, 0.91),
+        (224.71, 0.71),
+        (232.57, 0.57),
+        (240.47, 0.47),
+        (248.38, 0.38),
+        (256.26, 0.26),
+        (264.18, 0.18),
+        (272.14, 0.14),
+        (280.11, 0.11),
+        (288.12, 0.12),
+        (296.08, 0.08),
+        (304.05, 0.05),
+        (312.01, 0.01),
+        (319.95, -0.05),
+    ],
+    // 128 registers
+    &[
+        (91.55, 91.55),
+        (99.44, 83.44),
+        (107.80, 75.80),
+        (116.62, 68.62),
+        (125.90, 61.90),
+        (135.64, 55.64),
+        (145.84, 49.84),
+        (156.49, 44.49),
+        (167.56, 39.56),
+        (179.05, 35.05),
+        (190.94, 30.94),
+        (203.22, 27.22),
+        (215.86, 23.86),
+        (228.83, 20.83),
+        (242.12, 18.12),
+        (255.69, 15.69),
+        (269.53, 13.53),
+        (283.65, 11.65),
+        (298.00, 10.00),
+        (312.49, 8.49),
+        (327.25, 7.25),
+        (342.19, 6.19),
+        (357.21, 5.21),
+        (372.44, 4.44),
+        (387.78, 3.78),
+        (403.13, 3.13),
+        (418.60, 2.60),
+        (434.11, 2.11),
+        (449.69, 1.69),
+        (465.44, 1.44),
+        (481.08, 1.08),
+        (496.88, 0.88),
+        (512.72, 0.72),
+        (528.47, 0.47),
+        (544.22, 0.22),
+        (560.15, 0.15),
+        (576.08, 0.08),
+        (592.03, 0.03),
+        (608.07, 0.07),
+        (623.98, -0.02),
+        (639.89, -0.11),
+    ],
+    // 256 registers
+    &[
+        (183.88, 183.88),
+        (199.68, 167.68),
+        (216.43, 152.43),
+        (234.09, 138.09),
+        (252.68, 124.68),
+        (272.17, 112.17),
+        (292.53, 100.53),
+        (313.77, 89.77),
+        (335.89, 79.89),
+        (358.82, 70.82),
+        (382.58, 62.58),
+        (407.15, 55.15),
+        (432.39, 48.39),
+        (458.24, 42.24),
+        (484.70, 36.70),
+        (511.75, 31.75),
+        (539.38, 27.38),
+        (567.45, 23.45),
+        (596.05, 20.05),
+        (625.15, 17.15),
+        (654.79, 14.79),
+        (684.57, 12.57),
+        (714.72, 10.72),
+        (745.11, 9.11),
+        (775.69, 7.69),
+        (806.53, 6.53),
+        (837.49, 5.49),
+        (868.81, 4.81),
+        (899.99, 3.99),
+        (931.26, 3.26),
+        (962.77, 2.77),
+        (994.24, 2.24),
+        (1025.84, 1.84),
+        (1057.55, 1.55),
+        (1089.46, 1.46),
+        (1121.29, 1.29),
+        (1153.08, 1.08),
+        (1184.90, 0.90),
+        (1216.70, 0.70),
+        (1248.54, 0.54),
+        (1280.52, 0.52),
+    ],
+    // 512 registers
+    &[
+        (368.53, 368.53),
+        (400.18, 336.18),
+        (433.61, 305.61),
+        (468.94, 276.94),
+        (506.11, 250.11),
+        (545.12, 225.12),
+        (585.90, 201.90),
+        (628.46, 180.46),
+        (672.52, 160.52),
+        (718.53, 142.53),
+        (766.00, 126.00),
+        (814.94, 110.94),
+        (865.59, 97.59),
+        (917.45, 85.45),
+        (970.51, 74.51),
+        (1025.00, 65.00),
+        (1080.31, 56.31),
+        (1136.70, 48.70),
+        (1193.61, 41.61),
+        (1251.60, 35.60),
+        (1310.39, 30.39),
+        (1369.73, 25.73),
+        (1429.89, 21.89),
+        (1490.37, 18.37),
+        (1551.75, 15.75),
+        (1613.52, 13.52),
+        (1675.12, 11.12),
+        (1737.23, 9.23),
+        (1799.54, 7.54),
+        (1862.46, 6.46),
+        (1925.50, 5.50),
+        (1988.56, 4.56),
+        (2051.55, 3.55),
+        (2114.67, 2.67),
+        (2177.96, 1.96),
+        (2241.43, 1.43),
+        (2304.94, 0.94),
+        (2368.49, 0.49),
+        (2432.37, 0.37),
+        (2495.75, -0.25),
+        (2559.90, -0.10),
+    ],
+    // 1024 registers
+    &[
+        (737.83, 737.83),
+        (801.20, 673.20),
+        (868.31, 612.31),
+        (939.05, 555.05),
+        (1013.44, 501.44),
+        (1091.46, 451.46),
+        (1173.07, 405.07),
+        (1258.45, 362.45),
+        (1346.99, 322.99),
+        (1438.66, 286.66),
+        (1533.50, 253.50),
+        (1631.49, 223.49),
+        (1732.10, 196.10),
+        (1835.86, 171.86),
+        (1941.56, 149.56),
+        (2049.73, 129.73),
+        (2160.11, 112.11),
+        (2273.28, 97.28),
+        (2387.53, 83.53),
+        (2503.68, 71.68),
+        (2621.14, 61.14),
+        (2739.64, 51.64),
+        (2859.17, 43.17),
+        (2979.41, 35.41),
+        (3101.73, 29.73),
+        (3224.96, 24.96),
+        (3348.69, 20.69),
+        (3473.50, 17.50),
+        (3598.57, 14.57),
+        (3723.71, 11.71),
+        (3849.98, 9.98),
+        (3976.25, 8.25),
+        (4102.34, 6.34),
+        (4229.59, 5.59),
+        (4355.99, 3.99),
+        (4484.08, 4.08),
+        (4611.53, 3.53),
+        (4739.08, 3.08),
+        (4866.15, 2.15),
+        (4993.56, 1.56),
+        (5121.39, 1.39),
+    ],
+    // 2048 registers
+    &[
+        (1476.44, 1476.44),
+        (1603.16, 1347.16),
+        (1737.26, 1225.26),
+        (1878.91, 1110.91),
+        (2028.02, 1004.02),
+        (2184.29, 904.29),
+        (2347.63, 811.63),
+        (2518.01, 726.01),
+        (2695.05, 647.05),
+        (2878.61, 574.61),
+        (3068.52, 508.52),
+        (3264.86, 448.86),
+        (3466.13, 394.13),
+        (3673.24, 345.24),
+        (3885.77, 301.77),
+        (4102.48, 262.48),
+        (4322.98, 226.98),
+        (4547.23, 195.23),
+        (4775.93, 167.93),
+        (5009.42, 145.42),
+        (5244.88, 124.88),
+        (5481.58, 105.58),
+        (5720.65, 88.65),
+        (5962.34, 74.34),
+        (6206.30, 62.30),
+        (6452.49, 52.49),
+        (6698.86, 42.86),
+        (6946.84, 34.84),
+        (7196.46, 28.46),
+        (7448.90, 24.90),
+        (7701.00, 21.00),
+        (7954.23, 18.23),
+        (8206.91, 14.91),
+        (8458.61, 10.61),
+        (8712.98, 8.98),
+        (8968.54, 8.54),
+        (9222.96, 6.96),
+        (9477.11, 5.11),
+        (9731.91, 3.91),
+        (9987.87, 3.87),
+        (10242.38, 2.38),
+    ],
+    // 4096 registers
+    &[
+        (2953.67, 2953.67),
+        (3207.37, 2695.37),
+        (3475.64, 2451.64),
+        (3758.99, 2222.99),
+        (4056.41, 2008.41),
+        (4368.54, 1808.54),
+        (4694.99, 1622.99),
+        (5035.49, 1451.49),
+        (5389.64, 1293.64),
+        (5756.97, 1148.97),
+        (6136.30, 1016.30),
+        (6527.91, 895.91),
+        (6930.52, 786.52),
+        (7343.74, 687.74),
+        (7767.79, 599.79),
+        (8200.70, 520.70),
+        (8643.31, 451.31),
+        (9092.10, 388.10),
+        (9551.38, 335.38),
+        (10015.96, 287.96),
+        (10486.90, 246.90),
+        (10962.81, 210.81),
+        (11444.33, 180.33),
+        (11928.11, 152.11),
+        (12415.66, 127.66),
+        (12905.57, 105.57),
+        (13399.19, 87.19),
+        (13899.58, 75.58),
+        (14403.93, 67.93),
+        (14905.33, 57.33),
+        (15407.27, 47.27),
+        (15912.28, 40.28),
+        (16420.84, 36.84),
+        (16928.86, 32.86),
+        (17435.12, 27.12),
+        (17942.97, 22.97),
+        (18452.40, 20.40),
+        (18958.06, 14.06),
+        (19463.09, 7.09),
+        (19975.89, 7.89),
+        (20484.05, 4.05),
+    ],
+];
+
+/// Returns the interpolated bias of a raw estimate, or [`None`] if there is no
+/// bias table for the given number of registers.
+///
+/// The bias is interpolated linearly between the two table entries whose raw
+/// estimates surround `raw_estimate`, and clamped at the ends of the table.
+///
+/// # Arguments
+/// * `log_2_num_registers`: the base-2 logarithm of the number of registers.
+/// * `raw_estimate`: the raw HyperLogLog estimate.
+pub(super) fn bias(log_2_num_registers: usize, raw_estimate: f64) -> Option<f64> {
+    let table = BIAS_DATA.get(log_2_num_registers.checked_sub(MIN_LOG_2_NUM_REGISTERS)?)?;
+    let next = table.partition_point(|&(raw, _)| raw < raw_estimate);
+    Some(if next == 0 {
+        table[0].1
+    } else if next == table.len() {
+        table[table.len() - 1].1
+    } else {
+        let (raw_0, bias_0) = table[next - 1];
+        let (raw_1, bias_1) = table[next];
+        bias_0 + (bias_1 - bias_0) * (raw_estimate - raw_0) / (raw_1 - raw_0)
+    })
+}
+
+/// Returns the cardinality below which linear counting is preferred to the
+/// bias-corrected estimate, as suggested by Heule, Nunkesser and Hall in
+/// “HyperLogLog in practice”.
+///
+/// # Arguments
+/// * `log_2_num_registers`: the base-2 logarithm of the number of registers.
+pub(super) fn linear_counting_threshold(log_2_num_registers: usize) -> f64 {
+    match log_2_num_registers {
+        4 => 10.0,
+        5 => 20.0,
+        6 => 40.0,
+        7 => 80.0,
+        8 => 220.0,
+        9 => 400.0,
+        10 => 900.0,
+        11 => 1800.0,
+        12 => 3100.0,
+        13 => 6500.0,
+        14 => 11500.0,
+        15 => 20000.0,
+        16 => 50000.0,
+        17 => 120000.0,
+        _ => 350000.0,
+    }
+}
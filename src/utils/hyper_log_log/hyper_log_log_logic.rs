@@ -1,6 +1,6 @@
 use super::*;
 use crate::{prelude::*, utils::DefaultCounter};
-use anyhow::{ensure, Result};
+use anyhow::{bail, ensure, Context, Result};
 use common_traits::{CastableFrom, CastableInto, Number, UpcastableInto};
 use std::hash::*;
 use std::{borrow::Borrow, f64::consts::LN_2};
@@ -24,18 +24,58 @@ use sux::{
 /// number of registers it might be necessary to select a smaller type,
 /// resulting in slower merges. For example, using 16 5-bit registers one needs
 /// to use `u16`, whereas for 16 6-bit registers `u32` will be sufficient.
+///
+/// # Sparse mode
+///
+/// If [sparse mode](HyperLogLogBuilder::sparse) is enabled, the first word of
+/// each backend is a header, and counters start in a sparse representation
+/// that stores explicitly the (distinct) hashes of the elements added so far in
+/// the space that would be otherwise used by the registers. While a counter is
+/// sparse its count is exact; as soon as the number of distinct hashes exceeds
+/// the number of hashes fitting in the register space the counter is promoted,
+/// once and for all, to the usual dense representation, and its count becomes
+/// the HyperLogLog estimate. Merges work across representations. In sparse
+/// mode `W` must have at most 64 bits.
+///
+/// # Exact counts
+///
+/// If [exact counts](HyperLogLogBuilder::count_exact_below) below *k* are
+/// enabled, each backend ends with an additional exact store: a header word
+/// followed by space for *k* − 1 hashes. The store keeps the (distinct) hashes
+/// of the elements added so far, and the count of a counter is the number of
+/// stored hashes until a *k*-th distinct hash is added; from then on, the
+/// store is marked as full and the count is the usual one. Differently from
+/// sparse mode, registers are always updated, and the space of the store is
+/// added to the space of the registers. Also in this case `W` must have at
+/// most 64 bits.
+///
+/// # Bias correction
+///
+/// If [bias correction](HyperLogLogBuilder::bias_correction) is enabled, the
+/// estimate is computed as suggested by Heule, Nunkesser and Hall in
+/// “HyperLogLog in practice” (HyperLogLog++): linear counting is used below an
+/// empirical threshold, and otherwise, if the raw estimate is at most five
+/// times the number of registers, an empirical bias, interpolated from
+/// precomputed tables, is subtracted from the raw estimate. Tables are
+/// available for 2⁴ to 2¹² registers; for other numbers of registers the
+/// usual estimate is used.
 #[derive(Debug, PartialEq)]
 pub struct HyperLogLog<T, H, W> {
     build_hasher: H,
-    register_size: usize,
+    pub(super) register_size: usize,
     num_registers_minus_1: HashResult,
-    log_2_num_registers: usize,
+    pub(super) log_2_num_registers: usize,
     sentinel_mask: HashResult,
     num_registers: usize,
     pub(super) words_per_counter: usize,
     alpha_m_m: f64,
     msb_mask: Box<[W]>,
     lsb_mask: Box<[W]>,
+    pub(super) sparse: bool,
+    sparse_capacity: usize,
+    exact_offset: usize,
+    pub(super) exact_capacity: usize,
+    pub(super) bias_correction: bool,
     _marker: std::marker::PhantomData<T>,
 }
 
@@ -54,6 +94,11 @@ impl<T, H: Clone, W: Clone> Clone for HyperLogLog<T, H, W> {
             alpha_m_m: self.alpha_m_m,
             msb_mask: self.msb_mask.clone(),
             lsb_mask: self.lsb_mask.clone(),
+            sparse: self.sparse,
+            sparse_capacity: self.sparse_capacity,
+            exact_offset: self.exact_offset,
+            exact_capacity: self.exact_capacity,
+            bias_correction: self.bias_correction,
             _marker: std::marker::PhantomData,
         }
     }
@@ -108,6 +153,298 @@ impl<T, H: Clone, W: Word> HyperLogLog<T, H, W> {
     }
 }
 
+impl<
+        T: Hash,
+        H: BuildHasher + Clone,
+        W: Word + UpcastableInto<HashResult> + CastableFrom<HashResult>,
+    > HyperLogLog<T, H, W>
+{
+    /// The number of words used to store a hash in a sparse counter.
+    const WORDS_PER_HASH: usize = HashResult::BITS as usize / W::BITS;
+
+    /// Returns the register associated with the hash `x` and its candidate
+    /// value.
+    #[inline(always)]
+    fn register_update(&self, x: HashResult) -> (usize, HashResult) {
+        let j = x & self.num_registers_minus_1;
+        let r = (x >> self.log_2_num_registers | self.sentinel_mask).trailing_zeros() as HashResult;
+        let register = j as usize;
+
+        debug_assert!(r < (1 << self.register_size) - 1);
+        debug_assert!(register < self.num_registers);
+
+        (register, r + 1)
+    }
+
+    /// Updates the dense registers `registers` with the hash `x`.
+    fn add_hash_dense(&self, registers: &mut [W], x: HashResult) {
+        let (register, candidate_value) = self.register_update(x);
+        let current_value = self.get_register_unchecked(&*registers, register);
+        let new_value = std::cmp::max(current_value, candidate_value.cast());
+        if current_value != new_value {
+            self.set_register_unchecked(registers, register, new_value);
+        }
+    }
+
+    /// Returns the HyperLogLog estimate for the dense registers `registers`.
+    fn estimate(&self, registers: &[W]) -> f64 {
+        let mut harmonic_mean = 0.0;
+        let mut zeroes = 0;
+
+        for i in 0..self.num_registers {
+            let value: u64 = self.get_register_unchecked(registers, i).upcast();
+            if value == 0 {
+                zeroes += 1;
+            }
+            harmonic_mean += 1.0 / (1 << value) as f64;
+        }
+
+        let mut estimate = self.alpha_m_m / harmonic_mean;
+
+        if self.bias_correction {
+            let num_registers = self.num_registers as f64;
+            if let Some(bias) = bias::bias(self.log_2_num_registers, estimate) {
+                if zeroes != 0 {
+                    let linear_counting = num_registers * (num_registers / zeroes as f64).ln();
+                    if linear_counting <= bias::linear_counting_threshold(self.log_2_num_registers)
+                    {
+                        return linear_counting;
+                    }
+                }
+                if estimate <= 5.0 * num_registers {
+                    estimate -= bias;
+                }
+                return estimate;
+            }
+        }
+
+        if zeroes != 0 && estimate < 2.5 * self.num_registers as f64 {
+            estimate = self.num_registers as f64 * (self.num_registers as f64 / zeroes as f64).ln();
+        }
+        estimate
+    }
+
+    /// Returns the number of hashes of a sparse-mode backend, or [`None`] if
+    /// the backend has been promoted to the dense representation.
+    #[inline(always)]
+    fn sparse_len(&self, backend: &[W]) -> Option<usize> {
+        let header = backend[0];
+        if header == W::MAX {
+            None
+        } else {
+            Some(UpcastableInto::<HashResult>::upcast(header) as usize)
+        }
+    }
+
+    /// Returns the `index`-th hash of a sparse backend.
+    #[inline(always)]
+    fn get_hash(&self, backend: &[W], index: usize) -> HashResult {
+        let start = 1 + index * Self::WORDS_PER_HASH;
+        backend[start..start + Self::WORDS_PER_HASH]
+            .iter()
+            .enumerate()
+            .fold(0, |hash, (i, &word)| {
+                hash | UpcastableInto::<HashResult>::upcast(word) << (i * W::BITS)
+            })
+    }
+
+    /// Sets the `index`-th hash of a sparse backend.
+    #[inline(always)]
+    fn set_hash(&self, backend: &mut [W], index: usize, hash: HashResult) {
+        let start = 1 + index * Self::WORDS_PER_HASH;
+        backend[start..start + Self::WORDS_PER_HASH]
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, word)| *word = CastableFrom::cast_from(hash >> (i * W::BITS)));
+    }
+
+    /// Inserts the hash `x` among the `len` sorted hashes stored after the
+    /// header of `store`, which has room for `capacity` hashes.
+    ///
+    /// Returns false if `x` is not stored and there is no room for it.
+    fn insert_hash(&self, store: &mut [W], len: usize, capacity: usize, x: HashResult) -> bool {
+        // Hashes are kept sorted, so we can use a binary search
+        let (mut low, mut high) = (0, len);
+        while low < high {
+            let mid = (low + high) / 2;
+            match self.get_hash(store, mid).cmp(&x) {
+                std::cmp::Ordering::Less => low = mid + 1,
+                std::cmp::Ordering::Greater => high = mid,
+                std::cmp::Ordering::Equal => return true,
+            }
+        }
+
+        if len == capacity {
+            return false;
+        }
+
+        for i in (low..len).rev() {
+            let hash = self.get_hash(store, i);
+            self.set_hash(store, i + 1, hash);
+        }
+        self.set_hash(store, low, x);
+        store[0] = CastableFrom::cast_from((len + 1) as HashResult);
+        true
+    }
+
+    /// Adds the hash `x` to a sparse-mode backend, promoting it to the dense
+    /// representation if necessary.
+    fn add_hash(&self, backend: &mut [W], x: HashResult) {
+        let Some(len) = self.sparse_len(backend) else {
+            return self.add_hash_dense(&mut backend[1..], x);
+        };
+
+        if !self.insert_hash(backend, len, self.sparse_capacity, x) {
+            self.promote(backend);
+            self.add_hash_dense(&mut backend[1..], x);
+        }
+    }
+
+    /// Adds the hash `x` to the [exact store](HyperLogLog#exact-counts)
+    /// `exact`, marking it as full if there is no room for `x`.
+    fn add_exact_hash(&self, exact: &mut [W], x: HashResult) {
+        if let Some(len) = self.sparse_len(exact) {
+            if !self.insert_hash(exact, len, self.exact_capacity, x) {
+                exact[0] = W::MAX;
+            }
+        }
+    }
+
+    /// Returns whether the count of a backend is exact, that is, whether its
+    /// [exact store](HyperLogLog#exact-counts) is not full or it is in
+    /// [sparse mode](HyperLogLog#sparse-mode).
+    pub(super) fn is_exact(&self, backend: &[W]) -> bool {
+        let (backend, exact) = backend.split_at(self.exact_offset);
+        (self.exact_capacity > 0 && self.sparse_len(exact).is_some())
+            || (self.sparse && self.sparse_len(backend).is_some())
+    }
+
+    /// Returns the registers of a backend as a vector of bytes.
+    ///
+    /// The result contains one byte for each register, in register order,
+    /// holding the value of the register. This layout does not depend on
+    /// the word type or on the endianness of the platform, so it can be used
+    /// to exchange counters with other software. Backends can be rebuilt
+    /// from bytes using [`set_register_bytes`](Self::set_register_bytes).
+    ///
+    /// In [sparse mode](HyperLogLog#sparse-mode), the registers of a sparse
+    /// counter are computed from the stored hashes.
+    ///
+    /// # Arguments
+    /// * `backend`: the backend of a counter.
+    pub fn register_bytes(&self, backend: &[W]) -> Vec<u8> {
+        let backend = &backend[..self.exact_offset];
+        let dense;
+        let registers = if self.sparse {
+            match self.sparse_len(backend) {
+                Some(len) => {
+                    let mut registers = vec![W::ZERO; self.exact_offset - 1];
+                    for i in 0..len {
+                        self.add_hash_dense(&mut registers, self.get_hash(backend, i));
+                    }
+                    dense = registers;
+                    &dense[..]
+                }
+                None => &backend[1..],
+            }
+        } else {
+            backend
+        };
+
+        (0..self.num_registers)
+            .map(|i| {
+                UpcastableInto::<HashResult>::upcast(self.get_register_unchecked(registers, i))
+                    as u8
+            })
+            .collect()
+    }
+
+    /// Returns the histogram of the values of the registers of a backend.
+    ///
+    /// The result has length 2<sup>`register_size`</sup>, and its element of
+    /// index `v` is the number of registers with value `v`. Skewed
+    /// histograms, or many registers with the maximum value, point at a poor
+    /// hash function or at registers that are too small.
+    ///
+    /// Registers are read as in [`register_bytes`](Self::register_bytes), so
+    /// unused bits of the backend are never counted and, in [sparse
+    /// mode](HyperLogLog#sparse-mode), the registers of a sparse counter are
+    /// computed from the stored hashes.
+    ///
+    /// # Arguments
+    /// * `backend`: the backend of a counter.
+    pub fn register_histogram(&self, backend: &[W]) -> Vec<usize> {
+        let mut histogram = vec![0; 1 << self.register_size];
+        for value in self.register_bytes(backend) {
+            histogram[value as usize] += 1;
+        }
+        histogram
+    }
+
+    /// Sets the registers of a backend from bytes in the format returned by
+    /// [`register_bytes`](Self::register_bytes).
+    ///
+    /// In [sparse mode](HyperLogLog#sparse-mode), the backend is set to the
+    /// dense representation, and the [exact store](HyperLogLog#exact-counts),
+    /// if any, is marked as full, so its count will be the HyperLogLog
+    /// estimate.
+    ///
+    /// # Arguments
+    /// * `backend`: the backend of a counter.
+    /// * `bytes`: the values of the registers, one per byte.
+    ///
+    /// # Errors
+    /// Returns an error if the number of bytes is not equal to the number of
+    /// registers, or if a value does not fit in a register.
+    pub fn set_register_bytes(&self, backend: &mut [W], bytes: &[u8]) -> Result<()> {
+        ensure!(
+            bytes.len() == self.num_registers,
+            "Expected {} register values. Got {}",
+            self.num_registers,
+            bytes.len()
+        );
+        let max_value = (1 << self.register_size) - 1;
+        if let Some(position) = bytes.iter().position(|&value| value as usize > max_value) {
+            bail!(
+                "The value {} of register {} does not fit in {} bits",
+                bytes[position],
+                position,
+                self.register_size
+            );
+        }
+
+        let (backend, exact) = backend.split_at_mut(self.exact_offset);
+        if self.exact_capacity > 0 {
+            exact[0] = W::MAX;
+        }
+        let registers = if self.sparse {
+            backend[0] = W::MAX;
+            &mut backend[1..]
+        } else {
+            backend
+        };
+        for (i, &value) in bytes.iter().enumerate() {
+            self.set_register_unchecked(&mut *registers, i, (value as HashResult).cast());
+        }
+
+        Ok(())
+    }
+
+    /// Promotes a sparse backend to the dense representation.
+    fn promote(&self, backend: &mut [W]) {
+        let Some(len) = self.sparse_len(backend) else {
+            return;
+        };
+        let hashes: Vec<HashResult> = (0..len).map(|i| self.get_hash(backend, i)).collect();
+        backend[0] = W::MAX;
+        let registers = &mut backend[1..];
+        registers.fill(W::ZERO);
+        for hash in hashes {
+            self.add_hash_dense(registers, hash);
+        }
+    }
+}
+
 impl<
         T: Hash,
         H: BuildHasher + Clone,
@@ -141,40 +478,83 @@ impl<
         )
     }
 
-    fn add(&self, mut backend: &mut Self::Backend, element: impl Borrow<T>) {
+    fn add(&self, backend: &mut Self::Backend, element: impl Borrow<T>) {
         let x = self.build_hasher.hash_one(element.borrow());
-        let j = x & self.num_registers_minus_1;
-        let r = (x >> self.log_2_num_registers | self.sentinel_mask).trailing_zeros() as HashResult;
-        let register = j as usize;
+        let (backend, exact) = backend.split_at_mut(self.exact_offset);
+        if self.exact_capacity > 0 {
+            self.add_exact_hash(exact, x);
+        }
+        if self.sparse {
+            self.add_hash(backend, x);
+        } else {
+            self.add_hash_dense(backend, x);
+        }
+    }
 
-        debug_assert!(r < (1 << self.register_size) - 1);
-        debug_assert!(register < self.num_registers);
+    /// Adds all elements to the backend, computing first the maximum candidate
+    /// value of each register and then updating each register at most once.
+    ///
+    /// In [sparse mode](HyperLogLog#sparse-mode) elements are added one at a
+    /// time as long as the counter is sparse.
+    fn add_all(&self, backend: &mut [W], elements: impl IntoIterator<Item = impl Borrow<T>>) {
+        let (backend, exact) = backend.split_at_mut(self.exact_offset);
+        let mut exact = (self.exact_capacity > 0).then_some(exact);
+        let mut hashes = elements.into_iter().map(|element| {
+            let x = self.build_hasher.hash_one(element.borrow());
+            if let Some(exact) = &mut exact {
+                self.add_exact_hash(exact, x);
+            }
+            x
+        });
+        let registers = if self.sparse {
+            if self.sparse_len(backend).is_some() {
+                for x in hashes.by_ref() {
+                    self.add_hash(backend, x);
+                    if self.sparse_len(backend).is_none() {
+                        break;
+                    }
+                }
+            }
+            if self.sparse_len(backend).is_some() {
+                return;
+            }
+            &mut backend[1..]
+        } else {
+            backend
+        };
 
-        let current_value = self.get_register_unchecked(&mut backend, register);
-        let candidate_value = r + 1;
-        let new_value = std::cmp::max(current_value, candidate_value.cast());
-        if current_value != new_value {
-            self.set_register_unchecked(backend, register, new_value);
+        // Candidate values are at most 64, so they fit in a byte
+        let mut candidate_values = vec![0_u8; self.num_registers];
+        for x in hashes {
+            let (register, candidate_value) = self.register_update(x);
+            candidate_values[register] = candidate_values[register].max(candidate_value as u8);
+        }
+
+        for (register, &candidate_value) in candidate_values.iter().enumerate() {
+            if candidate_value == 0 {
+                continue;
+            }
+            let candidate_value: W = (candidate_value as HashResult).cast();
+            if self.get_register_unchecked(&*registers, register) < candidate_value {
+                self.set_register_unchecked(&mut *registers, register, candidate_value);
+            }
         }
     }
 
     fn count(&self, backend: &[W]) -> f64 {
-        let mut harmonic_mean = 0.0;
-        let mut zeroes = 0;
-
-        for i in 0..self.num_registers {
-            let value: u64 = self.get_register_unchecked(backend, i).upcast();
-            if value == 0 {
-                zeroes += 1;
+        let (backend, exact) = backend.split_at(self.exact_offset);
+        if self.exact_capacity > 0 {
+            if let Some(len) = self.sparse_len(exact) {
+                return len as f64;
             }
-            harmonic_mean += 1.0 / (1 << value) as f64;
         }
-
-        let mut estimate = self.alpha_m_m / harmonic_mean;
-        if zeroes != 0 && estimate < 2.5 * self.num_registers as f64 {
-            estimate = self.num_registers as f64 * (self.num_registers as f64 / zeroes as f64).ln();
+        if !self.sparse {
+            return self.estimate(backend);
+        }
+        match self.sparse_len(backend) {
+            Some(len) => len as f64,
+            None => self.estimate(&backend[1..]),
         }
-        estimate
     }
 
     fn clear(&self, backend: &mut [W]) {
@@ -203,12 +583,43 @@ impl<
 
     fn new_helper(&self) -> Self::Helper {
         HyperLogLogHelper {
-            acc: vec![W::ZERO; self.words_per_counter],
-            mask: vec![W::ZERO; self.words_per_counter],
+            acc: vec![W::ZERO; self.msb_mask.len()],
+            mask: vec![W::ZERO; self.msb_mask.len()],
         }
     }
 
     fn merge_with_helper(&self, dst: &mut [W], src: &[W], helper: &mut Self::Helper) {
+        let (dst, dst_exact) = dst.split_at_mut(self.exact_offset);
+        let (src, src_exact) = src.split_at(self.exact_offset);
+        if self.exact_capacity > 0 {
+            match self.sparse_len(src_exact) {
+                Some(len) => {
+                    for i in 0..len {
+                        self.add_exact_hash(dst_exact, self.get_hash(src_exact, i));
+                    }
+                }
+                None => dst_exact[0] = W::MAX,
+            }
+        }
+
+        let (dst, src) = if self.sparse {
+            match (self.sparse_len(dst), self.sparse_len(src)) {
+                (_, Some(len)) => {
+                    for i in 0..len {
+                        self.add_hash(dst, self.get_hash(src, i));
+                    }
+                    return;
+                }
+                (Some(_), None) => {
+                    self.promote(dst);
+                    (&mut dst[1..], &src[1..])
+                }
+                (None, None) => (&mut dst[1..], &src[1..]),
+            }
+        } else {
+            (dst, src)
+        };
+
         merge_hyperloglog_bitwise(
             dst,
             src,
@@ -227,17 +638,27 @@ pub struct HyperLogLogBuilder<H, W = usize> {
     build_hasher: H,
     log_2_num_registers: usize,
     n: usize,
+    sparse: bool,
+    count_exact_below: usize,
+    bias_correction: bool,
     _marker: std::marker::PhantomData<(H, W)>,
 }
 
 impl HyperLogLogBuilder<BuildHasherDefault<DefaultHasher>, usize> {
     /// Creates a new builder for a [`HyperLogLog`] logic with a default word
     /// type of `usize`.
+    ///
+    /// # Arguments
+    /// * `n`: an upper bound on the number of distinct elements. It must be
+    ///   positive, or [`build`](HyperLogLogBuilder::build) will fail.
     pub fn new(n: usize) -> Self {
         Self {
             build_hasher: BuildHasherDefault::default(),
             log_2_num_registers: 4,
             n,
+            sparse: false,
+            count_exact_below: 0,
+            bias_correction: false,
             _marker: std::marker::PhantomData,
         }
     }
@@ -331,16 +752,79 @@ impl<H, W: Word> HyperLogLogBuilder<H, W> {
             n: self.n,
             build_hasher: self.build_hasher,
             log_2_num_registers: self.log_2_num_registers,
+            sparse: self.sparse,
+            count_exact_below: self.count_exact_below,
+            bias_correction: self.bias_correction,
             _marker: std::marker::PhantomData,
         }
     }
 
     /// Sets the upper bound on the number of elements.
+    ///
+    /// The bound must be positive, or [`build`](Self::build) will fail.
     pub fn num_elements(mut self, n: usize) -> Self {
         self.n = n;
         self
     }
 
+    /// Sets whether counters should start in [sparse mode](HyperLogLog#sparse-mode).
+    ///
+    /// In sparse mode counters with few elements store explicitly their
+    /// hashes, and are thus exact, until they are promoted to the dense
+    /// representation. The backend of each counter uses an additional
+    /// header word.
+    ///
+    /// # Arguments
+    /// * `sparse`: whether to enable sparse mode.
+    pub fn sparse(mut self, sparse: bool) -> Self {
+        self.sparse = sparse;
+        self
+    }
+
+    /// Makes counts exact while fewer than `k` distinct elements have been
+    /// added to a counter.
+    ///
+    /// Each counter stores the hashes of its first `k - 1` distinct elements
+    /// in an [exact store](HyperLogLog#exact-counts) that is separate from
+    /// the registers, and independent from [sparse
+    /// mode](HyperLogLog#sparse-mode). The backend of each counter uses an
+    /// additional header word and `k - 1` hashes.
+    ///
+    /// Calling this method with `k` at most one restores the default
+    /// behavior.
+    ///
+    /// # Arguments
+    /// * `k`: the number of distinct elements below which counts are exact.
+    pub fn count_exact_below(mut self, k: usize) -> Self {
+        self.count_exact_below = k;
+        self
+    }
+
+    /// Sets whether estimates should use the HyperLogLog++
+    /// [bias correction](HyperLogLog#bias-correction).
+    ///
+    /// # Arguments
+    /// * `bias_correction`: whether to enable bias correction.
+    pub fn bias_correction(mut self, bias_correction: bool) -> Self {
+        self.bias_correction = bias_correction;
+        self
+    }
+
+    /// Uses [xxh3](xxhash_rust::xxh3) as hash function.
+    ///
+    /// The default hasher, [SipHash](DefaultHasher), provides strong
+    /// guarantees but it is slow, and it might dominate the initialization
+    /// time of large counter arrays; xxh3 is much faster and provides the
+    /// same estimation accuracy.
+    ///
+    /// Use [`build_hasher`](Self::build_hasher) with
+    /// [`Xxh3Builder::with_seed`](xxhash_rust::xxh3::Xxh3Builder::with_seed)
+    /// to select a specific seed.
+    #[cfg(feature = "xxhash")]
+    pub fn with_xxhash(self) -> HyperLogLogBuilder<xxhash_rust::xxh3::Xxh3Builder, W> {
+        self.build_hasher(xxhash_rust::xxh3::Xxh3Builder::new())
+    }
+
     /// Sets the [`BuildHasher`] to use.
     ///
     /// Note that using this method you can select a specific
@@ -350,6 +834,9 @@ impl<H, W: Word> HyperLogLogBuilder<H, W> {
             n: self.n,
             log_2_num_registers: self.log_2_num_registers,
             build_hasher,
+            sparse: self.sparse,
+            count_exact_below: self.count_exact_below,
+            bias_correction: self.bias_correction,
             _marker: std::marker::PhantomData,
         }
     }
@@ -361,21 +848,70 @@ impl<H, W: Word> HyperLogLogBuilder<H, W> {
     ///
     /// # Errors
     ///
-    /// Errors will be caused by consistency checks (at least 16 registers per counter,
-    /// backend bits divisible exactly `W::BITS`)
+    /// Errors will be caused by consistency checks (positive upper bound on the
+    /// number of elements, at least 16 registers per counter, backend bits
+    /// divisible exactly `W::BITS`, at most 64 bits per word in sparse mode
+    /// or with [exact counts](Self::count_exact_below), exact store size
+    /// representable by a word).
     pub fn build<T>(self) -> Result<HyperLogLog<T, H, W>> {
-        let log_2_num_registers = self.log_2_num_registers;
-        let num_elements = self.n;
+        ensure!(
+            self.n > 0,
+            "the upper bound on the number of elements should be positive"
+        );
+        let register_size = HyperLogLog::register_size(self.n);
+        HyperLogLog::with_geometry(
+            self.log_2_num_registers,
+            register_size,
+            self.sparse,
+            self.count_exact_below.saturating_sub(1),
+            self.bias_correction,
+            self.build_hasher,
+        )
+    }
+}
 
+impl<T, H, W: Word> HyperLogLog<T, H, W> {
+    /// Creates a logic with the given number of registers per counter and
+    /// register size, computing all derived parameters.
+    ///
+    /// # Arguments
+    /// * `log_2_num_registers`: the logarithm of the number of registers per
+    ///   counter.
+    /// * `register_size`: the size in bits of a register.
+    /// * `sparse`: whether counters use [sparse mode](HyperLogLog#sparse-mode).
+    /// * `exact_capacity`: the number of hashes of the [exact
+    ///   store](HyperLogLog#exact-counts), or zero for no exact store.
+    /// * `bias_correction`: whether estimates use the
+    ///   [bias correction](HyperLogLog#bias-correction).
+    /// * `build_hasher`: the [`BuildHasher`] to use.
+    pub(super) fn with_geometry(
+        log_2_num_registers: usize,
+        register_size: usize,
+        sparse: bool,
+        exact_capacity: usize,
+        bias_correction: bool,
+        build_hasher: H,
+    ) -> Result<Self> {
         // This ensures counters are at least 16-bit-aligned.
         ensure!(
             log_2_num_registers >= 4,
             "the logarithm of the number of registers per counter should be at least 4; got {}",
             log_2_num_registers
         );
+        ensure!(
+            log_2_num_registers < usize::BITS as usize,
+            "the logarithm of the number of registers per counter should be less than {}; got {}",
+            usize::BITS,
+            log_2_num_registers
+        );
+        // The sentinel mask, 1 << (2^register_size - 2), must fit a hash
+        ensure!(
+            (1..=6).contains(&register_size),
+            "the register size should be between 1 and 6; got {}",
+            register_size
+        );
 
         let number_of_registers = 1 << log_2_num_registers;
-        let register_size = HyperLogLog::register_size(num_elements);
         let sentinel_mask = 1 << ((1 << register_size) - 2);
         let alpha = match log_2_num_registers {
             4 => 0.673,
@@ -385,7 +921,9 @@ impl<H, W: Word> HyperLogLogBuilder<H, W> {
         };
         let num_registers_minus_1 = (number_of_registers - 1) as HashResult;
 
-        let counter_size_in_bits = number_of_registers * register_size;
+        let counter_size_in_bits = number_of_registers
+            .checked_mul(register_size)
+            .with_context(|| "the size of a counter should fit a usize")?;
 
         // This ensures counters are always aligned to W
         ensure!(
@@ -395,6 +933,49 @@ impl<H, W: Word> HyperLogLogBuilder<H, W> {
         );
         let counter_size_in_words = counter_size_in_bits / W::BITS;
 
+        // Headers must be able to represent the number of stored hashes and
+        // the marker W::MAX
+        let max_header = if W::BITS >= usize::BITS as usize {
+            usize::MAX - 1
+        } else {
+            (1 << W::BITS) - 2
+        };
+
+        let sparse_capacity = if sparse {
+            ensure!(
+                W::BITS <= HashResult::BITS as usize,
+                "sparse mode requires words of at most {} bits; got {}",
+                HashResult::BITS,
+                W::BITS
+            );
+            std::cmp::min(counter_size_in_bits / HashResult::BITS as usize, max_header)
+        } else {
+            0
+        };
+
+        let exact_offset = counter_size_in_words + sparse as usize;
+        let exact_size_in_words = if exact_capacity > 0 {
+            ensure!(
+                W::BITS <= HashResult::BITS as usize,
+                "exact counts require words of at most {} bits; got {}",
+                HashResult::BITS,
+                W::BITS
+            );
+            ensure!(
+                exact_capacity <= max_header,
+                "exact counts can be kept for at most {} elements with {}-bit words; got {}",
+                max_header,
+                W::BITS,
+                exact_capacity
+            );
+            exact_capacity
+                .checked_mul(HashResult::BITS as usize / W::BITS)
+                .and_then(|words| words.checked_add(1))
+                .with_context(|| "the size of the exact store should fit a usize")?
+        } else {
+            0
+        };
+
         let mut msb = BitFieldVec::new(register_size, number_of_registers);
         let mut lsb = BitFieldVec::new(register_size, number_of_registers);
         let msb_w = W::ONE << (register_size - 1);
@@ -411,10 +992,15 @@ impl<H, W: Word> HyperLogLogBuilder<H, W> {
             register_size,
             alpha_m_m: alpha * (number_of_registers as f64).powi(2),
             sentinel_mask,
-            build_hasher: self.build_hasher,
+            build_hasher,
             msb_mask: msb.as_slice().into(),
             lsb_mask: lsb.as_slice().into(),
-            words_per_counter: counter_size_in_words,
+            words_per_counter: exact_offset + exact_size_in_words,
+            sparse,
+            sparse_capacity,
+            exact_offset,
+            exact_capacity,
+            bias_correction,
             _marker: std::marker::PhantomData,
         })
     }
@@ -424,11 +1010,26 @@ impl<T, H, W> std::fmt::Display for HyperLogLog<T, H, W> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "HyperLogLog with relative standard deviation: {}% ({} registers/counter, {} bits/register, {} bytes/counter)",
+            "HyperLogLog with relative standard deviation: {}% ({} registers/counter, {} bits/register, {} bytes/counter){}{}{}",
             100.0 * HyperLogLog::rel_std(self.log_2_num_registers),
             self.num_registers,
             self.register_size,
-            (self.num_registers * self.register_size) / 8
+            (self.num_registers * self.register_size) / 8,
+            if self.sparse {
+                format!(", sparse up to {} elements", self.sparse_capacity)
+            } else {
+                String::new()
+            },
+            if self.exact_capacity > 0 {
+                format!(", exact up to {} elements", self.exact_capacity)
+            } else {
+                String::new()
+            },
+            if self.bias_correction {
+                ", bias correction"
+            } else {
+                ""
+            }
         )
     }
 }
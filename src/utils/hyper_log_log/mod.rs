@@ -1,5 +1,8 @@
+mod array;
+mod bias;
 mod hyper_log_log_logic;
 pub use hyper_log_log_logic::*;
+mod store;
 
 /// The type returned by the hash function.
 type HashResult = u64;
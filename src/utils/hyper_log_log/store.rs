@@ -0,0 +1,173 @@
+use super::HyperLogLog;
+use crate::utils::{MmapFlags, MmapHelper, SliceCounterArray};
+use anyhow::{ensure, Context, Result};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use sux::traits::Word;
+
+/// The extension of the file containing the backend of a stored array.
+const BACKEND_EXTENSION: &str = "hll";
+/// The extension of the file containing the parameters of a stored array.
+const PROPERTIES_EXTENSION: &str = "properties";
+
+/// The endianness of the target, as written in the properties file.
+const NATIVE_ENDIANNESS: &str = if cfg!(target_endian = "little") {
+    "little"
+} else {
+    "big"
+};
+
+fn with_extension(basename: &Path, extension: &str) -> PathBuf {
+    let mut path = basename.as_os_str().to_owned();
+    path.push(".");
+    path.push(extension);
+    path.into()
+}
+
+/// Parses the value of a property, failing if it is missing.
+fn property<T: FromStr>(properties: &HashMap<&str, &str>, key: &str) -> Result<T>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    properties
+        .get(key)
+        .with_context(|| format!("Missing property {}", key))?
+        .parse()
+        .with_context(|| format!("Invalid value for property {}", key))
+}
+
+impl<T, H, W: Word, S: AsRef<[W]>> SliceCounterArray<HyperLogLog<T, H, W>, W, S> {
+    /// Stores this array of [HyperLogLog counters](HyperLogLog) so that it can be
+    /// memory-mapped later with [`load_mmap`](SliceCounterArray::load_mmap).
+    ///
+    /// Two files are written: `BASENAME.hll`, containing the backend in native
+    /// endianness, and `BASENAME.properties`, containing the parameters of the
+    /// logic, the word size, and the endianness of the backend. The hasher is not stored, so the same hasher must be used when
+    /// loading the array.
+    ///
+    /// # Arguments
+    /// * `basename`: the basename of the files to write.
+    pub fn store(&self, basename: impl AsRef<Path>) -> Result<()> {
+        let basename = basename.as_ref();
+
+        let properties_path = with_extension(basename, PROPERTIES_EXTENSION);
+        let mut properties = std::fs::File::create(&properties_path)
+            .with_context(|| format!("Could not create {}", properties_path.display()))?;
+        writeln!(
+            properties,
+            "log2numregisters={}",
+            self.logic.log_2_num_registers
+        )?;
+        writeln!(properties, "registersize={}", self.logic.register_size)?;
+        writeln!(properties, "wordbits={}", W::BITS)?;
+        writeln!(properties, "endianness={}", NATIVE_ENDIANNESS)?;
+        writeln!(properties, "sparse={}", self.logic.sparse)?;
+        writeln!(properties, "exactcapacity={}", self.logic.exact_capacity)?;
+        writeln!(properties, "biascorrection={}", self.logic.bias_correction)?;
+        let backend = self.backend.as_ref();
+        writeln!(
+            properties,
+            "numcounters={}",
+            backend.len() / self.logic.words_per_counter
+        )?;
+
+        let backend_path = with_extension(basename, BACKEND_EXTENSION);
+        // SAFETY: words are unsigned integers, so they have no padding and
+        // can be viewed as bytes.
+        let bytes = unsafe {
+            std::slice::from_raw_parts(
+                backend.as_ptr() as *const u8,
+                std::mem::size_of_val(backend),
+            )
+        };
+        std::fs::write(&backend_path, bytes)
+            .with_context(|| format!("Could not write {}", backend_path.display()))?;
+
+        Ok(())
+    }
+}
+
+impl<T, H, W: Word> SliceCounterArray<HyperLogLog<T, H, W>, W, MmapHelper<W>> {
+    /// Memory-maps an array of [HyperLogLog counters](HyperLogLog) stored with
+    /// [`store`](SliceCounterArray::store), using the default hasher.
+    ///
+    /// The logic is rebuilt from the stored parameters, so the derived masks are
+    /// computed again rather than read from disk. Loading fails if the array was
+    /// stored with a different word size or endianness.
+    ///
+    /// # Arguments
+    /// * `basename`: the basename of the stored array.
+    /// * `flags`: the flags to use for the memory mapping.
+    pub fn load_mmap(basename: impl AsRef<Path>, flags: MmapFlags) -> Result<Self>
+    where
+        H: Default,
+    {
+        Self::load_mmap_with_hasher(basename, flags, H::default())
+    }
+
+    /// Memory-maps an array of [HyperLogLog counters](HyperLogLog) stored with
+    /// [`store`](SliceCounterArray::store), using the given hasher.
+    ///
+    /// # Arguments
+    /// * `basename`: the basename of the stored array.
+    /// * `flags`: the flags to use for the memory mapping.
+    /// * `build_hasher`: the hasher used by the stored array.
+    pub fn load_mmap_with_hasher(
+        basename: impl AsRef<Path>,
+        flags: MmapFlags,
+        build_hasher: H,
+    ) -> Result<Self> {
+        let basename = basename.as_ref();
+
+        let properties_path = with_extension(basename, PROPERTIES_EXTENSION);
+        let properties = std::fs::read_to_string(&properties_path)
+            .with_context(|| format!("Could not read {}", properties_path.display()))?;
+        let properties: HashMap<&str, &str> = properties
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .collect();
+
+        let word_bits: usize = property(&properties, "wordbits")?;
+        ensure!(
+            word_bits == W::BITS,
+            "The array was stored using {}-bit words, but {}-bit words were requested",
+            word_bits,
+            W::BITS
+        );
+
+        let endianness: String = property(&properties, "endianness")?;
+        ensure!(
+            endianness == NATIVE_ENDIANNESS,
+            "The array was stored in {}-endian order, but the target is {}-endian",
+            endianness,
+            NATIVE_ENDIANNESS
+        );
+
+        let logic = HyperLogLog::with_geometry(
+            property(&properties, "log2numregisters")?,
+            property(&properties, "registersize")?,
+            property(&properties, "sparse")?,
+            property(&properties, "exactcapacity")?,
+            property(&properties, "biascorrection")?,
+            build_hasher,
+        )
+        .with_context(|| "Could not rebuild the HyperLogLog logic")?;
+
+        let backend_path = with_extension(basename, BACKEND_EXTENSION);
+        let backend = MmapHelper::mmap(&backend_path, flags)
+            .with_context(|| format!("Could not mmap {}", backend_path.display()))?;
+
+        let num_counters: usize = property(&properties, "numcounters")?;
+        ensure!(
+            backend.len() == num_counters * logic.words_per_counter,
+            "The backend should contain {} words. Got {}",
+            num_counters * logic.words_per_counter,
+            backend.len()
+        );
+
+        Ok(Self::from_parts(logic, backend))
+    }
+}
@@ -1,13 +1,14 @@
-use anyhow::{ensure, Context, Result};
-use common_traits::UnsignedInt;
+use anyhow::{bail, ensure, Context, Result};
+use common_traits::{Atomic, UnsignedInt};
 use core::fmt::Debug;
 use mmap_rs::{Mmap, MmapMut, MmapOptions};
+use rayon::iter::IntoParallelIterator;
 use std::{
     fs::File,
     mem::size_of,
     ops::{Deref, DerefMut},
     path::*,
-    sync::Arc,
+    sync::{atomic::Ordering, Arc},
 };
 use tempfile::{tempfile, tempfile_in};
 
@@ -39,6 +40,11 @@ pub struct MmapHelper<W, M = Mmap> {
     mmap: M,
     /// The length of the mapping in `W`'s.
     len: usize,
+    /// The mapped file, if the mapping is backed by a file that might need to
+    /// be remapped.
+    file: Option<Arc<File>>,
+    /// The flags used for the mapping.
+    flags: MmapFlags,
     _marker: core::marker::PhantomData<W>,
 }
 
@@ -72,6 +78,8 @@ impl<W> TryFrom<Mmap> for MmapHelper<W> {
         Ok(Self {
             len,
             mmap: value,
+            file: None,
+            flags: MmapFlags::empty(),
             _marker: core::marker::PhantomData,
         })
     }
@@ -133,6 +141,8 @@ impl<W> MmapHelper<W> {
         Ok(Self {
             len: mmap_len / core::mem::size_of::<W>(),
             mmap,
+            file: None,
+            flags,
             _marker: core::marker::PhantomData,
         })
     }
@@ -184,6 +194,8 @@ impl<W> MmapHelper<W, MmapMut> {
         Ok(Self {
             len: mmap.len() / core::mem::size_of::<W>(),
             mmap,
+            file: Some(Arc::new(file)),
+            flags,
             _marker: core::marker::PhantomData,
         })
     }
@@ -221,6 +233,8 @@ impl<W> MmapHelper<W, MmapMut> {
         Ok(Self {
             len: mmap.len() / core::mem::size_of::<W>(),
             mmap,
+            file: Some(Arc::new(file)),
+            flags,
             _marker: core::marker::PhantomData,
         })
     }
@@ -283,6 +297,48 @@ pub enum TempMmapOptions {
     /// Data is stored in a tempfile created with [`tempfile::tempfile_in`] using the provided
     /// path and is memory mapped using the provided [`MmapFlags`].
     CustomDir(PathBuf, MmapFlags),
+    /// Data is stored in the file at the provided path, which is created or truncated, and is
+    /// memory mapped using the provided [`MmapFlags`].
+    ///
+    /// The file is not deleted when the slice is dropped, so it can be mapped again later, for
+    /// example with [`MmapHelper::mmap`]. Modifications reach the file only if the flags include
+    /// [`MmapFlags::SHARED`]: with a private mapping the file will just contain the initial
+    /// content of the slice. Modifications are written back to disk by the operating system
+    /// at some point after being performed, at the latest when the slice is dropped; there is
+    /// no guarantee that they will survive a system crash.
+    ///
+    /// Since each slice needs its own file, these options are rejected with
+    /// an error by the methods creating more than one slice from the same
+    /// options, such as [`CsrGraph::transpose`](crate::utils::CsrGraph::transpose).
+    PersistentFile(PathBuf, MmapFlags),
+}
+
+impl TempMmapOptions {
+    /// Returns an error if these options cannot be used to create several
+    /// slices, that is, if they are [`TempMmapOptions::PersistentFile`]:
+    /// all the slices would be backed by the same file, each one truncating
+    /// the others.
+    pub(crate) fn ensure_multi_slice(&self) -> Result<()> {
+        if let TempMmapOptions::PersistentFile(path, _) = self {
+            bail!(
+                "Cannot back several slices with the same persistent file {}",
+                path.display()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Creates, or truncates if it exists, the file backing a
+/// [`TempMmapOptions::PersistentFile`] slice.
+fn persistent_file(path: &Path) -> Result<File> {
+    std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)
+        .with_context(|| format!("Cannot create {}", path.display()))
 }
 
 /// A utility struct to reduce RAM consumption by allowing storing data in persistent memory and
@@ -325,6 +381,22 @@ pub enum TempMmapOptions {
 /// # }
 ///
 /// ```
+///
+/// Slices can be iterated upon in parallel directly, as references to a slice
+/// implement [`IntoParallelIterator`]:
+///
+/// ```
+/// use webgraph_algo::utils::*;
+/// use rayon::prelude::*;
+/// # use anyhow::Result;
+///
+/// # fn main() -> Result<()> {
+/// let mut mmap_slice: MmapSlice<usize> = MmapSlice::from_default(100, TempMmapOptions::Default)?;
+/// mmap_slice.par_iter_mut().enumerate().for_each(|(i, x)| *x = i);
+/// assert_eq!(mmap_slice.par_iter().sum::<usize>(), 4950);
+/// # Ok(())
+/// # }
+/// ```
 pub type MmapSlice<T> = MmapHelper<T, MmapMut>;
 
 impl<T: Default> MmapSlice<T> {
@@ -411,10 +483,63 @@ impl<T: Clone> MmapSlice<T> {
                     dir.display()
                 )
             })?,
+            TempMmapOptions::PersistentFile(path, flags) => {
+                Self::from_file_and_len(Some(persistent_file(&path)?), flags, len).with_context(
+                    || format!("Cannot create mmap of len {} in {}", len, path.display()),
+                )?
+            }
         };
         mmap_slice.fill(value);
         Ok(mmap_slice)
     }
+
+    /// Resizes the slice so that its length is `new_len`.
+    ///
+    /// If `new_len` is greater than the current length, the slice is extended
+    /// with copies of `value`; otherwise, it is truncated.
+    ///
+    /// The slice is remapped: if it is backed by a file, the length of the
+    /// file is changed accordingly. In any case, the content of the slice is
+    /// preserved, even if the mapping is private.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use webgraph_algo::utils::*;
+    ///
+    /// # use anyhow::Result;
+    /// # fn main() -> Result<()> {
+    /// let mut slice = MmapSlice::from_vec(vec![0, 1, 2], TempMmapOptions::Default)?;
+    /// slice.resize(5, 42)?;
+    /// assert_eq!(slice.as_slice(), &[0, 1, 2, 42, 42]);
+    /// slice.resize(2, 42)?;
+    /// assert_eq!(slice.as_slice(), &[0, 1]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn resize(&mut self, new_len: usize, value: T) -> Result<()> {
+        let old_len = self.len;
+        let mut mmap = Self::map(self.file.as_deref(), self.flags, new_len)
+            .with_context(|| format!("Cannot remap slice to len {}", new_len))?;
+
+        unsafe {
+            // SAFETY: both regions are aligned, src is valid for old_len reads
+            // and dst is valid for new_len writes; the regions might map the
+            // same file, so we do not assume they are non-overlapping.
+            std::ptr::copy(
+                self.mmap.as_ptr() as *const T,
+                mmap.as_mut_ptr() as *mut T,
+                old_len.min(new_len),
+            );
+        }
+
+        self.mmap = mmap;
+        self.len = new_len;
+        if new_len > old_len {
+            self[old_len..].fill(value);
+        }
+        Ok(())
+    }
 }
 
 impl<T> MmapSlice<T> {
@@ -422,9 +547,23 @@ impl<T> MmapSlice<T> {
     const BLOCK_SIZE: usize = size_of::<T>();
 
     fn from_file_and_len(file: Option<File>, flags: MmapFlags, len: usize) -> Result<Self> {
+        let mmap = Self::map(file.as_ref(), flags, len)?;
+
+        Ok(Self {
+            mmap,
+            len,
+            file: file.map(Arc::new),
+            flags,
+            _marker: std::marker::PhantomData,
+        })
+    }
+
+    /// Creates a mapping able to contain `len` elements, backed by `file` if
+    /// present, setting the length of the file accordingly.
+    fn map(file: Option<&File>, flags: MmapFlags, len: usize) -> Result<MmapMut> {
         let mmap_bytes = std::cmp::max(1, len * Self::BLOCK_SIZE);
 
-        if let Some(f) = file.as_ref() {
+        if let Some(f) = file {
             f.set_len(mmap_bytes as u64)
                 .with_context(|| format!("Cannot set file len to {} bytes", mmap_bytes))?;
         }
@@ -433,7 +572,7 @@ impl<T> MmapSlice<T> {
             .with_context(|| format!("Cannot initialize mmap of size {}", mmap_bytes))?
             .with_flags(flags);
 
-        let mmap = if let Some(f) = file.as_ref() {
+        let mmap = if let Some(f) = file {
             unsafe { mmap_builder.with_file(f, 0) }
         } else {
             mmap_builder
@@ -448,11 +587,7 @@ impl<T> MmapSlice<T> {
             std::mem::size_of::<T>()
         );
 
-        Ok(Self {
-            mmap,
-            len,
-            _marker: std::marker::PhantomData,
-        })
+        Ok(mmap)
     }
 
     /// Creates a new slice from a [`Vec`] with the provided [`TempMmapOptions`].
@@ -510,6 +645,12 @@ impl<T> MmapSlice<T> {
                 v,
             )
             .with_context(|| format!("Cannot create mmap in directory {}", dir.display()))?),
+            TempMmapOptions::PersistentFile(path, flags) => {
+                Ok(
+                    Self::from_file_and_vec(Some(persistent_file(&path)?), flags, v)
+                        .with_context(|| format!("Cannot create mmap in {}", path.display()))?,
+                )
+            }
         }
     }
 
@@ -564,6 +705,11 @@ impl<T> MmapSlice<T> {
                     dir.display()
                 )
             })?,
+            TempMmapOptions::PersistentFile(path, flags) => {
+                Self::from_file_and_len(Some(persistent_file(&path)?), flags, len).with_context(
+                    || format!("Cannot create mmap of len {} in {}", len, path.display()),
+                )?
+            }
         };
         mmap_slice.fill_with(closure);
         Ok(mmap_slice)
@@ -587,6 +733,43 @@ impl<T> MmapSlice<T> {
         Ok(mmap)
     }
 
+    /// Returns a reference to the element at position `index`, or an error
+    /// describing the slice if `index` is out of bounds.
+    ///
+    /// Non-panicking access returning an [`Option`] is available through the
+    /// [`get`](slice::get) and [`get_mut`](slice::get_mut) methods of the
+    /// underlying slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use webgraph_algo::utils::*;
+    ///
+    /// # use anyhow::Result;
+    /// # fn main() -> Result<()> {
+    /// let slice = MmapSlice::from_vec(vec![0, 1, 2], TempMmapOptions::Default)?;
+    /// assert_eq!(*slice.try_index(1)?, 1);
+    /// assert!(slice.try_index(3).is_err());
+    /// assert_eq!(slice.get(3), None);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn try_index(&self, index: usize) -> Result<&T> {
+        ensure!(
+            index < self.len,
+            "Index {} out of bounds for slice of {} of len {} ({})",
+            index,
+            std::any::type_name::<T>(),
+            self.len,
+            if self.file.is_some() {
+                "backed by a file"
+            } else {
+                "in memory"
+            }
+        );
+        Ok(&self[index])
+    }
+
     /// Extracts a slice containing the entire data.
     ///
     /// Equivalent to `&s[..]`
@@ -604,6 +787,40 @@ impl<T> MmapSlice<T> {
     }
 }
 
+impl<T: Atomic> MmapSlice<T> {
+    /// Stores `value` into every element of a slice of atomic elements.
+    ///
+    /// Differently from [`fill`](slice::fill), this method needs just a shared
+    /// reference, so the slice can be reset while it is shared among threads.
+    ///
+    /// # Arguments
+    /// * `value`: the value to store.
+    /// * `ordering`: the memory ordering of each store.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use webgraph_algo::utils::*;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// # use anyhow::Result;
+    /// # fn main() -> Result<()> {
+    /// let slice = MmapSlice::<AtomicUsize>::from_default(100, TempMmapOptions::Default)?;
+    /// slice.fill_atomic(42, Ordering::Relaxed);
+    /// assert!(slice.iter().all(|x| x.load(Ordering::Relaxed) == 42));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fill_atomic(&self, value: T::NonAtomicType, ordering: Ordering)
+    where
+        T::NonAtomicType: Copy,
+    {
+        for element in self.iter() {
+            element.store(value, ordering);
+        }
+    }
+}
+
 impl<T> Deref for MmapSlice<T> {
     type Target = [T];
 
@@ -619,3 +836,23 @@ impl<T> DerefMut for MmapSlice<T> {
         self.as_mut()
     }
 }
+
+impl<'data, T: Sync + 'data> IntoParallelIterator for &'data MmapSlice<T> {
+    type Iter = rayon::slice::Iter<'data, T>;
+    type Item = &'data T;
+
+    #[inline(always)]
+    fn into_par_iter(self) -> Self::Iter {
+        self.as_slice().into_par_iter()
+    }
+}
+
+impl<'data, T: Send + 'data> IntoParallelIterator for &'data mut MmapSlice<T> {
+    type Iter = rayon::slice::IterMut<'data, T>;
+    type Item = &'data mut T;
+
+    #[inline(always)]
+    fn into_par_iter(self) -> Self::Iter {
+        self.as_mut_slice().into_par_iter()
+    }
+}
@@ -7,11 +7,13 @@ use webgraph::traits::RandomAccessGraph;
 
 mod argmax;
 mod argmin;
+mod top_k;
 
 /// Module containing mathematical utilities.
 pub mod math {
     pub use super::argmax::*;
     pub use super::argmin::*;
+    pub use super::top_k::*;
 }
 
 mod mmap_helper;
@@ -26,8 +28,12 @@ pub use hyper_log_log::{HyperLogLog, HyperLogLogBuilder, HyperLogLogHelper};
 
 mod counter;
 pub use counter::DefaultCounter;
+mod csr_graph;
+pub use csr_graph::CsrGraph;
 mod slice_counter_array;
 pub use slice_counter_array::SliceCounterArray;
+pub mod symmetrize;
+pub mod transpose;
 
 /// Module containing utility traits.
 pub mod traits;
@@ -81,6 +87,60 @@ pub(crate) fn check_transposed<G1: RandomAccessGraph + Sync, G2: RandomAccessGra
     if graph.num_nodes() != transposed.num_nodes() || graph.num_arcs() != transposed.num_arcs() {
         return false;
     }
+    check_transposed_arcs(graph, transposed, false)
+}
+
+/// Returns whether `transposed` is the transposed graph of `graph` once
+/// self-loops are removed from both graphs.
+///
+/// # Arguments
+/// * `graph`: the direct graph.
+/// * `transposed`: the graph to check whether is the transposed of `graph`
+///   up to self-loops.
+pub(crate) fn check_transposed_up_to_loops<
+    G1: RandomAccessGraph + Sync,
+    G2: RandomAccessGraph + Sync,
+>(
+    graph: G1,
+    transposed: G2,
+) -> bool {
+    if graph.num_nodes() != transposed.num_nodes()
+        || graph.num_arcs() - num_self_loops(&graph) as u64
+            != transposed.num_arcs() - num_self_loops(&transposed) as u64
+    {
+        return false;
+    }
+    check_transposed_arcs(graph, transposed, true)
+}
+
+/// Returns the number of self-loops of the graph.
+///
+/// # Arguments
+/// * `graph`: the graph.
+pub(crate) fn num_self_loops<G: RandomAccessGraph + Sync>(graph: G) -> usize {
+    (0..graph.num_nodes())
+        .into_par_iter()
+        .map(|node| {
+            graph
+                .successors(node)
+                .into_iter()
+                .filter(|&succ| succ == node)
+                .count()
+        })
+        .sum()
+}
+
+/// Checks arc by arc whether `transposed` is the transposed graph of `graph`.
+///
+/// # Arguments
+/// * `graph`: the direct graph.
+/// * `transposed`: the graph to check whether is the transposed of `graph`.
+/// * `ignore_loops`: whether self-loops should be skipped.
+fn check_transposed_arcs<G1: RandomAccessGraph + Sync, G2: RandomAccessGraph + Sync>(
+    graph: G1,
+    transposed: G2,
+    ignore_loops: bool,
+) -> bool {
     let max_nodes = std::env::var(MAX_NODES_ENV_VAR)
         .map(|v| v.parse().unwrap_or(MAX_NODES_DEFAULT))
         .unwrap_or(MAX_NODES_DEFAULT);
@@ -92,6 +152,9 @@ pub(crate) fn check_transposed<G1: RandomAccessGraph + Sync, G2: RandomAccessGra
         .into_par_iter()
         .try_for_each(|node| {
             for succ in graph.successors(node) {
+                if ignore_loops && succ == node {
+                    continue;
+                }
                 if !transposed
                     .successors(succ)
                     .into_iter()
@@ -101,6 +164,9 @@ pub(crate) fn check_transposed<G1: RandomAccessGraph + Sync, G2: RandomAccessGra
                 }
             }
             for succ in transposed.successors(node) {
+                if ignore_loops && succ == node {
+                    continue;
+                }
                 if !graph.successors(succ).into_iter().any(|pred| pred == node) {
                     return Err(());
                 }
@@ -1,6 +1,6 @@
 use crate::utils::DefaultCounter;
 use crate::{prelude::*, utils::MmapSlice};
-use anyhow::{Context, Result};
+use anyhow::{ensure, Context, Result};
 use sux::traits::Word;
 use sync_cell_slice::{SyncCell, SyncSlice};
 
@@ -60,11 +60,32 @@ impl<L: SliceCounterLogic<W> + Sync, W: Word, S: AsRef<[SyncCell<W>]> + Sync> Sy
         self.backend.as_ref().iter().for_each(|c| c.set(W::ZERO))
     }
 
+    unsafe fn clear_counter(&self, index: usize) {
+        // Backends are made of whole words, so no other counter is affected
+        let offset = index * self.logic.backend_len();
+        self.backend.as_ref()[offset..][..self.logic.backend_len()]
+            .iter()
+            .for_each(|c| c.set(W::ZERO))
+    }
+
     fn len(&self) -> usize {
         self.backend.as_ref().len() / self.logic.backend_len()
     }
 }
 
+impl<L, W, S> SliceCounterArray<L, W, S> {
+    /// Creates a counter array from a logic and a backend whose length has
+    /// already been checked to be a multiple of the backend length of the
+    /// logic.
+    pub(super) fn from_parts(logic: L, backend: S) -> Self {
+        Self {
+            logic,
+            backend,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
 impl<L: SliceCounterLogic<W>, W, S: AsRef<[W]>> SliceCounterArray<L, W, S> {
     /// Returns the number of counters in the array.
     #[inline(always)]
@@ -147,6 +168,31 @@ impl<L: SliceCounterLogic<W>, W: Word> SliceCounterArray<L, W, MmapSlice<W>> {
     }
 }
 
+impl<L: SliceCounterLogic<W>, W: Word, S: AsMut<[W]>> SliceCounterArray<L, W, S> {
+    /// Creates a new counter slice with the provided logic over a backend
+    /// provided by the caller (e.g., a pre-allocated [`MmapSlice`]).
+    ///
+    /// The backend is cleared, so all counters of the resulting array are
+    /// empty.
+    ///
+    /// # Arguments
+    /// * `logic`: the counter logic to use.
+    /// * `len`: the number of the counters in the array.
+    /// * `backend`: the backend, which must contain exactly `len` times the
+    ///   [backend length](SliceCounterLogic::backend_len) of `logic` words.
+    pub fn with_backend(logic: L, len: usize, mut backend: S) -> Result<Self> {
+        let num_words = len * logic.backend_len();
+        ensure!(
+            backend.as_mut().len() == num_words,
+            "The backend should contain {} words. Got {}",
+            num_words,
+            backend.as_mut().len()
+        );
+        backend.as_mut().iter_mut().for_each(|w| *w = W::ZERO);
+        Ok(Self::from_parts(logic, backend))
+    }
+}
+
 impl<L: SliceCounterLogic<W> + Clone, W: Word, S: AsRef<[W]>> CounterArray<L>
     for SliceCounterArray<L, W, S>
 {
@@ -0,0 +1,49 @@
+//! Utilities to build the symmetric closure of a graph.
+
+use crate::utils::{CsrGraph, TempMmapOptions};
+use anyhow::{Context, Result};
+use dsi_progress_logger::ProgressLog;
+use webgraph::traits::RandomAccessGraph;
+
+/// Builds the symmetric closure of a graph in [CSR format](CsrGraph).
+///
+/// The successors of a node in the result are the union, without
+/// duplicates, of its successors and of its predecessors in `graph`, so the
+/// result can be used as an undirected version of `graph` by algorithms
+/// requiring a symmetric graph. Successors in the result are sorted.
+///
+/// If a transpose of `graph` is not available, it can be built with
+/// [`transpose::build`](crate::utils::transpose::build).
+///
+/// # Arguments
+/// * `graph`: the graph to symmetrize.
+/// * `transpose`: the transpose of `graph`.
+/// * `mmap_options`: the options to use for the memory backing the result.
+/// * `pl`: A progress logger.
+///
+/// # Examples
+///
+/// ```
+/// use dsi_progress_logger::no_logging;
+/// use webgraph::{graphs::vec_graph::VecGraph, labels::Left, traits::RandomAccessGraph};
+/// use webgraph_algo::utils::{symmetrize, transpose, TempMmapOptions};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let graph = Left(VecGraph::from_arc_list([(0, 1), (2, 1)]));
+/// let transpose = transpose::build(&graph, TempMmapOptions::Default, no_logging![])?;
+/// let symmetric = symmetrize::build(&graph, &transpose, TempMmapOptions::Default, no_logging![])?;
+///
+/// assert_eq!(symmetric.successors(1).collect::<Vec<_>>(), vec![0, 2]);
+/// assert_eq!(symmetric.successors(2).collect::<Vec<_>>(), vec![1]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn build(
+    graph: impl RandomAccessGraph,
+    transpose: impl RandomAccessGraph,
+    mmap_options: TempMmapOptions,
+    pl: &mut impl ProgressLog,
+) -> Result<CsrGraph> {
+    CsrGraph::symmetrize(graph, transpose, mmap_options, pl)
+        .with_context(|| "Could not build the symmetric graph")
+}
@@ -0,0 +1,81 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+/// An element of a slice in the heap of [`top_k`].
+///
+/// Elements are ordered from the best to the worst, that is, by decreasing
+/// value and then by increasing index, so that the maximum of a
+/// [`BinaryHeap`] is the worst element.
+struct Candidate<T> {
+    value: T,
+    index: usize,
+}
+
+impl<T: PartialOrd> PartialEq for Candidate<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: PartialOrd> Eq for Candidate<T> {}
+
+impl<T: PartialOrd> PartialOrd for Candidate<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: PartialOrd> Ord for Candidate<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .value
+            .partial_cmp(&self.value)
+            .unwrap()
+            .then(self.index.cmp(&other.index))
+    }
+}
+
+/// Returns the indices of the `k` largest values in a slice, in decreasing
+/// order of value.
+///
+/// If the slice contains less than `k` elements, the indices of all elements
+/// are returned. In case of ties, elements with smaller index come first.
+///
+/// The selection uses a binary heap containing at most `k` elements, so it
+/// requires time *O*(*n* log *k*) and space *O*(*k*).
+///
+/// # Arguments
+/// * `slice`: the slice of elements.
+/// * `k`: the number of indices to return.
+///
+/// # Panics
+///
+/// If a comparison returns [`None`].
+///
+/// # Examples
+/// ```
+/// # use webgraph_algo::utils::math::top_k;
+/// let v = vec![1, 7, 5, 2, 7, 3];
+/// let indices = top_k(&v, 3);
+/// assert_eq!(indices, vec![1, 4, 2]);
+/// ```
+pub fn top_k<T: PartialOrd + Copy>(slice: &[T], k: usize) -> Vec<usize> {
+    if k == 0 {
+        return Vec::new();
+    }
+
+    let mut heap = BinaryHeap::with_capacity(k.min(slice.len()) + 1);
+    for (index, &value) in slice.iter().enumerate() {
+        let candidate = Candidate { value, index };
+        if heap.len() < k {
+            heap.push(candidate);
+        } else if candidate < *heap.peek().unwrap() {
+            // The candidate is better than the worst element in the heap
+            *heap.peek_mut().unwrap() = candidate;
+        }
+    }
+
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|candidate| candidate.index)
+        .collect()
+}
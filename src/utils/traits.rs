@@ -1,3 +1,4 @@
+use rayon::{prelude::*, ThreadPool};
 use std::borrow::Borrow;
 
 /// Algorithms definining a kind of counter.
@@ -42,6 +43,22 @@ pub trait CounterLogic {
     /// Adds an element to a counter with the given backend.
     fn add(&self, backend: &mut Self::Backend, element: impl Borrow<Self::Item>);
 
+    /// Adds all elements returned by an iterator to a counter with the given
+    /// backend.
+    ///
+    /// The default implementation calls [`add`](CounterLogic::add) on each
+    /// element, but implementations may override this method to amortize the
+    /// cost of updating the backend.
+    fn add_all(
+        &self,
+        backend: &mut Self::Backend,
+        elements: impl IntoIterator<Item = impl Borrow<Self::Item>>,
+    ) {
+        for element in elements {
+            self.add(backend, element);
+        }
+    }
+
     /// Returns the count (possibly an estimation) of the number of distinct
     /// elements that have been added to a counter with the given backend so
     /// far.
@@ -89,6 +106,34 @@ pub trait MergeCounterLogic: CounterLogic {
         src: &Self::Backend,
         helper: &mut Self::Helper,
     );
+
+    /// Returns the count (possibly an estimation) of the number of distinct
+    /// elements that have been added to at least one of two counters with the
+    /// given backends, that is, the count of the union.
+    ///
+    /// Neither backend is modified: the merge is performed on a temporary
+    /// counter.
+    fn union_count(&self, x: &Self::Backend, y: &Self::Backend) -> f64 {
+        let mut counter = self.new_counter();
+        counter.set(x);
+        self.merge(counter.as_mut(), y);
+        counter.count()
+    }
+
+    /// Returns an estimation of the Jaccard similarity of the sets of elements
+    /// added to two counters with the given backends.
+    ///
+    /// The similarity is estimated as `(|X| + |Y| - |X ∪ Y|) / |X ∪ Y|` using
+    /// [`count`](CounterLogic::count) and
+    /// [`union_count`](MergeCounterLogic::union_count), and it is clamped to
+    /// the interval [0 . . 1]. The similarity of two empty counters is zero.
+    fn jaccard(&self, x: &Self::Backend, y: &Self::Backend) -> f64 {
+        let union = self.union_count(x, y);
+        if union == 0.0 {
+            return 0.0;
+        }
+        ((self.count(x) + self.count(y) - union) / union).clamp(0.0, 1.0)
+    }
 }
 
 /// Trait implemented by [counter logics](CounterLogic) whose backend is a slice
@@ -127,6 +172,15 @@ pub trait CounterMut<L: CounterLogic + ?Sized>: Counter<L> + AsMut<L::Backend> {
     /// Adds an element to the counter.
     fn add(&mut self, element: impl Borrow<L::Item>);
 
+    /// Adds all elements returned by an iterator to the counter.
+    ///
+    /// See [`CounterLogic::add_all`] for more details.
+    fn add_all(&mut self, elements: impl IntoIterator<Item = impl Borrow<L::Item>>) {
+        for element in elements {
+            self.add(element);
+        }
+    }
+
     /// Clears the counter, making it empty.
     fn clear(&mut self);
 
@@ -157,6 +211,23 @@ pub trait MergeCounter<L: MergeCounterLogic + ?Sized>: CounterMut<L> {
     /// [`as_ref`](AsRef) on the counter. This approach makes it
     /// possible to merge both owned and non-owned counters.
     fn merge_with_helper(&mut self, backend: &L::Backend, helper: &mut L::Helper);
+
+    /// Returns the count (possibly an estimation) of the union of `self`
+    /// and of a backend, without modifying either.
+    ///
+    /// If you need the count of the union with another counter, just use
+    /// [`as_ref`](AsRef) on the counter.
+    fn union_count(&self, backend: &L::Backend) -> f64 {
+        self.logic().union_count(self.as_ref(), backend)
+    }
+
+    /// Returns an estimation of the Jaccard similarity of `self` and of a
+    /// backend.
+    ///
+    /// See [`MergeCounterLogic::jaccard`] for more details.
+    fn jaccard(&self, backend: &L::Backend) -> f64 {
+        self.logic().jaccard(self.as_ref(), backend)
+    }
 }
 
 /// An array of immutable counters sharing a [`CounterLogic`].
@@ -195,6 +266,35 @@ pub trait CounterArray<L: CounterLogic + ?Sized> {
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Returns the count (possibly an estimation) of each counter in the
+    /// array.
+    ///
+    /// Counts are computed directly on the [backends](CounterArray::get_backend),
+    /// without creating intermediate counters.
+    fn counts(&self) -> Vec<f64> {
+        let logic = self.logic();
+        (0..self.len())
+            .map(|index| logic.count(self.get_backend(index)))
+            .collect()
+    }
+
+    /// Returns the count (possibly an estimation) of each counter in the
+    /// array, computing counts in parallel.
+    ///
+    /// # Arguments
+    /// * `thread_pool`: The thread pool to use for parallel computation.
+    fn par_counts(&self, thread_pool: &ThreadPool) -> Vec<f64>
+    where
+        Self: Sync,
+    {
+        thread_pool.install(|| {
+            (0..self.len())
+                .into_par_iter()
+                .map(|index| self.logic().count(self.get_backend(index)))
+                .collect()
+        })
+    }
 }
 
 /// An array of mutable counters sharing a [`CounterLogic`].
@@ -219,6 +319,12 @@ pub trait CounterArrayMut<L: CounterLogic + ?Sized>: CounterArray<L> {
 
     /// Resets all counters in the array.
     fn clear(&mut self);
+
+    /// Resets the counter at the specified index, leaving the other counters
+    /// untouched.
+    fn clear_counter(&mut self, index: usize) {
+        self.get_counter_mut(index).clear();
+    }
 }
 
 /// A trait for counter arrays that can be viewed as a [`SyncCounterArray`].
@@ -275,6 +381,14 @@ pub trait SyncCounterArray<L: CounterLogic + ?Sized>: Sync {
     /// The caller must ensure that there are no data races.
     unsafe fn clear(&self);
 
+    /// Clears the counter at `index`, leaving the other counters untouched,
+    /// using a shared reference to the counter array.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that there are no data races.
+    unsafe fn clear_counter(&self, index: usize);
+
     /// Returns the number of counters in the array.
     fn len(&self) -> usize;
 
@@ -0,0 +1,44 @@
+//! Utilities to build the transpose of a graph.
+
+use crate::utils::{CsrGraph, TempMmapOptions};
+use anyhow::{Context, Result};
+use dsi_progress_logger::ProgressLog;
+use webgraph::traits::RandomAccessGraph;
+
+/// Builds the transpose of a graph in [CSR format](CsrGraph).
+///
+/// The result can be used wherever a [`RandomAccessGraph`] is expected, for
+/// example as the transpose required by [strongly connected
+/// components](crate::algo::sccs) or
+/// [ExactSumSweep](crate::algo::exact_sum_sweep) algorithms when a
+/// precomputed transpose is not available. Successors in the transpose are
+/// sorted.
+///
+/// # Arguments
+/// * `graph`: the graph to transpose.
+/// * `mmap_options`: the options to use for the memory backing the
+///   transpose.
+/// * `pl`: A progress logger.
+///
+/// # Examples
+///
+/// ```
+/// use dsi_progress_logger::no_logging;
+/// use webgraph::{graphs::vec_graph::VecGraph, labels::Left, traits::RandomAccessGraph};
+/// use webgraph_algo::utils::{transpose, TempMmapOptions};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let graph = Left(VecGraph::from_arc_list([(0, 1), (2, 1), (1, 0)]));
+/// let transpose = transpose::build(&graph, TempMmapOptions::Default, no_logging![])?;
+///
+/// assert_eq!(transpose.successors(1).collect::<Vec<_>>(), vec![0, 2]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn build(
+    graph: impl RandomAccessGraph,
+    mmap_options: TempMmapOptions,
+    pl: &mut impl ProgressLog,
+) -> Result<CsrGraph> {
+    CsrGraph::transpose(graph, mmap_options, pl).with_context(|| "Could not build the transpose")
+}
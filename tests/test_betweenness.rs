@@ -0,0 +1,71 @@
+use dsi_progress_logger::prelude::*;
+use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
+use webgraph_algo::{algo::betweenness, threads};
+
+#[test]
+fn test_simple_graph() {
+    let arcs = [
+        (0, 0),
+        (1, 0),
+        (1, 2),
+        (2, 1),
+        (2, 3),
+        (2, 4),
+        (2, 5),
+        (3, 4),
+        (4, 3),
+        (5, 5),
+        (5, 6),
+        (5, 7),
+        (5, 8),
+        (6, 7),
+        (8, 7),
+    ];
+    let mut g = VecGraph::from_arc_list(arcs);
+    // An isolated node
+    g.add_node(9);
+    let graph = Left(g);
+
+    let betweenness = betweenness::brandes(&graph, &threads![], no_logging![]);
+
+    // Node 1 lies on the only shortest path from 2 to 0; node 2 lies on all
+    // shortest paths from 1 to 3, 4, 5, 6, 7, and 8; node 5 lies on all
+    // shortest paths from 1 and 2 to 6, 7, and 8. Self-loops at 0 and 5 and
+    // the isolated node 9 do not contribute.
+    assert_eq!(
+        betweenness,
+        vec![0.0, 1.0, 6.0, 0.0, 0.0, 6.0, 0.0, 0.0, 0.0, 0.0]
+    );
+}
+
+#[test]
+fn test_multiple_shortest_paths() {
+    // Two shortest paths from 0 to 3, one through 1 and one through 2
+    let graph = Left(VecGraph::from_arc_list([(0, 1), (0, 2), (1, 3), (2, 3)]));
+
+    let betweenness = betweenness::brandes(&graph, &threads![], no_logging![]);
+
+    assert_eq!(betweenness, vec![0.0, 0.5, 0.5, 0.0]);
+}
+
+#[test]
+fn test_many_shortest_paths() {
+    // A chain of diamonds: the number of shortest paths doubles at each
+    // diamond, and would overflow an integer counter
+    let k = 100;
+    let arcs: Vec<_> = (0..k)
+        .flat_map(|i| {
+            let c = 3 * i;
+            [(c, c + 1), (c, c + 2), (c + 1, c + 3), (c + 2, c + 3)]
+        })
+        .collect();
+    let graph = Left(VecGraph::from_arc_list(arcs));
+
+    let betweenness = betweenness::brandes(&graph, &threads![], no_logging![]);
+
+    // All shortest paths from a node preceding a cut node to a node following
+    // it pass through the cut node
+    for i in 1..k {
+        assert_eq!(betweenness[3 * i], (9 * i * (k - i)) as f64);
+    }
+}
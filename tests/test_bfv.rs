@@ -1,9 +1,13 @@
 use anyhow::Result;
 use dsi_progress_logger::prelude::*;
 use no_break::NoBreak;
-use std::ops::ControlFlow::Continue;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::ops::ControlFlow::{Break, Continue};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Mutex,
+};
 use webgraph::{
+    graphs::random::ErdosRenyi,
     labels::Left,
     prelude::{BvGraph, VecGraph},
     traits::{RandomAccessGraph, SequentialLabeling},
@@ -274,3 +278,672 @@ test_bfv_algo_par!(
     |g| { webgraph_algo::prelude::breadth_first::ParLowMem::<_>::new(g, 32,) },
     parallel_fast_callback
 );
+
+#[test]
+fn test_bidir_shortest_distance() -> Result<()> {
+    let arcs = [
+        (0, 0),
+        (1, 0),
+        (1, 2),
+        (2, 1),
+        (2, 3),
+        (2, 4),
+        (2, 5),
+        (3, 4),
+        (4, 3),
+        (5, 5),
+        (5, 6),
+        (5, 7),
+        (5, 8),
+        (6, 7),
+        (8, 7),
+    ];
+    let graph = Left(VecGraph::from_arc_list(arcs));
+    let transpose = Left(VecGraph::from_arc_list(arcs.map(|(u, v)| (v, u))));
+    let mut visit = breadth_first::BiDir::new(&graph, &transpose, 1);
+    let threads = threads![];
+
+    for src in 0..graph.num_nodes() {
+        // Distances from src with a plain sequential visit
+        let mut expected = vec![None; graph.num_nodes()];
+        let mut seq = breadth_first::Seq::new(&graph);
+        seq.visit(
+            src,
+            |event| {
+                if let breadth_first::EventPred::Unknown { curr, distance, .. } = event {
+                    expected[curr] = Some(distance);
+                }
+                Continue(())
+            },
+            no_logging![],
+        )
+        .continue_value_no_break();
+
+        for (dst, &expected) in expected.iter().enumerate() {
+            assert_eq!(
+                visit.shortest_distance(src, dst, &threads, no_logging![]),
+                expected,
+                "wrong distance from {} to {}",
+                src,
+                dst
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_parents() -> Result<()> {
+    let arcs = vec![
+        (0, 0),
+        (1, 0),
+        (1, 2),
+        (2, 1),
+        (2, 3),
+        (2, 4),
+        (2, 5),
+        (3, 4),
+        (4, 3),
+        (5, 5),
+        (5, 6),
+        (5, 7),
+        (5, 8),
+        (6, 7),
+        (8, 7),
+    ];
+    let graph = Left(VecGraph::from_arc_list(arcs));
+    let mut visit = breadth_first::ParFairPred::new(&graph, 1);
+    let threads = threads![];
+
+    let parents = visit.parents(1, &threads, no_logging![]);
+    assert_eq!(parents[1], None);
+    assert!(parents
+        .iter()
+        .enumerate()
+        .all(|(node, &parent)| node == 1 || parent.is_some()));
+
+    // Reconstruct a shortest path from 1 to 7
+    let mut path = vec![7];
+    while let Some(parent) = parents[*path.last().unwrap()] {
+        path.push(parent);
+    }
+    path.reverse();
+    assert_eq!(path, vec![1, 2, 5, 7]);
+
+    // Nodes not reachable from the root have no parent
+    let parents = visit.parents(3, &threads, no_logging![]);
+    assert_eq!(
+        parents,
+        vec![None, None, None, None, Some(3), None, None, None, None]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_par_visit_from_nodes() -> Result<()> {
+    // A symmetric path 0 - 1 - ... - 9
+    let mut arcs = vec![];
+    for i in 0..9 {
+        arcs.push((i, i + 1));
+        arcs.push((i + 1, i));
+    }
+    let graph = Left(VecGraph::from_arc_list(arcs));
+    let mut visit = breadth_first::ParFairNoPred::new(&graph, 1);
+    let dists: Vec<AtomicUsize> = (0..10).map(|_| AtomicUsize::new(usize::MAX)).collect();
+    let roots: Vec<AtomicUsize> = (0..10).map(|_| AtomicUsize::new(usize::MAX)).collect();
+
+    visit
+        .par_visit_from_nodes(
+            [0, 9],
+            |event| {
+                if let breadth_first::EventNoPred::Unknown {
+                    curr,
+                    root,
+                    distance,
+                } = event
+                {
+                    dists[curr].store(distance, Ordering::Relaxed);
+                    roots[curr].store(root, Ordering::Relaxed);
+                }
+                Continue(())
+            },
+            |_| true,
+            &threads![],
+            no_logging![],
+        )
+        .continue_value_no_break();
+
+    assert_eq!(into_non_atomic(dists), vec![0, 1, 2, 3, 4, 4, 3, 2, 1, 0]);
+    let roots = into_non_atomic(roots);
+    assert_eq!(&roots[..5], &[0; 5]);
+    assert_eq!(&roots[5..], &[9; 5]);
+
+    Ok(())
+}
+
+#[test]
+fn test_break_on_target() -> Result<()> {
+    let num_nodes = 100;
+    let target = 10;
+    let graph = Left(VecGraph::from_arc_list((1..num_nodes).map(|i| (i - 1, i))));
+
+    let mut seq = breadth_first::Seq::new(&graph);
+    let mut discovered = 0;
+    let result = seq.visit(
+        0,
+        |event| {
+            if let breadth_first::EventPred::Unknown { curr, .. } = event {
+                discovered += 1;
+                if curr == target {
+                    return Break(StoppedWhenDone);
+                }
+            }
+            Continue(())
+        },
+        no_logging![],
+    );
+    assert!(matches!(result, Break(StoppedWhenDone)));
+    assert_eq!(discovered, target + 1);
+
+    let mut visit = breadth_first::ParFairNoPred::new(&graph, 1);
+    let discovered = AtomicUsize::new(0);
+    let result = visit.par_visit(
+        0,
+        |event| {
+            if let breadth_first::EventNoPred::Unknown { curr, .. } = event {
+                discovered.fetch_add(1, Ordering::Relaxed);
+                if curr == target {
+                    return Break(StoppedWhenDone);
+                }
+            }
+            Continue(())
+        },
+        &threads![],
+        no_logging![],
+    );
+    assert!(matches!(result, Break(StoppedWhenDone)));
+    assert_eq!(discovered.into_inner(), target + 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_reset_nodes() -> Result<()> {
+    fn known_nodes<G: RandomAccessGraph + Sync>(
+        visit: &mut breadth_first::ParFairNoPred<G>,
+        root: usize,
+        threads: &rayon::ThreadPool,
+    ) -> Vec<(usize, usize)> {
+        let known = Mutex::new(Vec::new());
+        visit
+            .par_visit(
+                root,
+                |event| {
+                    if let breadth_first::EventNoPred::Unknown { curr, distance, .. } = event {
+                        known.lock().unwrap().push((curr, distance));
+                    }
+                    Continue(())
+                },
+                threads,
+                no_logging![],
+            )
+            .continue_value_no_break();
+        let mut known = known.into_inner().unwrap();
+        known.sort();
+        known
+    }
+
+    let graph = BvGraph::with_basename("tests/graphs/cnr-2000").load()?;
+    let mut visit = breadth_first::ParFairNoPred::new(&graph, 32);
+    let mut full = breadth_first::ParFairNoPred::new(&graph, 32);
+    let threads = threads![];
+
+    for root in (0..graph.num_nodes()).step_by(50_000) {
+        let known = known_nodes(&mut visit, root, &threads);
+        visit.reset_nodes(&known.iter().map(|&(node, _)| node).collect::<Vec<_>>());
+
+        // After resetting the known nodes, the visit must behave as after
+        // a full reset
+        let next = (root + 1) % graph.num_nodes();
+        Parallel::<breadth_first::EventNoPred>::reset(&mut full);
+        let expected = known_nodes(&mut full, next, &threads);
+        let actual = known_nodes(&mut visit, next, &threads);
+        assert_eq!(actual, expected);
+        visit.reset_nodes(&actual.iter().map(|&(node, _)| node).collect::<Vec<_>>());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_visit_until() -> Result<()> {
+    let graph = BvGraph::with_basename("tests/graphs/cnr-2000").load()?;
+    let mut visit = breadth_first::ParFairNoPred::new(&graph, 32);
+    let threads = threads![];
+    let expected_dists = correct_dists(&graph, 0);
+
+    let mut seq = breadth_first::Seq::new(&graph);
+    let mut reachable = vec![false; graph.num_nodes()];
+    seq.visit(
+        0,
+        |event| {
+            if let breadth_first::EventPred::Unknown { curr, .. } = event {
+                reachable[curr] = true;
+            }
+            Continue(())
+        },
+        no_logging![],
+    )
+    .continue_value_no_break();
+
+    for target in (0..graph.num_nodes()).step_by(10_000) {
+        assert_eq!(
+            visit.visit_until(0, target, &threads, no_logging![]),
+            reachable[target].then_some(expected_dists[target])
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_visit_until_stops_early() -> Result<()> {
+    // A long path: when the target is close to the root, only a few nodes
+    // are known at the end of the visit, and the remaining ones are visited
+    // by a subsequent visit of the whole graph
+    let num_nodes = 10_000;
+    let graph = Left(VecGraph::from_arc_list((1..num_nodes).map(|i| (i - 1, i))));
+    let mut visit = breadth_first::ParFairNoPred::new(&graph, 1);
+    let threads = threads![];
+
+    assert_eq!(
+        visit.visit_until(0, num_nodes - 1, &threads, no_logging![]),
+        Some(num_nodes - 1)
+    );
+    assert_eq!(visit.visit_until(0, 1, &threads, no_logging![]), Some(1));
+
+    let unknown = AtomicUsize::new(0);
+    visit
+        .par_visit_all(
+            |event| {
+                if let breadth_first::EventNoPred::Unknown { .. } = event {
+                    unknown.fetch_add(1, Ordering::Relaxed);
+                }
+                Continue(())
+            },
+            &threads,
+            no_logging![],
+        )
+        .continue_value_no_break();
+    assert!(unknown.into_inner() >= num_nodes - 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_distances() -> Result<()> {
+    let graph = BvGraph::with_basename("tests/graphs/cnr-2000").load()?;
+    let mut visit = breadth_first::ParFairNoPred::new(&graph, 32);
+    let expected_dists = correct_dists(&graph, 0);
+
+    let mut seq = breadth_first::Seq::new(&graph);
+    let mut reachable = vec![false; graph.num_nodes()];
+    seq.visit(
+        0,
+        |event| {
+            if let breadth_first::EventPred::Unknown { curr, .. } = event {
+                reachable[curr] = true;
+            }
+            Continue(())
+        },
+        no_logging![],
+    )
+    .continue_value_no_break();
+
+    let dists = visit.distances(0, &threads![], no_logging![]);
+
+    assert_eq!(dists.len(), graph.num_nodes());
+    for node in 0..graph.num_nodes() {
+        if reachable[node] {
+            assert_eq!(dists[node], expected_dists[node] as i64);
+        } else {
+            assert_eq!(dists[node], -1);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_granularity() {
+    let graph = Left(VecGraph::from_arc_list([(0, 1), (1, 2)]));
+    for granularity in [1, 32, 1000] {
+        assert_eq!(
+            breadth_first::ParFairNoPred::new(&graph, granularity).granularity(),
+            granularity
+        );
+        assert_eq!(
+            breadth_first::ParFairPred::new(&graph, granularity).granularity(),
+            granularity
+        );
+        assert_eq!(
+            breadth_first::ParLowMem::new(&graph, granularity).granularity(),
+            granularity
+        );
+    }
+}
+
+#[test]
+fn test_visit_backward() {
+    let arcs = [
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (2, 3),
+        (3, 5),
+        (4, 3),
+        (6, 4),
+        (1, 6),
+    ];
+    let graph = Left(VecGraph::from_arc_list(arcs));
+    let transpose = Left(VecGraph::from_arc_list(arcs.map(|(u, v)| (v, u))));
+    let sink = 5;
+    let threads = threads![4];
+
+    // Forward distances to the sink
+    let mut visit = breadth_first::ParFairNoPred::new(&graph, 1);
+    let expected: Vec<i64> = (0..graph.num_nodes())
+        .map(|node| visit.distances(node, &threads, no_logging![])[sink])
+        .collect();
+
+    Parallel::<breadth_first::EventNoPred>::reset(&mut visit);
+    let dists: Vec<AtomicUsize> = (0..graph.num_nodes())
+        .map(|_| AtomicUsize::new(usize::MAX))
+        .collect();
+    visit
+        .visit_backward(
+            sink,
+            &transpose,
+            |event| {
+                if let breadth_first::EventNoPred::Unknown { curr, distance, .. } = event {
+                    dists[curr].store(distance, Ordering::Relaxed);
+                }
+                Continue(())
+            },
+            |_| true,
+            &threads,
+            no_logging![],
+        )
+        .continue_value_no_break();
+
+    let dists: Vec<i64> = into_non_atomic(dists)
+        .into_iter()
+        .map(|d| if d == usize::MAX { -1 } else { d as i64 })
+        .collect();
+    assert_eq!(dists, expected);
+    assert_eq!(dists, vec![4, 3, 2, 1, 2, 0, 3]);
+}
+
+#[test]
+fn test_deterministic() {
+    let graph = Left(VecGraph::from_lender(
+        ErdosRenyi::new(1000, 0.005, 0).iter(),
+    ));
+
+    let events_pred = |num_threads: usize| {
+        let events = Mutex::new(Vec::new());
+        let mut visit = breadth_first::ParFairPred::new(&graph, 1).deterministic(true);
+        visit
+            .par_visit_all(
+                |event| {
+                    events.lock().unwrap().push(event);
+                    Continue(())
+                },
+                &threads![num_threads],
+                no_logging![],
+            )
+            .continue_value_no_break();
+        events.into_inner().unwrap()
+    };
+    let events_no_pred = |num_threads: usize| {
+        let events = Mutex::new(Vec::new());
+        let mut visit = breadth_first::ParFairNoPred::new(&graph, 1).deterministic(true);
+        visit
+            .par_visit_all(
+                |event| {
+                    events.lock().unwrap().push(event);
+                    Continue(())
+                },
+                &threads![num_threads],
+                no_logging![],
+            )
+            .continue_value_no_break();
+        events.into_inner().unwrap()
+    };
+
+    let expected = events_pred(1);
+    for num_threads in [2, 4, 8] {
+        assert_eq!(events_pred(num_threads), expected);
+    }
+    let expected = events_no_pred(1);
+    for num_threads in [2, 4, 8] {
+        assert_eq!(events_no_pred(num_threads), expected);
+    }
+
+    // Within each level, nodes are visited in increasing order
+    let mut last = None;
+    for event in expected {
+        match event {
+            breadth_first::EventNoPred::Unknown { curr, distance, .. } => {
+                if let Some((last_curr, last_distance)) = last {
+                    assert!(distance > last_distance || curr > last_curr);
+                }
+                last = Some((curr, distance));
+            }
+            breadth_first::EventNoPred::Done { .. } => last = None,
+            _ => {}
+        }
+    }
+}
+
+#[test]
+fn test_visit_subset() {
+    // Two cycles 0 -> 1 -> 2 -> 0 and 3 -> 4 -> 3 joined by arcs 1 -> 3 and 2 -> 4
+    let graph = Left(VecGraph::from_arc_list([
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (1, 3),
+        (2, 4),
+        (3, 4),
+        (4, 3),
+    ]));
+    let component = [0, 0, 0, 1, 1];
+    let threads = threads![4];
+    let mut visit = breadth_first::ParFairNoPred::new(&graph, 1);
+
+    let visited = (0..graph.num_nodes())
+        .map(|_| AtomicUsize::new(usize::MAX))
+        .collect::<Vec<_>>();
+    let known = AtomicUsize::new(0);
+    visit
+        .visit_subset(
+            0,
+            &|node| component[node] == 0,
+            |event| {
+                match event {
+                    breadth_first::EventNoPred::Unknown { curr, distance, .. } => {
+                        visited[curr].store(distance, Ordering::Relaxed)
+                    }
+                    breadth_first::EventNoPred::Known { curr, .. } => {
+                        assert_eq!(component[curr], 0);
+                        known.fetch_add(1, Ordering::Relaxed);
+                    }
+                    _ => {}
+                }
+                Continue(())
+            },
+            &threads,
+            no_logging![],
+        )
+        .continue_value_no_break();
+
+    assert_eq!(
+        into_non_atomic(visited),
+        vec![0, 1, 2, usize::MAX, usize::MAX]
+    );
+    assert_eq!(known.load(Ordering::Relaxed), 1);
+
+    // Nodes outside the subset have not been marked as known
+    let count = AtomicUsize::new(0);
+    visit
+        .par_visit(
+            3,
+            |event| {
+                if let breadth_first::EventNoPred::Unknown { .. } = event {
+                    count.fetch_add(1, Ordering::Relaxed);
+                }
+                Continue(())
+            },
+            &threads,
+            no_logging![],
+        )
+        .continue_value_no_break();
+    assert_eq!(count.load(Ordering::Relaxed), 2);
+
+    // A root outside the subset yields no visit
+    Parallel::<breadth_first::EventNoPred>::reset(&mut visit);
+    count.store(0, Ordering::Relaxed);
+    visit
+        .visit_subset(
+            3,
+            &|node| component[node] == 0,
+            |_| {
+                count.fetch_add(1, Ordering::Relaxed);
+                Continue(())
+            },
+            &threads,
+            no_logging![],
+        )
+        .continue_value_no_break();
+    assert_eq!(count.load(Ordering::Relaxed), 0);
+}
+
+#[test]
+fn test_visit_with_level_sizes() -> Result<()> {
+    // A path 0 -> 1 -> ... -> 9
+    let graph = Left(VecGraph::from_arc_list((0..9).map(|i| (i, i + 1))));
+    let threads = threads![4];
+    let mut visit = breadth_first::ParFairNoPred::new(&graph, 1);
+
+    let level_sizes = visit
+        .visit_with_level_sizes(0, |_| Continue(()), |_| true, &threads, no_logging![])
+        .continue_value_no_break();
+    assert_eq!(level_sizes, vec![1; 10]);
+
+    // The root is already known
+    let level_sizes = visit
+        .visit_with_level_sizes(0, |_| Continue(()), |_| true, &threads, no_logging![])
+        .continue_value_no_break();
+    assert!(level_sizes.is_empty());
+
+    // The filter stops the visit at node 5
+    Parallel::<breadth_first::EventNoPred>::reset(&mut visit);
+    let level_sizes = visit
+        .visit_with_level_sizes(
+            2,
+            |_| Continue(()),
+            |args| args.curr <= 5,
+            &threads,
+            no_logging![],
+        )
+        .continue_value_no_break();
+    assert_eq!(level_sizes, vec![1; 4]);
+
+    // On a random graph, the sizes are the histogram of distances
+    let graph = Left(VecGraph::from_lender(
+        ErdosRenyi::new(1000, 0.005, 0).iter(),
+    ));
+    for num_threads in [1, 2, 4, 8] {
+        let threads = threads![num_threads];
+        let mut visit = breadth_first::ParFairNoPred::new(&graph, 8);
+        let distances = visit.distances(0, &threads, no_logging![]);
+        let max_distance = distances.iter().copied().max().unwrap_or(0) as usize;
+        let mut expected = vec![0; max_distance + 1];
+        for &distance in distances.iter().filter(|&&d| d >= 0) {
+            expected[distance as usize] += 1;
+        }
+
+        Parallel::<breadth_first::EventNoPred>::reset(&mut visit);
+        let level_sizes = visit
+            .visit_with_level_sizes(0, |_| Continue(()), |_| true, &threads, no_logging![])
+            .continue_value_no_break();
+        assert_eq!(level_sizes, expected);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_instrument() {
+    let graph = Left(VecGraph::from_lender(
+        ErdosRenyi::new(1000, 0.005, 0).iter(),
+    ));
+    let threads = threads![4];
+
+    let mut reachable = Vec::new();
+    breadth_first::Seq::new(&graph)
+        .visit(
+            0,
+            |event| {
+                if let breadth_first::EventPred::Unknown { curr, .. } = event {
+                    reachable.push(curr);
+                }
+                Continue(())
+            },
+            no_logging![],
+        )
+        .continue_value_no_break();
+    let reachable_arcs: usize = reachable.iter().map(|&node| graph.outdegree(node)).sum();
+
+    let check = |work: Vec<breadth_first::ThreadWork>| {
+        assert_eq!(work.len(), threads.current_num_threads());
+        assert_eq!(work.iter().map(|w| w.nodes).sum::<usize>(), reachable.len());
+        assert_eq!(work.iter().map(|w| w.arcs).sum::<usize>(), reachable_arcs);
+    };
+
+    for deterministic in [false, true] {
+        let mut visit = breadth_first::ParFairNoPred::new(&graph, 1)
+            .deterministic(deterministic)
+            .instrument(true);
+        visit
+            .par_visit(0, |_| Continue(()), &threads, no_logging![])
+            .continue_value_no_break();
+        check(visit.thread_work());
+        Parallel::<breadth_first::EventNoPred>::reset(&mut visit);
+        assert!(visit.thread_work().is_empty());
+
+        let mut visit = breadth_first::ParFairPred::new(&graph, 1)
+            .deterministic(deterministic)
+            .instrument(true);
+        visit
+            .par_visit(0, |_| Continue(()), &threads, no_logging![])
+            .continue_value_no_break();
+        check(visit.thread_work());
+    }
+
+    let mut visit = breadth_first::ParLowMem::new(&graph, 1).instrument(true);
+    visit
+        .par_visit(0, |_| Continue(()), &threads, no_logging![])
+        .continue_value_no_break();
+    check(visit.thread_work());
+
+    // Without instrumentation no work is recorded
+    let mut visit = breadth_first::ParFairNoPred::new(&graph, 1);
+    visit
+        .par_visit(0, |_| Continue(()), &threads, no_logging![])
+        .continue_value_no_break();
+    assert!(visit.thread_work().is_empty());
+}
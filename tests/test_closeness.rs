@@ -0,0 +1,85 @@
+use anyhow::Result;
+use dsi_progress_logger::prelude::*;
+use epserde::deser::{Deserialize, Flags};
+use webgraph::{
+    graphs::vec_graph::VecGraph,
+    labels::Left,
+    prelude::{BvGraph, DCF},
+    traits::SequentialLabeling,
+};
+use webgraph_algo::{
+    algo::{closeness, hyperball::HyperBallBuilder},
+    threads,
+    utils::{hyper_log_log::HyperLogLogBuilder, HyperLogLog, SliceCounterArray},
+};
+
+#[test]
+fn test_simple_graph() {
+    // A cycle 0 -> 1 -> 2 -> 0 with a pendant node 3 and an isolated node 4
+    let mut g = VecGraph::from_arc_list([(0, 1), (1, 2), (2, 0), (2, 3)]);
+    g.add_node(4);
+    let graph = Left(g);
+
+    let closeness = closeness::exact(&graph, false, &threads![], no_logging![]);
+    assert_eq!(closeness, vec![3.0 / 6.0, 3.0 / 5.0, 3.0 / 4.0, 0.0, 0.0]);
+
+    let normalized = closeness::exact(&graph, true, &threads![], no_logging![]);
+    assert_eq!(
+        normalized,
+        vec![9.0 / 24.0, 9.0 / 20.0, 9.0 / 16.0, 0.0, 0.0]
+    );
+}
+
+#[cfg_attr(feature = "slow_tests", test)]
+#[cfg_attr(not(feature = "slow_tests"), allow(dead_code))]
+fn test_cnr_2000() -> Result<()> {
+    let basename = "tests/graphs/cnr-2000";
+
+    let graph = BvGraph::with_basename(basename).load()?;
+    let transpose = BvGraph::with_basename(basename.to_owned() + "-t").load()?;
+    let cumulative = DCF::load_mmap(basename.to_owned() + ".dcf", Flags::empty())?;
+    let threads = threads![];
+
+    let log_2_num_reg = 8;
+    let hyper_log_log = HyperLogLogBuilder::new(graph.num_nodes())
+        .log_2_num_reg(log_2_num_reg)
+        .build()?;
+    let mut hyperball = HyperBallBuilder::with_transpose(
+        &graph,
+        &transpose,
+        cumulative.as_ref(),
+        SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+        SliceCounterArray::new(hyper_log_log, graph.num_nodes())?,
+    )
+    .sum_of_distances(true)
+    .build(no_logging![]);
+    hyperball.run_until_done(&threads, no_logging![])?;
+
+    // HyperBall's closeness is the reciprocal of the sum of distances, so
+    // we multiply it by the estimated number of reachable nodes
+    let approximate: Vec<f64> = hyperball
+        .closeness_centrality()?
+        .into_iter()
+        .zip(hyperball.reachable_nodes()?)
+        .map(|(c, r)| c * (r - 1.0).max(0.0))
+        .collect();
+    let exact = closeness::exact(&graph, false, &threads, no_logging![]);
+
+    let relative_errors: Vec<f64> = exact
+        .iter()
+        .zip(&approximate)
+        .filter(|(&e, _)| e != 0.0)
+        .map(|(&e, &a)| ((e - a) / e).abs())
+        .collect();
+    let mean_relative_error = relative_errors.iter().sum::<f64>() / relative_errors.len() as f64;
+    let rsd = HyperLogLog::rel_std(log_2_num_reg);
+
+    assert!(
+        mean_relative_error < rsd,
+        "mean relative error {} should be smaller than the RSD {}",
+        mean_relative_error,
+        rsd
+    );
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use dsi_progress_logger::prelude::*;
+use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
+use webgraph_algo::{algo::clustering, threads};
+
+#[test]
+fn test_path() {
+    let arcs = [(0, 1), (1, 2), (2, 3)];
+    let graph = Left(VecGraph::from_arc_list(arcs));
+    let transpose = Left(VecGraph::from_arc_list(arcs.map(|(u, v)| (v, u))));
+
+    let local = clustering::local_coefficient(&graph, &transpose, &threads![], no_logging![]);
+    assert_eq!(local, vec![0.0; 4]);
+
+    let global = clustering::global_coefficient(&graph, &transpose, &threads![], no_logging![]);
+    assert_eq!(global, 0.0);
+}
+
+#[test]
+fn test_triangle() {
+    // Arcs in both directions and self-loops must not change the result
+    let arcs = [(0, 1), (1, 2), (2, 0), (1, 0), (0, 0)];
+    let graph = Left(VecGraph::from_arc_list(arcs));
+    let transpose = Left(VecGraph::from_arc_list(arcs.map(|(u, v)| (v, u))));
+
+    let local = clustering::local_coefficient(&graph, &transpose, &threads![], no_logging![]);
+    assert_eq!(local, vec![1.0; 3]);
+
+    let global = clustering::global_coefficient(&graph, &transpose, &threads![], no_logging![]);
+    assert_eq!(global, 1.0);
+}
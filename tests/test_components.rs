@@ -0,0 +1,45 @@
+use dsi_progress_logger::prelude::*;
+use webgraph::{labels::Left, prelude::VecGraph};
+use webgraph_algo::{prelude::components, threads};
+
+#[test]
+fn test_count_undirected() {
+    // Three disconnected cliques of sizes 3, 4 and 5
+    let mut graph = VecGraph::new();
+    let mut first = 0;
+    for size in [3, 4, 5] {
+        for u in first..first + size {
+            graph.add_node(u);
+            for v in first..first + size {
+                if u != v {
+                    graph.add_arc(u, v);
+                }
+            }
+        }
+        first += size;
+    }
+    let graph = Left(graph);
+
+    for num_threads in [1, 4] {
+        assert_eq!(
+            components::count_undirected(&graph, &threads![num_threads], no_logging![]),
+            3
+        );
+    }
+}
+
+#[test]
+fn test_count_undirected_isolated() {
+    let mut graph = VecGraph::new();
+    for node in 0..10 {
+        graph.add_node(node);
+    }
+    graph.add_arc(3, 7);
+    graph.add_arc(7, 3);
+    let graph = Left(graph);
+
+    assert_eq!(
+        components::count_undirected(&graph, &threads![], no_logging![]),
+        9
+    );
+}
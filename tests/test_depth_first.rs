@@ -1,6 +1,19 @@
+use anyhow::Result;
 use dsi_progress_logger::prelude::*;
-use webgraph::{labels::Left, prelude::VecGraph};
-use webgraph_algo::algo::{acyclicity, top_sort, traits::Acyclicity};
+use no_break::NoBreak;
+use std::ops::ControlFlow::Continue;
+use webgraph::{
+    labels::Left,
+    prelude::{BvGraph, VecGraph},
+    traits::{RandomAccessGraph, SequentialLabeling},
+};
+use webgraph_algo::algo::{
+    acyclicity, find_cycle, top_sort, top_sort_kahn,
+    traits::{Acyclicity, Sequential},
+    try_top_sort,
+    visits::depth_first,
+};
+use webgraph_algo::threads;
 
 #[test]
 fn test_top_sort() {
@@ -46,3 +59,194 @@ fn test_acyclicity() {
     assert!(acyclicity(&graph, no_logging![]));
     assert!(graph.is_acyclic());
 }
+
+#[test]
+fn test_find_cycle() {
+    let graph = Left(VecGraph::from_arc_list([(1, 2), (0, 1)]));
+    assert_eq!(find_cycle(&graph, no_logging![]), None);
+
+    // A 3-cycle reachable from 0, with a chord that does not close a cycle
+    let graph = Left(VecGraph::from_arc_list([
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 1),
+        (1, 3),
+        (3, 4),
+    ]));
+    assert_eq!(find_cycle(&graph, no_logging![]), Some(vec![1, 2, 3]));
+
+    let graph = Left(VecGraph::from_arc_list([(0, 1), (1, 1)]));
+    assert_eq!(find_cycle(&graph, no_logging![]), Some(vec![1]));
+}
+
+#[test]
+fn test_try_top_sort() {
+    let graph = Left(VecGraph::from_arc_list([(0, 1), (0, 2), (2, 3), (1, 3)]));
+    assert_eq!(
+        try_top_sort(&graph, no_logging![]),
+        Ok(top_sort(&graph, no_logging![]))
+    );
+
+    let graph = Left(VecGraph::from_arc_list([
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 4),
+        (4, 2),
+        (4, 5),
+    ]));
+    let cycle = try_top_sort(&graph, no_logging![]).unwrap_err();
+    assert!(!cycle.is_empty());
+    // Each node of the cycle must have an arc to the next one
+    for (i, &node) in cycle.iter().enumerate() {
+        let next = cycle[(i + 1) % cycle.len()];
+        assert!(graph.successors(node).into_iter().any(|succ| succ == next));
+    }
+}
+
+/// Returns whether `sort` is a topological sort of `graph`.
+fn is_top_sort(graph: &impl RandomAccessGraph, sort: &[usize]) -> bool {
+    let mut pos = vec![usize::MAX; graph.num_nodes()];
+    for (i, &node) in sort.iter().enumerate() {
+        pos[node] = i;
+    }
+    sort.len() == graph.num_nodes()
+        && (0..graph.num_nodes()).all(|node| {
+            graph
+                .successors(node)
+                .into_iter()
+                .all(|succ| pos[node] < pos[succ])
+        })
+}
+
+#[test]
+fn test_top_sort_kahn() -> Result<()> {
+    let arcs = [(0, 1), (0, 2), (2, 3), (1, 3), (4, 0)];
+    let graph = Left(VecGraph::from_arc_list(arcs));
+    let transpose = Left(VecGraph::from_arc_list(arcs.map(|(u, v)| (v, u))));
+    for num_threads in [1, 4] {
+        let sort = top_sort_kahn(&graph, &transpose, &threads![num_threads], no_logging![])?;
+        assert_eq!(sort, vec![4, 0, 1, 2, 3].into_boxed_slice());
+    }
+
+    let arcs = [(0, 1), (1, 2), (2, 1), (2, 3), (4, 3)];
+    let graph = Left(VecGraph::from_arc_list(arcs));
+    let transpose = Left(VecGraph::from_arc_list(arcs.map(|(u, v)| (v, u))));
+    assert!(top_sort_kahn(&graph, &transpose, &threads![], no_logging![]).is_err());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "slow_tests", test)]
+#[cfg_attr(not(feature = "slow_tests"), allow(dead_code))]
+fn test_top_sort_kahn_cnr_2000() -> Result<()> {
+    let graph = BvGraph::with_basename("tests/graphs/cnr-2000").load()?;
+
+    // Keeping only the arcs towards larger nodes yields a DAG
+    let mut arcs = vec![];
+    for node in 0..graph.num_nodes() {
+        arcs.extend(
+            graph
+                .successors(node)
+                .into_iter()
+                .filter(|&succ| node < succ)
+                .map(|succ| (node, succ)),
+        );
+    }
+    let mut dag = VecGraph::new();
+    let mut transpose = VecGraph::new();
+    for node in 0..graph.num_nodes() {
+        dag.add_node(node);
+        transpose.add_node(node);
+    }
+    for &(u, v) in &arcs {
+        dag.add_arc(u, v);
+        transpose.add_arc(v, u);
+    }
+    let dag = Left(dag);
+    let transpose = Left(transpose);
+
+    assert!(is_top_sort(&dag, &top_sort(&dag, no_logging![])));
+    for num_threads in [1, 4] {
+        let sort = top_sort_kahn(&dag, &transpose, &threads![num_threads], no_logging![])?;
+        assert!(is_top_sort(&dag, &sort));
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "slow_tests", test)]
+#[cfg_attr(not(feature = "slow_tests"), allow(dead_code))]
+fn test_top_sort_long_path() {
+    // Depth-first visits are iterative, so a path much longer than what a
+    // recursive visit could handle on the native stack is not a problem
+    let num_nodes = 2_000_000;
+    let graph = Left(VecGraph::from_arc_list(
+        (1..num_nodes).rev().map(|i| (i - 1, i)),
+    ));
+
+    let sort = top_sort(&graph, no_logging![]);
+
+    assert_eq!(sort, (0..num_nodes).collect::<Vec<_>>().into_boxed_slice());
+    assert!(acyclicity(&graph, no_logging![]));
+}
+
+#[test]
+fn test_preorder_postorder() -> Result<()> {
+    let graph = BvGraph::with_basename("tests/graphs/cnr-2000").load()?;
+
+    let mut previsits = vec![];
+    let mut postvisits = vec![];
+    depth_first::SeqPred::new(&graph)
+        .visit_all(
+            |event| {
+                match event {
+                    depth_first::EventPred::Previsit { curr, .. } => previsits.push(curr),
+                    depth_first::EventPred::Postvisit { curr, .. } => postvisits.push(curr),
+                    _ => {}
+                }
+                Continue(())
+            },
+            no_logging![],
+        )
+        .continue_value_no_break();
+
+    assert_eq!(depth_first::preorder(&graph).collect::<Vec<_>>(), previsits);
+    assert_eq!(
+        depth_first::postorder(&graph).collect::<Vec<_>>(),
+        postvisits
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_postorder_top_sort() {
+    let graph = Left(VecGraph::from_arc_list([
+        (0, 3),
+        (1, 0),
+        (1, 4),
+        (2, 1),
+        (2, 5),
+        (4, 3),
+        (5, 4),
+        (6, 2),
+    ]));
+
+    let mut sort = depth_first::postorder(&graph).collect::<Vec<_>>();
+    sort.reverse();
+    assert_eq!(sort.len(), graph.num_nodes());
+
+    let mut position = vec![0; graph.num_nodes()];
+    for (i, &node) in sort.iter().enumerate() {
+        position[node] = i;
+    }
+    for node in 0..graph.num_nodes() {
+        for succ in graph.successors(node) {
+            assert!(position[node] < position[succ]);
+        }
+    }
+
+    assert_eq!(sort, top_sort(&graph, no_logging![]).into_vec());
+}
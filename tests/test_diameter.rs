@@ -0,0 +1,41 @@
+use dsi_progress_logger::prelude::*;
+use webgraph::{labels::Left, prelude::VecGraph};
+use webgraph_algo::{prelude::diameter, threads};
+
+/// Returns a symmetric path visiting the given nodes in order.
+fn path(nodes: &[usize]) -> Left<VecGraph<()>> {
+    let mut graph = VecGraph::new();
+    for w in nodes.windows(2) {
+        graph.add_node(w[0].max(w[1]));
+        graph.add_arc(w[0], w[1]);
+        graph.add_arc(w[1], w[0]);
+    }
+    Left(graph)
+}
+
+#[test]
+fn test_double_sweep_path() {
+    // Node 0 is in the middle of the path, so the first visit does not start
+    // from an endpoint
+    let nodes = [5, 3, 8, 1, 0, 7, 2, 9, 4, 6];
+    let graph = path(&nodes);
+
+    let (lower_bound, (u, v)) = diameter::double_sweep(&graph, &threads![], no_logging![]);
+
+    assert_eq!(lower_bound, nodes.len() - 1);
+    // From 0, the farthest endpoint is 6 (at distance 5, against 4 for 5)
+    assert_eq!(u, 6);
+    assert_eq!(v, 5);
+}
+
+#[test]
+fn test_double_sweep_single_node() {
+    let mut graph = VecGraph::new();
+    graph.add_node(0);
+    let graph = Left(graph);
+
+    assert_eq!(
+        diameter::double_sweep(&graph, &threads![], no_logging![]),
+        (0, (0, 0))
+    );
+}
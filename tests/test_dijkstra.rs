@@ -0,0 +1,141 @@
+use anyhow::Result;
+use dsi_progress_logger::prelude::*;
+use no_break::NoBreak;
+use std::ops::ControlFlow::Continue;
+use webgraph::prelude::VecGraph;
+use webgraph_algo::algo::visits::dijkstra::{self, EventPred};
+
+#[test]
+fn test_distances() -> Result<()> {
+    let graph = VecGraph::from_labeled_arc_list([
+        (0, 1, 7.0),
+        (0, 2, 9.0),
+        (0, 5, 14.0),
+        (1, 2, 10.0),
+        (1, 3, 15.0),
+        (2, 3, 11.0),
+        (2, 5, 2.0),
+        (3, 4, 6.0),
+        (5, 4, 9.0),
+        (6, 0, 1.0),
+    ]);
+    let mut visit = dijkstra::Seq::new(&graph);
+    let mut dist = vec![None; 7];
+    let mut pred = vec![None; 7];
+    let mut last = 0.0;
+    visit
+        .visit(
+            0,
+            |event| {
+                if let EventPred::Settled {
+                    curr,
+                    pred: p,
+                    distance,
+                    ..
+                } = event
+                {
+                    // Nodes are settled in nondecreasing order of distance
+                    assert!(distance >= last);
+                    last = distance;
+                    dist[curr] = Some(distance);
+                    pred[curr] = Some(p);
+                }
+                Continue(())
+            },
+            no_logging![],
+        )?
+        .continue_value_no_break();
+
+    assert_eq!(
+        dist,
+        vec![
+            Some(0.0),
+            Some(7.0),
+            Some(9.0),
+            Some(20.0),
+            Some(20.0),
+            Some(11.0),
+            None
+        ]
+    );
+    assert_eq!(
+        pred,
+        vec![Some(0), Some(0), Some(0), Some(2), Some(5), Some(2), None]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_integer_weights() -> Result<()> {
+    let graph = VecGraph::from_labeled_arc_list([
+        (0, 1, 5_usize),
+        (0, 2, 1),
+        (2, 1, 1),
+        (1, 3, 1),
+        (2, 3, 4),
+    ]);
+    let mut visit = dijkstra::Seq::new(&graph);
+    let mut dist = vec![usize::MAX; 4];
+    visit
+        .visit(
+            0,
+            |event| {
+                if let EventPred::Settled { curr, distance, .. } = event {
+                    dist[curr] = distance;
+                }
+                Continue(())
+            },
+            no_logging![],
+        )?
+        .continue_value_no_break();
+
+    assert_eq!(dist, vec![0, 2, 1, 3]);
+
+    // A second visit from a settled node does nothing
+    let mut events = 0;
+    visit
+        .visit(
+            2,
+            |_| {
+                events += 1;
+                Continue(())
+            },
+            no_logging![],
+        )?
+        .continue_value_no_break();
+    assert_eq!(events, 0);
+
+    // After a reset, the visit can be reused
+    visit.reset();
+    visit
+        .visit(
+            2,
+            |event| {
+                if let EventPred::Settled { curr, distance, .. } = event {
+                    dist[curr] = distance;
+                }
+                Continue(())
+            },
+            no_logging![],
+        )?
+        .continue_value_no_break();
+    assert_eq!(dist, vec![0, 1, 0, 2]);
+
+    Ok(())
+}
+
+#[test]
+fn test_negative_weight() {
+    let graph = VecGraph::from_labeled_arc_list([(0, 1, 1.0), (1, 2, -1.0)]);
+    let mut visit = dijkstra::Seq::new(&graph);
+    assert!(visit
+        .visit(0, |_| Continue::<()>(()), no_logging![])
+        .is_err());
+
+    let graph = VecGraph::from_labeled_arc_list([(0, 1, f64::NAN)]);
+    let mut visit = dijkstra::Seq::new(&graph);
+    assert!(visit
+        .visit(0, |_| Continue::<()>(()), no_logging![])
+        .is_err());
+}
@@ -0,0 +1,37 @@
+use anyhow::Result;
+use dsi_progress_logger::prelude::*;
+use webgraph::{
+    graphs::random::ErdosRenyi, labels::Left, prelude::VecGraph, traits::SequentialLabeling,
+    transform::transpose,
+};
+use webgraph_algo::{
+    algo::exact_sum_sweep::{self, OutputLevel},
+    prelude::eccentricity,
+    threads,
+};
+
+#[test]
+fn test_exact_sum_sweep() -> Result<()> {
+    let graph = Left(VecGraph::from_lender(ErdosRenyi::new(100, 0.03, 0).iter()));
+    let transpose = Left(VecGraph::from_lender(transpose(&graph, 10000)?.iter()));
+    let threads = threads![];
+
+    let expected =
+        exact_sum_sweep::All::compute_directed(&graph, &transpose, None, &threads, no_logging![]);
+
+    for node in 0..graph.num_nodes() {
+        assert_eq!(
+            eccentricity::single_source(&graph, node, &threads, no_logging![]),
+            expected.forward_eccentricities[node]
+        );
+        assert_eq!(
+            eccentricity::forward_and_backward(&graph, &transpose, node, &threads, no_logging![]),
+            (
+                expected.forward_eccentricities[node],
+                expected.backward_eccentricities[node]
+            )
+        );
+    }
+
+    Ok(())
+}
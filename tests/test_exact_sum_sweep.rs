@@ -5,10 +5,11 @@ use dsi_progress_logger::no_logging;
 use no_break::NoBreak;
 use sux::bits::AtomicBitVec;
 use webgraph::graphs::random::ErdosRenyi;
-use webgraph::traits::SequentialLabeling;
+use webgraph::traits::{RandomAccessGraph, SequentialLabeling};
 use webgraph::transform::transpose;
 use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
 use webgraph_algo::algo::exact_sum_sweep::*;
+use webgraph_algo::algo::sccs;
 use webgraph_algo::prelude::breadth_first::{EventPred, Seq};
 use webgraph_algo::threads;
 use webgraph_algo::traits::Sequential;
@@ -303,14 +304,30 @@ fn test_no_radial_vertices() -> Result<()> {
 }
 
 #[test]
-#[should_panic(expected = "Trying to build All without all eccentricities computed")]
 fn test_empty_graph() {
     let vec_graph: VecGraph<()> = VecGraph::new();
 
     let graph = Left(vec_graph.clone());
     let transposed = Left(vec_graph);
 
-    All::compute_directed(&graph, &transposed, None, &threads![], no_logging![]);
+    let sum_sweep = All::compute_directed(&graph, &transposed, None, &threads![], no_logging![]);
+
+    assert!(sum_sweep.forward_eccentricities.is_empty());
+    assert!(sum_sweep.backward_eccentricities.is_empty());
+    assert_eq!(sum_sweep.diameter, 0);
+    assert_eq!(sum_sweep.radius, 0);
+    assert_eq!(sum_sweep.radius_iterations, 0);
+    assert_eq!(sum_sweep.diameter_iterations, 0);
+
+    let sum_sweep =
+        RadiusDiameter::compute_directed(&graph, &transposed, None, &threads![], no_logging![]);
+    assert_eq!(sum_sweep.diameter, 0);
+    assert_eq!(sum_sweep.radius, 0);
+
+    let sum_sweep = All::compute_undirected(&graph, &threads![], no_logging![]);
+    assert!(sum_sweep.eccentricities.is_empty());
+    assert_eq!(sum_sweep.diameter, 0);
+    assert_eq!(sum_sweep.radius, 0);
 }
 
 #[test]
@@ -373,3 +390,200 @@ fn test_er() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_scc_graph_topological_order() -> Result<()> {
+    // Components {0, 1, 2}, {3, 4}, {5}, {6, 7} and {8}
+    let arcs = [
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (0, 3),
+        (2, 4),
+        (3, 4),
+        (4, 3),
+        (4, 5),
+        (3, 6),
+        (5, 6),
+        (6, 7),
+        (7, 6),
+        (8, 0),
+        (8, 7),
+    ];
+    let transposed_arcs = arcs.iter().map(|&(a, b)| (b, a)).collect::<Vec<_>>();
+    let graph = Left(VecGraph::from_arc_list(arcs));
+    let transpose = Left(VecGraph::from_arc_list(transposed_arcs));
+
+    let scc = webgraph_algo::algo::sccs::tarjan(&graph, no_logging![]);
+    let scc_graph = SccGraph::new_directed(&graph, &transpose, &scc, no_logging![]);
+
+    assert_eq!(scc_graph.num_components(), 5);
+    assert_eq!(scc_graph.num_connections(), 6);
+
+    let order = scc_graph.topological_order();
+    assert_eq!(order.len(), 5);
+    let mut position = vec![usize::MAX; 5];
+    for (i, &c) in order.iter().enumerate() {
+        position[c] = i;
+    }
+    for c in 0..scc_graph.num_components() {
+        for connection in scc_graph.children(c) {
+            assert!(position[c] < position[connection.target]);
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_precomputed_sccs() -> Result<()> {
+    for d in 2..=4 {
+        let graph = Left(VecGraph::from_lender(
+            ErdosRenyi::new(100, (d as f64) / 100.0, 1).iter(),
+        ));
+        let transpose = Left(VecGraph::from_lender(transpose(&graph, 10000)?.iter()));
+        let threads = threads![];
+
+        let expected =
+            RadiusDiameter::compute_directed(&graph, &transpose, None, &threads, no_logging![]);
+        let precomputed = RadiusDiameter::compute_directed_with_sccs(
+            &graph,
+            &transpose,
+            None,
+            sccs::tarjan(&graph, no_logging![]),
+            &threads,
+            no_logging![],
+        );
+
+        assert_eq!(precomputed.radius, expected.radius);
+        assert_eq!(precomputed.diameter, expected.diameter);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_bounds_snapshots() -> Result<()> {
+    let graph = Left(VecGraph::from_lender(ErdosRenyi::new(100, 0.05, 0).iter()));
+    let transpose = Left(VecGraph::from_lender(transpose(&graph, 10000)?.iter()));
+
+    let mut snapshots = Vec::new();
+    let result = All::compute_directed_with_hook(
+        &graph,
+        &transpose,
+        None,
+        sccs::tarjan(&graph, no_logging![]),
+        &threads![],
+        no_logging![],
+        |snapshot| snapshots.push(snapshot),
+    );
+
+    assert!(!snapshots.is_empty());
+    for pair in snapshots.windows(2) {
+        assert!(pair[0].diameter_low <= pair[1].diameter_low);
+        assert!(pair[0].radius_high >= pair[1].radius_high);
+        assert!(pair[0].iterations <= pair[1].iterations);
+    }
+    let last: &BoundsSnapshot = snapshots.last().unwrap();
+    assert_eq!(last.missing_nodes, 0);
+    assert_eq!(last.diameter_low, result.diameter);
+    assert_eq!(last.radius_high, result.radius);
+
+    Ok(())
+}
+
+#[test]
+fn test_scc_graph_save_load() -> Result<()> {
+    let graph = Left(VecGraph::from_lender(ErdosRenyi::new(200, 0.01, 0).iter()));
+    let transpose = Left(VecGraph::from_lender(transpose(&graph, 10000)?.iter()));
+
+    let scc = sccs::tarjan(&graph, no_logging![]);
+    let scc_graph = SccGraph::new_directed(&graph, &transpose, &scc, no_logging![]);
+    assert!(scc_graph.num_connections() > 0);
+
+    let tmp_dir = tempfile::tempdir()?;
+    let path = tmp_dir.path().join("scc_graph");
+    scc_graph.save(&path)?;
+    let loaded = SccGraph::load(&path, &graph, &transpose, &scc)?;
+
+    assert_eq!(loaded.num_components(), scc_graph.num_components());
+    assert_eq!(loaded.num_connections(), scc_graph.num_connections());
+    for component in 0..scc_graph.num_components() {
+        assert_eq!(loaded.children(component), scc_graph.children(component));
+    }
+
+    // Components of another graph are rejected
+    let other = Left(VecGraph::from_arc_list([(0, 1)]));
+    let other_scc = sccs::tarjan(&other, no_logging![]);
+    assert!(SccGraph::load(&path, &other, &other, &other_scc).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_diametral_path() -> Result<()> {
+    // A path 0 -> 1 -> ... -> 5
+    let graph = Left(VecGraph::from_arc_list((0..5).map(|i| (i, i + 1))));
+    let transposed = Left(VecGraph::from_arc_list((0..5).map(|i| (i + 1, i))));
+
+    let sum_sweep = All::compute_directed(&graph, &transposed, None, &threads![], no_logging![]);
+    assert_eq!(sum_sweep.diameter, 5);
+    let path = diametral_path(
+        &graph,
+        &transposed,
+        sum_sweep.diametral_vertex,
+        sum_sweep.diameter,
+        &threads![],
+    )?;
+    assert_eq!(path, vec![0, 1, 2, 3, 4, 5]);
+
+    // Node 2 has eccentricity 3 both forward and backward
+    assert!(diametral_path(&graph, &transposed, 2, 5, &threads![]).is_err());
+    assert!(diametral_path(&graph, &transposed, 6, 5, &threads![]).is_err());
+
+    // The symmetric path
+    let graph = Left(VecGraph::from_arc_list(
+        (0..5).flat_map(|i| [(i, i + 1), (i + 1, i)]),
+    ));
+    let sum_sweep = Diameter::compute_undirected(&graph, &threads![], no_logging![]);
+    let mut path = diametral_path(
+        &graph,
+        &graph,
+        sum_sweep.diametral_vertex,
+        sum_sweep.diameter,
+        &threads![],
+    )?;
+    if path[0] != 0 {
+        path.reverse();
+    }
+    assert_eq!(path, vec![0, 1, 2, 3, 4, 5]);
+
+    // On random graphs, the path is made of arcs of the graph
+    for d in 2..=4 {
+        let graph = Left(VecGraph::from_lender(
+            ErdosRenyi::new(100, (d as f64) / 100.0, 0).iter(),
+        ));
+        let transpose = Left(VecGraph::from_lender(transpose(&graph, 10000)?.iter()));
+
+        let ess =
+            RadiusDiameter::compute_directed(&graph, &transpose, None, &threads![], no_logging![]);
+        let diametral_vertex = ess.diametral_vertex;
+        let path = diametral_path(
+            &graph,
+            &transpose,
+            diametral_vertex,
+            ess.diameter,
+            &threads![],
+        )?;
+        assert_eq!(path.len(), ess.diameter + 1);
+        assert!(path.contains(&diametral_vertex));
+        for arc in path.windows(2) {
+            assert!(graph
+                .successors(arc[0])
+                .into_iter()
+                .any(|succ| succ == arc[1]));
+        }
+    }
+
+    Ok(())
+}
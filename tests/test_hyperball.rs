@@ -4,13 +4,18 @@ use common_traits::Float;
 use dsi_progress_logger::no_logging;
 use epserde::deser::{Deserialize, Flags};
 use std::hash::*;
+use sux::prelude::*;
 use webgraph::{
-    prelude::{BvGraph, DCF},
-    traits::SequentialLabeling,
+    labels::Left,
+    prelude::{BvGraph, VecGraph, DCF},
+    traits::{RandomAccessGraph, SequentialLabeling},
 };
-use webgraph_algo::utils::SliceCounterArray;
+use webgraph_algo::utils::{SliceCounterArray, TempMmapOptions};
 use webgraph_algo::{
-    algo::hyperball::HyperBallBuilder, threads, utils::hyper_log_log::HyperLogLogBuilder,
+    algo::hyperball::{HyperBallBuilder, Precision},
+    prelude::breadth_first,
+    threads,
+    utils::hyper_log_log::HyperLogLogBuilder,
 };
 
 /// Jenkins Hasher as implemented in the
@@ -293,3 +298,858 @@ fn test_cnr_2000() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "slow_tests", test)]
+#[cfg_attr(not(feature = "slow_tests"), allow(dead_code))]
+fn test_resume_from_checkpoint() -> Result<()> {
+    let basename = "tests/graphs/cnr-2000";
+
+    let graph = BvGraph::with_basename(basename).load()?;
+    let transpose = BvGraph::with_basename(basename.to_owned() + "-t").load()?;
+    let cumulative = DCF::load_mmap(basename.to_owned() + ".dcf", Flags::empty())?;
+
+    let hyper_log_log = HyperLogLogBuilder::new(graph.num_nodes())
+        .log_2_num_reg(6)
+        .build()?;
+    // A single thread makes the order of floating-point sums deterministic
+    let threads = threads![1];
+
+    let mut hyperball = HyperBallBuilder::with_transpose(
+        &graph,
+        &transpose,
+        cumulative.as_ref(),
+        SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+        SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+    )
+    .sum_of_distances(true)
+    .build(no_logging![]);
+    hyperball.run_until_done(&threads, no_logging![])?;
+
+    let tmp_dir = tempfile::tempdir()?;
+    let checkpoint = tmp_dir.path().join("hyperball.ckpt");
+
+    let mut interrupted = HyperBallBuilder::with_transpose(
+        &graph,
+        &transpose,
+        cumulative.as_ref(),
+        SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+        SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+    )
+    .sum_of_distances(true)
+    .build(no_logging![]);
+    interrupted.run_until_stable(5, &threads, no_logging![])?;
+    interrupted.save_state(&checkpoint)?;
+    drop(interrupted);
+
+    let mut resumed = HyperBallBuilder::with_transpose(
+        &graph,
+        &transpose,
+        cumulative.as_ref(),
+        SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+        SliceCounterArray::new(hyper_log_log, graph.num_nodes())?,
+    )
+    .sum_of_distances(true)
+    .resume_from(&checkpoint)?
+    .build(no_logging![]);
+    resumed.run_until_done(&threads, no_logging![])?;
+
+    let expected = hyperball.neighbourhood_function()?;
+    let actual = resumed.neighbourhood_function()?;
+    assert_eq!(expected.len(), actual.len());
+    for (&e, &a) in expected.iter().zip(actual.iter()) {
+        assert_eq!(e.to_bits(), a.to_bits());
+    }
+    assert_eq!(hyperball.sum_of_distances()?, resumed.sum_of_distances()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_resume_with_different_settings() -> Result<()> {
+    // A cycle with chords
+    let arcs: Vec<_> = (0..100)
+        .flat_map(|x| [(x, (x + 1) % 100), (x, (3 * x) % 100)])
+        .collect();
+    let graph = Left(VecGraph::from_arc_list(arcs.iter().copied()));
+    let mut builder = EliasFanoBuilder::new(graph.num_nodes() + 1, graph.num_arcs() as usize);
+    let mut cumulative = 0;
+    builder.push(cumulative);
+    for node in 0..graph.num_nodes() {
+        cumulative += graph.outdegree(node);
+        builder.push(cumulative);
+    }
+    let cumulative = builder.build_with_seq_and_dict();
+
+    let weights: Vec<usize> = (0..graph.num_nodes()).map(|node| node % 4).collect();
+    let other_weights: Vec<usize> = (0..graph.num_nodes()).map(|node| node % 3).collect();
+    let hyper_log_log = HyperLogLogBuilder::new(weights.iter().sum())
+        .log_2_num_reg(4)
+        .build()?;
+    let new_builder = || -> Result<_> {
+        Ok(HyperBallBuilder::new(
+            &graph,
+            &cumulative,
+            SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+            SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+        ))
+    };
+
+    let tmp_dir = tempfile::tempdir()?;
+    let checkpoint = tmp_dir.path().join("hyperball.ckpt");
+
+    let mut hyperball = new_builder()?
+        .weights(Some(&weights))
+        .seed(42)
+        .build(no_logging![]);
+    hyperball.run_until_stable(2, &threads![], no_logging![])?;
+    hyperball.save_state(&checkpoint)?;
+    drop(hyperball);
+
+    // The same settings can resume
+    new_builder()?
+        .weights(Some(&weights))
+        .seed(42)
+        .resume_from(&checkpoint)?;
+
+    assert!(new_builder()?.seed(42).resume_from(&checkpoint).is_err());
+    assert!(new_builder()?
+        .weights(Some(&other_weights))
+        .seed(42)
+        .resume_from(&checkpoint)
+        .is_err());
+    assert!(new_builder()?
+        .weights(Some(&weights))
+        .seed(43)
+        .resume_from(&checkpoint)
+        .is_err());
+    assert!(new_builder()?
+        .weights(Some(&weights))
+        .seed(42)
+        .centrality_precision(Precision::F32)
+        .resume_from(&checkpoint)
+        .is_err());
+    assert!(new_builder()?
+        .weights(Some(&weights))
+        .seed(42)
+        .reachable_only()
+        .resume_from(&checkpoint)
+        .is_err());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "slow_tests", test)]
+#[cfg_attr(not(feature = "slow_tests"), allow(dead_code))]
+fn test_seeded_weights() -> Result<()> {
+    let basename = "tests/graphs/cnr-2000";
+
+    let graph = BvGraph::with_basename(basename).load()?;
+    let transpose = BvGraph::with_basename(basename.to_owned() + "-t").load()?;
+    let cumulative = DCF::load_mmap(basename.to_owned() + ".dcf", Flags::empty())?;
+
+    let weights: Vec<usize> = (0..graph.num_nodes()).map(|node| node % 4).collect();
+    let hyper_log_log = HyperLogLogBuilder::new(weights.iter().sum())
+        .log_2_num_reg(6)
+        .build()?;
+
+    let mut reachable_nodes = Vec::new();
+    // Results must not depend on the number of threads
+    for num_threads in [1, 4] {
+        let mut hyperball = HyperBallBuilder::with_transpose(
+            &graph,
+            &transpose,
+            cumulative.as_ref(),
+            SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+            SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+        )
+        .weights(Some(&weights))
+        .seed(42)
+        .build(no_logging![]);
+        hyperball.run_until_done(&threads![num_threads], no_logging![])?;
+        reachable_nodes.push(hyperball.reachable_nodes()?);
+    }
+
+    assert_eq!(reachable_nodes[0], reachable_nodes[1]);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "slow_tests", test)]
+#[cfg_attr(not(feature = "slow_tests"), allow(dead_code))]
+fn test_transpose_in_memory() -> Result<()> {
+    let basename = "tests/graphs/cnr-2000";
+
+    let graph = BvGraph::with_basename(basename).load()?;
+    let transpose = BvGraph::with_basename(basename.to_owned() + "-t").load()?;
+    let cumulative = DCF::load_mmap(basename.to_owned() + ".dcf", Flags::empty())?;
+
+    let hyper_log_log = HyperLogLogBuilder::new(graph.num_nodes())
+        .log_2_num_reg(6)
+        .build()?;
+    // A single thread makes the order of floating-point sums deterministic
+    let threads = threads![1];
+
+    let mut hyperball = HyperBallBuilder::with_transpose(
+        &graph,
+        &transpose,
+        cumulative.as_ref(),
+        SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+        SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+    )
+    .sum_of_distances(true)
+    .build(no_logging![]);
+    hyperball.run_until_done(&threads, no_logging![])?;
+
+    let mut in_memory = HyperBallBuilder::new(
+        &graph,
+        cumulative.as_ref(),
+        SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+        SliceCounterArray::new(hyper_log_log, graph.num_nodes())?,
+    )
+    .sum_of_distances(true)
+    .build_transpose_in_memory(TempMmapOptions::Default, no_logging![])?
+    .build(no_logging![]);
+    in_memory.run_until_done(&threads, no_logging![])?;
+
+    assert_eq!(
+        hyperball.neighbourhood_function()?,
+        in_memory.neighbourhood_function()?
+    );
+    assert_eq!(hyperball.reachable_nodes()?, in_memory.reachable_nodes()?);
+    assert_eq!(hyperball.sum_of_distances()?, in_memory.sum_of_distances()?);
+
+    Ok(())
+}
+
+#[test]
+fn test_transpose_with_different_self_loops() -> Result<()> {
+    let graph = Left(VecGraph::from_arc_list([(0, 0), (0, 1), (1, 2), (2, 2)]));
+    // The transpose lacks the self-loops of the graph, which do not matter
+    // for systolic iterations
+    let transpose = Left(VecGraph::from_arc_list([(1, 0), (2, 1)]));
+    let mut builder = EliasFanoBuilder::new(graph.num_nodes() + 1, graph.num_arcs() as usize);
+    let mut cumul = 0;
+    builder.push(cumul);
+    for node in 0..graph.num_nodes() {
+        cumul += graph.outdegree(node);
+        builder.push(cumul);
+    }
+    let cumulative = builder.build_with_seq_and_dict();
+
+    let hyper_log_log = HyperLogLogBuilder::new(graph.num_nodes())
+        .log_2_num_reg(6)
+        .build()?;
+    let threads = threads![1];
+
+    let mut hyperball = HyperBallBuilder::with_transpose(
+        &graph,
+        &transpose,
+        &cumulative,
+        SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+        SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+    )
+    .sum_of_distances(true)
+    .build(no_logging![]);
+    hyperball.run_until_done(&threads, no_logging![])?;
+
+    let mut without_transpose = HyperBallBuilder::new(
+        &graph,
+        &cumulative,
+        SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+        SliceCounterArray::new(hyper_log_log, graph.num_nodes())?,
+    )
+    .sum_of_distances(true)
+    .build(no_logging![]);
+    without_transpose.run_until_done(&threads, no_logging![])?;
+
+    assert_eq!(
+        hyperball.reachable_nodes()?,
+        without_transpose.reachable_nodes()?
+    );
+    assert_eq!(
+        hyperball.sum_of_distances()?,
+        without_transpose.sum_of_distances()?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_last_iteration_stats() -> Result<()> {
+    // A cycle with chords
+    let arcs: Vec<_> = (0..100)
+        .flat_map(|x| [(x, (x + 1) % 100), (x, (3 * x) % 100)])
+        .collect();
+    let graph = Left(VecGraph::from_arc_list(arcs.iter().copied()));
+    let transpose = Left(VecGraph::from_arc_list(arcs.iter().map(|&(x, y)| (y, x))));
+    let mut builder = EliasFanoBuilder::new(graph.num_nodes() + 1, graph.num_arcs() as usize);
+    let mut cumulative = 0;
+    builder.push(cumulative);
+    for node in 0..graph.num_nodes() {
+        cumulative += graph.outdegree(node);
+        builder.push(cumulative);
+    }
+    let cumulative = builder.build_with_seq_and_dict();
+
+    let hyper_log_log = HyperLogLogBuilder::new(graph.num_nodes())
+        .log_2_num_reg(4)
+        .build()?;
+
+    let mut hyperball = HyperBallBuilder::with_transpose(
+        &graph,
+        &transpose,
+        &cumulative,
+        SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+        SliceCounterArray::new(hyper_log_log, graph.num_nodes())?,
+    )
+    .build(no_logging![]);
+    assert_eq!(hyperball.last_iteration_stats().iteration, 0);
+
+    hyperball.run(1, None, &threads![], no_logging![])?;
+
+    let stats = hyperball.last_iteration_stats();
+    assert_eq!(stats.iteration, 1);
+    assert!(stats.visited_arcs > 0);
+    assert!(stats.visited_arcs <= graph.num_arcs());
+    assert!(stats.modified_counters > 0);
+    assert!(stats.modified_counters <= graph.num_nodes() as u64);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "slow_tests", test)]
+#[cfg_attr(not(feature = "slow_tests"), allow(dead_code))]
+fn test_f32_centralities() -> Result<()> {
+    let basename = "tests/graphs/cnr-2000";
+
+    let graph = BvGraph::with_basename(basename).load()?;
+    let transpose = BvGraph::with_basename(basename.to_owned() + "-t").load()?;
+    let cumulative = DCF::load_mmap(basename.to_owned() + ".dcf", Flags::empty())?;
+
+    let hyper_log_log = HyperLogLogBuilder::new(graph.num_nodes())
+        .log_2_num_reg(6)
+        .build()?;
+    // A single thread makes the order of floating-point sums deterministic
+    let threads = threads![1];
+
+    let mut closeness = Vec::new();
+    for precision in [Precision::F64, Precision::F32] {
+        let mut hyperball = HyperBallBuilder::with_transpose(
+            &graph,
+            &transpose,
+            cumulative.as_ref(),
+            SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+            SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+        )
+        .sum_of_distances(true)
+        .centrality_precision(precision)
+        .build(no_logging![]);
+        hyperball.run_until_done(&threads, no_logging![])?;
+        closeness.push(hyperball.closeness_centrality()?);
+    }
+
+    assert_array_equal(&closeness[0], &closeness[1], 1E-4, "closeness");
+
+    Ok(())
+}
+
+#[test]
+fn test_arc_granularity() -> Result<()> {
+    // A cycle with chords
+    let arcs: Vec<_> = (0..100)
+        .flat_map(|x| [(x, (x + 1) % 100), (x, (3 * x) % 100)])
+        .collect();
+    let graph = Left(VecGraph::from_arc_list(arcs.iter().copied()));
+    let transpose = Left(VecGraph::from_arc_list(arcs.iter().map(|&(x, y)| (y, x))));
+    let mut builder = EliasFanoBuilder::new(graph.num_nodes() + 1, graph.num_arcs() as usize);
+    let mut cumulative = 0;
+    builder.push(cumulative);
+    for node in 0..graph.num_nodes() {
+        cumulative += graph.outdegree(node);
+        builder.push(cumulative);
+    }
+    let cumulative = builder.build_with_seq_and_dict();
+
+    let hyper_log_log = HyperLogLogBuilder::new(graph.num_nodes())
+        .log_2_num_reg(4)
+        .build()?;
+
+    let mut hyperball = HyperBallBuilder::with_transpose(
+        &graph,
+        &transpose,
+        &cumulative,
+        SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+        SliceCounterArray::new(hyper_log_log, graph.num_nodes())?,
+    )
+    .sum_of_distances(true)
+    .arc_granularity(1024)
+    .build(no_logging![]);
+    hyperball.run(2, None, &threads![], no_logging![])?;
+
+    let closeness = hyperball.closeness_centrality()?;
+    assert_eq!(closeness.len(), graph.num_nodes());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "slow_tests", test)]
+#[cfg_attr(not(feature = "slow_tests"), allow(dead_code))]
+fn test_normalized_centralities() -> Result<()> {
+    let basename = "tests/graphs/cnr-2000";
+
+    let graph = BvGraph::with_basename(basename).load()?;
+    let transpose = BvGraph::with_basename(basename.to_owned() + "-t").load()?;
+    let cumulative = DCF::load_mmap(basename.to_owned() + ".dcf", Flags::empty())?;
+
+    let hyper_log_log = HyperLogLogBuilder::new(graph.num_nodes())
+        .log_2_num_reg(6)
+        .build()?;
+
+    let mut hyperball = HyperBallBuilder::with_transpose(
+        &graph,
+        &transpose,
+        cumulative.as_ref(),
+        SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+        SliceCounterArray::new(hyper_log_log, graph.num_nodes())?,
+    )
+    .with_sum_of_distances(true)
+    .with_sum_of_inverse_distances(true)
+    .build(no_logging![]);
+    hyperball.run_until_done(&threads![], no_logging![])?;
+
+    let closeness = hyperball.closeness_centrality_normalized()?;
+    assert_eq!(closeness.len(), graph.num_nodes());
+    assert!(closeness.iter().all(|c| (0.0..=1.0).contains(c)));
+
+    let harmonic = hyperball.harmonic_centralities()?;
+    let normalized_harmonic = hyperball.harmonic_centrality_normalized()?;
+    let n = graph.num_nodes() as f64;
+    for (&h, &normalized) in harmonic.iter().zip(normalized_harmonic.iter()) {
+        assert!((0.0..=1.0).contains(&normalized));
+        assert!((h / (n - 1.0) - normalized).abs() < 1E-12);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_run_with() -> Result<()> {
+    // A cycle with chords
+    let arcs: Vec<_> = (0..100)
+        .flat_map(|x| [(x, (x + 1) % 100), (x, (3 * x) % 100)])
+        .collect();
+    let graph = Left(VecGraph::from_arc_list(arcs.iter().copied()));
+    let transpose = Left(VecGraph::from_arc_list(arcs.iter().map(|&(x, y)| (y, x))));
+    let mut builder = EliasFanoBuilder::new(graph.num_nodes() + 1, graph.num_arcs() as usize);
+    let mut cumulative = 0;
+    builder.push(cumulative);
+    for node in 0..graph.num_nodes() {
+        cumulative += graph.outdegree(node);
+        builder.push(cumulative);
+    }
+    let cumulative = builder.build_with_seq_and_dict();
+
+    let hyper_log_log = HyperLogLogBuilder::new(graph.num_nodes())
+        .log_2_num_reg(4)
+        .build()?;
+
+    let mut hyperball = HyperBallBuilder::with_transpose(
+        &graph,
+        &transpose,
+        &cumulative,
+        SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+        SliceCounterArray::new(hyper_log_log, graph.num_nodes())?,
+    )
+    .build(no_logging![]);
+
+    let mut calls = 0;
+    hyperball.run_with(
+        usize::MAX,
+        |stats| {
+            calls += 1;
+            assert_eq!(stats.iteration, calls);
+            stats.iteration == 2
+        },
+        &threads![],
+        no_logging![],
+    )?;
+
+    assert_eq!(calls, 2);
+    assert_eq!(hyperball.neighbourhood_function()?.len(), 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_weights_f64() -> Result<()> {
+    // A directed path, so that each node reaches the following ones
+    let graph = Left(VecGraph::from_arc_list([
+        (0, 1),
+        (1, 2),
+        (2, 3),
+        (3, 4),
+        (4, 5),
+    ]));
+    let mut builder = EliasFanoBuilder::new(graph.num_nodes() + 1, graph.num_arcs() as usize);
+    let mut cumulative = 0;
+    builder.push(cumulative);
+    for node in 0..graph.num_nodes() {
+        cumulative += graph.outdegree(node);
+        builder.push(cumulative);
+    }
+    let cumulative = builder.build_with_seq_and_dict();
+
+    let weights = [1.4, 3.2, 0.6, 2.7, 1.1, 4.0];
+    let mut rankings = Vec::new();
+    for scale in [1.0, 2.0] {
+        let weights: Vec<f64> = weights.iter().map(|w| w * scale).collect();
+        let hyper_log_log = HyperLogLogBuilder::new(weights.iter().sum::<f64>().round() as usize)
+            .log_2_num_reg(10)
+            .build()?;
+        let mut hyperball = HyperBallBuilder::new(
+            &graph,
+            &cumulative,
+            SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+            SliceCounterArray::new(hyper_log_log, graph.num_nodes())?,
+        )
+        .weights_f64(Some(&weights))
+        .with_sum_of_distances(true)
+        .seed(42)
+        .build(no_logging![]);
+        hyperball.run_until_done(&threads![], no_logging![])?;
+
+        let closeness = hyperball.closeness_centrality()?;
+        let mut ranking: Vec<usize> = (0..graph.num_nodes()).collect();
+        ranking.sort_by(|&x, &y| closeness[x].total_cmp(&closeness[y]));
+        rankings.push(ranking);
+    }
+
+    assert_eq!(rankings[0], rankings[1]);
+
+    Ok(())
+}
+
+#[test]
+fn test_reachable_only() -> Result<()> {
+    // A cycle with chords
+    let arcs: Vec<_> = (0..100)
+        .flat_map(|x| [(x, (x + 1) % 100), (x, (3 * x) % 100)])
+        .collect();
+    let graph = Left(VecGraph::from_arc_list(arcs.iter().copied()));
+    let transpose = Left(VecGraph::from_arc_list(arcs.iter().map(|&(x, y)| (y, x))));
+    let mut builder = EliasFanoBuilder::new(graph.num_nodes() + 1, graph.num_arcs() as usize);
+    let mut cumulative = 0;
+    builder.push(cumulative);
+    for node in 0..graph.num_nodes() {
+        cumulative += graph.outdegree(node);
+        builder.push(cumulative);
+    }
+    let cumulative = builder.build_with_seq_and_dict();
+
+    let hyper_log_log = HyperLogLogBuilder::new(graph.num_nodes())
+        .log_2_num_reg(6)
+        .build()?;
+
+    let mut runs = Vec::new();
+    for reachable_only in [false, true] {
+        let mut builder = HyperBallBuilder::with_transpose(
+            &graph,
+            &transpose,
+            &cumulative,
+            SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+            SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+        )
+        .with_sum_of_distances(true)
+        .with_sum_of_inverse_distances(true);
+        if reachable_only {
+            builder = builder.reachable_only();
+        }
+        let mut hyperball = builder.build(no_logging![]);
+
+        let mut visited_arcs = Vec::new();
+        hyperball.run_with(
+            usize::MAX,
+            |stats| {
+                visited_arcs.push(stats.visited_arcs);
+                false
+            },
+            &threads![],
+            no_logging![],
+        )?;
+        assert_eq!(
+            Some(&hyperball.last_iteration_stats().visited_arcs),
+            visited_arcs.last()
+        );
+
+        if reachable_only {
+            assert!(hyperball.neighbourhood_function().is_err());
+            assert!(hyperball.sum_of_distances().is_err());
+            assert!(hyperball.harmonic_centralities().is_err());
+        }
+        runs.push((
+            visited_arcs,
+            hyperball.last_iteration_stats().visited_arcs,
+            hyperball.reachable_nodes()?,
+        ));
+    }
+
+    // The same arcs are visited at each iteration, and the estimates are
+    // the same. Running times are not compared: the lean run saves only
+    // the centrality bookkeeping, which on a graph this small is dominated
+    // by the noise of the measurement, so a timing assertion would be flaky.
+    assert_eq!(runs[0].0, runs[1].0);
+    assert_eq!(runs[0].1, runs[1].1);
+    assert_eq!(runs[0].2, runs[1].2);
+
+    Ok(())
+}
+
+#[test]
+fn test_last_modified_nodes() -> Result<()> {
+    // A directed path: the counters modified at each iteration are a subset
+    // of the predecessors of those modified at the previous one
+    let graph = Left(VecGraph::from_arc_list((0..99).map(|x| (x, x + 1))));
+    let mut builder = EliasFanoBuilder::new(graph.num_nodes() + 1, graph.num_arcs() as usize);
+    let mut cumulative = 0;
+    builder.push(cumulative);
+    for node in 0..graph.num_nodes() {
+        cumulative += graph.outdegree(node);
+        builder.push(cumulative);
+    }
+    let cumulative = builder.build_with_seq_and_dict();
+
+    let hyper_log_log = HyperLogLogBuilder::new(graph.num_nodes())
+        .log_2_num_reg(8)
+        .build()?;
+    let mut hyperball = HyperBallBuilder::new(
+        &graph,
+        &cumulative,
+        SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+        SliceCounterArray::new(hyper_log_log, graph.num_nodes())?,
+    )
+    .build(no_logging![]);
+    assert!(hyperball.last_modified_nodes().is_empty());
+
+    // Each run starts from scratch, so the last iteration of each run is
+    // the next iteration of the computation
+    let threads = threads![];
+    let mut sizes = Vec::new();
+    for iterations in 1..=graph.num_nodes() {
+        hyperball.run(iterations, None, &threads, no_logging![])?;
+        let modified = hyperball.last_modified_nodes();
+        assert_eq!(
+            modified.len() as u64,
+            hyperball.last_iteration_stats().modified_counters
+        );
+        sizes.push(modified.len());
+        if modified.is_empty() {
+            break;
+        }
+    }
+
+    assert!(sizes[0] > 0);
+    assert!(sizes.windows(2).all(|w| w[0] >= w[1]));
+    assert_eq!(sizes.last(), Some(&0));
+
+    Ok(())
+}
+
+#[test]
+fn test_empty_graph() -> Result<()> {
+    let graph = Left(VecGraph::<()>::new());
+    let mut builder = EliasFanoBuilder::new(1, 0);
+    builder.push(0);
+    let cumulative = builder.build_with_seq_and_dict();
+
+    let hyper_log_log = HyperLogLogBuilder::new(1).log_2_num_reg(4).build()?;
+    let mut hyperball = HyperBallBuilder::new(
+        &graph,
+        &cumulative,
+        SliceCounterArray::new(hyper_log_log.clone(), 0)?,
+        SliceCounterArray::new(hyper_log_log, 0)?,
+    )
+    .with_sum_of_distances(true)
+    .with_sum_of_inverse_distances(true)
+    .build(no_logging![]);
+    hyperball.run_until_done(&threads![], no_logging![])?;
+
+    assert!(hyperball.reachable_nodes()?.is_empty());
+    assert!(hyperball.sum_of_distances()?.is_empty());
+    assert!(hyperball.harmonic_centralities()?.is_empty());
+    assert_eq!(hyperball.last_iteration_stats().iteration, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_single_node() -> Result<()> {
+    for arcs in [vec![], vec![(0, 0)]] {
+        let mut graph = VecGraph::from_arc_list(arcs);
+        graph.add_node(0);
+        let graph = Left(graph);
+        let mut builder = EliasFanoBuilder::new(2, graph.num_arcs() as usize);
+        builder.push(0);
+        builder.push(graph.outdegree(0));
+        let cumulative = builder.build_with_seq_and_dict();
+
+        let hyper_log_log = HyperLogLogBuilder::new(graph.num_nodes())
+            .log_2_num_reg(4)
+            .build()?;
+        let mut hyperball = HyperBallBuilder::new(
+            &graph,
+            &cumulative,
+            SliceCounterArray::new(hyper_log_log.clone(), 1)?,
+            SliceCounterArray::new(hyper_log_log, 1)?,
+        )
+        .with_sum_of_distances(true)
+        .with_sum_of_inverse_distances(true)
+        .build(no_logging![]);
+        hyperball.run_until_done(&threads![], no_logging![])?;
+
+        assert_eq!(hyperball.sum_of_distances()?, vec![0.0]);
+        assert_eq!(hyperball.harmonic_centralities()?, vec![0.0]);
+        assert_eq!(hyperball.harmonic_centrality_normalized()?, vec![0.0]);
+        assert_eq!(hyperball.closeness_centrality()?, vec![0.0]);
+        assert_eq!(hyperball.closeness_centrality_normalized()?, vec![0.0]);
+        assert_eq!(hyperball.lin_centrality()?, vec![1.0]);
+        assert_eq!(hyperball.nieminen_centrality()?, vec![1.0]);
+        assert!(hyperball.reachable_nodes()?.iter().all(|r| r.is_finite()));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_shared_thread_pool() -> Result<()> {
+    // A path 0 -> 1 -> ... -> 9
+    let graph = Left(VecGraph::from_arc_list((0..9).map(|i| (i, i + 1))));
+    let mut builder = EliasFanoBuilder::new(11, 9);
+    for cumul in (0..10).chain([9]) {
+        builder.push(cumul);
+    }
+    let cumulative = builder.build_with_seq_and_dict();
+
+    let thread_pool = rayon::ThreadPoolBuilder::new().num_threads(4).build()?;
+
+    let hyper_log_log = HyperLogLogBuilder::new(graph.num_nodes())
+        .log_2_num_reg(8)
+        .build()?;
+    let mut hyperball = HyperBallBuilder::new(
+        &graph,
+        &cumulative,
+        SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+        SliceCounterArray::new(hyper_log_log, graph.num_nodes())?,
+    )
+    .build(no_logging![]);
+    hyperball.run_until_done(&thread_pool, no_logging![])?;
+
+    // The pool is still available to other algorithms
+    let mut visit = breadth_first::ParFairNoPred::new(&graph, 1);
+    let reachable_nodes = hyperball.reachable_nodes()?;
+    for (node, &estimate) in reachable_nodes.iter().enumerate() {
+        let distances = visit.distances(node, &thread_pool, no_logging![]);
+        let exact = distances.iter().filter(|&&d| d >= 0).count();
+        assert!((estimate - exact as f64).abs() <= 1.0);
+    }
+
+    // And it can be used again by HyperBall
+    hyperball.run(2, None, &thread_pool, no_logging![])?;
+    assert_eq!(hyperball.last_iteration_stats().iteration, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_validate_discount_functions() -> Result<()> {
+    // A path 0 -> 1 -> ... -> 9
+    let graph = Left(VecGraph::from_arc_list((0..9).map(|i| (i, i + 1))));
+    let mut builder = EliasFanoBuilder::new(11, 9);
+    for cumul in (0..10).chain([9]) {
+        builder.push(cumul);
+    }
+    let cumulative = builder.build_with_seq_and_dict();
+
+    let hyper_log_log = HyperLogLogBuilder::new(graph.num_nodes())
+        .log_2_num_reg(6)
+        .build()?;
+    let builder = || {
+        HyperBallBuilder::new(
+            &graph,
+            &cumulative,
+            SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes()).unwrap(),
+            SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes()).unwrap(),
+        )
+        .discount_function(|d| 1.0 / d as f64)
+    };
+
+    // Infinite at distance 5
+    let singular = |d: usize| 1.0 / (d as f64 - 5.0);
+
+    assert!(builder()
+        .discount_function(singular)
+        .validate_discount_functions(true)
+        .try_build(no_logging![])
+        .is_err());
+    // Without validation the function is accepted
+    assert!(builder()
+        .discount_function(singular)
+        .try_build(no_logging![])
+        .is_ok());
+    // NaN is rejected, too
+    assert!(builder()
+        .discount_function(|d| if d == 9 { f64::NAN } else { 1.0 })
+        .validate_discount_functions(true)
+        .try_build(no_logging![])
+        .is_err());
+
+    let mut hyperball = builder()
+        .validate_discount_functions(true)
+        .try_build(no_logging![])?;
+    hyperball.run_until_done(&threads![], no_logging![])?;
+    assert!(hyperball
+        .discounted_centrality(0)?
+        .iter()
+        .all(|c| c.is_finite()));
+
+    Ok(())
+}
+
+#[test]
+fn test_discount_function_streaming() -> Result<()> {
+    let graph = Left(VecGraph::from_lender(
+        webgraph::graphs::random::ErdosRenyi::new(200, 0.02, 0).iter(),
+    ));
+    let mut builder = EliasFanoBuilder::new(graph.num_nodes() + 1, graph.num_arcs() as usize);
+    let mut cumul = 0;
+    builder.push(0);
+    for node in 0..graph.num_nodes() {
+        cumul += graph.outdegree(node);
+        builder.push(cumul);
+    }
+    let cumulative = builder.build_with_seq_and_dict();
+
+    let hyper_log_log = HyperLogLogBuilder::new(graph.num_nodes())
+        .log_2_num_reg(6)
+        .build()?;
+    let discount = |d: usize| 1.0 / (d * d) as f64;
+    let streamed = std::sync::Mutex::new(vec![0.0; graph.num_nodes()]);
+    let mut hyperball = HyperBallBuilder::new(
+        &graph,
+        &cumulative,
+        SliceCounterArray::new(hyper_log_log.clone(), graph.num_nodes())?,
+        SliceCounterArray::new(hyper_log_log, graph.num_nodes())?,
+    )
+    .discount_function(discount)
+    .discount_function_streaming(discount, |node, value| {
+        streamed.lock().unwrap()[node] += value;
+    })
+    .build(no_logging![]);
+    hyperball.run_until_done(&threads![4], no_logging![])?;
+
+    let accumulated = hyperball.discounted_centrality(0)?;
+    drop(hyperball);
+    let streamed = streamed.into_inner().unwrap();
+    assert_array_equal(&accumulated, &streamed, 1e-9, "discounted centralities");
+
+    Ok(())
+}
@@ -1,7 +1,11 @@
 use anyhow::Result;
+use std::hash::{BuildHasherDefault, DefaultHasher};
 use webgraph_algo::{
     prelude::*,
-    utils::{HyperLogLog, HyperLogLogBuilder, SliceCounterArray},
+    threads,
+    utils::{
+        HyperLogLog, HyperLogLogBuilder, MmapHelper, MmapSlice, SliceCounterArray, TempMmapOptions,
+    },
 };
 use xxhash_rust::xxh3::Xxh3Builder;
 
@@ -56,6 +60,67 @@ fn test_single() -> Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "xxhash")]
+#[test]
+fn test_single_xxhash() -> Result<()> {
+    let sizes = [1, 10, 100, 1000, 100_000];
+    let log2ms = [4, 6, 8, 12];
+
+    for size in sizes {
+        for log2m in log2ms {
+            let rsd = HyperLogLog::rel_std(log2m);
+            let mut default_correct = 0;
+            let mut xxhash_correct = 0;
+
+            for trial in 0..NUM_TRIALS {
+                let builder = HyperLogLogBuilder::new(size)
+                    .word_type::<u16>()
+                    .log_2_num_reg(log2m);
+                let default_logic = builder.clone().build()?;
+                let xxhash_logic = builder.with_xxhash().build()?;
+                let mut default_counter = default_logic.new_counter();
+                let mut xxhash_counter = xxhash_logic.new_counter();
+                // The default hasher has no seed, so we change the elements
+                let incr = (1 << 32) / size as i64;
+                let x = i64::MIN + trial as i64;
+
+                for i in 0..size {
+                    default_counter.add(x + i as i64 * incr);
+                    xxhash_counter.add(x + i as i64 * incr);
+                }
+
+                let float_size = size as f64;
+
+                if (float_size - default_counter.count()).abs() / float_size < 2.0 * rsd {
+                    default_correct += 1;
+                }
+                if (float_size - xxhash_counter.count()).abs() / float_size < 2.0 * rsd {
+                    xxhash_correct += 1;
+                }
+            }
+
+            assert!(
+                default_correct >= REQUIRED_TRIALS,
+                "assertion failed for size {} and log2m {}: default_correct = {} < {}",
+                size,
+                log2m,
+                default_correct,
+                REQUIRED_TRIALS
+            );
+            assert!(
+                xxhash_correct >= REQUIRED_TRIALS,
+                "assertion failed for size {} and log2m {}: xxhash_correct = {} < {}",
+                size,
+                log2m,
+                xxhash_correct,
+                REQUIRED_TRIALS
+            );
+        }
+    }
+
+    Ok(())
+}
+
 #[test]
 fn test_double() -> Result<()> {
     let sizes = [1, 10, 100, 1000, 100_000];
@@ -177,6 +242,392 @@ fn test_merge() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_union_count() -> Result<()> {
+    let sizes = [10, 100, 1000, 100_000];
+    let log2ms = [4, 6, 8, 12];
+
+    for size in sizes {
+        for log2m in log2ms {
+            let rsd = HyperLogLog::rel_std(log2m);
+            let mut correct = 0;
+
+            for trial in 0..NUM_TRIALS {
+                let logic = HyperLogLogBuilder::new(size)
+                    .word_type::<u16>()
+                    .log_2_num_reg(log2m)
+                    .build_hasher(Xxh3Builder::new().with_seed(trial))
+                    .build()?;
+                let mut counter_0 = logic.new_counter();
+                let mut counter_1 = logic.new_counter();
+                // The two counters share half of their elements
+                let incr = (1 << 32) / (size * 2) as i64;
+                let mut x = i64::MIN;
+                for i in 0..size + size / 2 {
+                    if i < size {
+                        counter_0.add(x);
+                    }
+                    if i >= size / 2 {
+                        counter_1.add(x);
+                    }
+                    x += incr;
+                }
+
+                let backend_0 = counter_0.as_ref().to_vec();
+                let backend_1 = counter_1.as_ref().to_vec();
+
+                let union = counter_0.union_count(counter_1.as_ref());
+
+                // The operands must not be modified
+                assert_eq!(counter_0.as_ref(), backend_0.as_slice());
+                assert_eq!(counter_1.as_ref(), backend_1.as_slice());
+
+                let float_size = (size + size / 2) as f64;
+
+                if (float_size - union).abs() / float_size < 2.0 * rsd {
+                    correct += 1;
+                }
+            }
+
+            assert!(
+                correct >= REQUIRED_TRIALS,
+                "assertion failed for size {} and log2m {}: correct = {} < {}",
+                size,
+                log2m,
+                correct,
+                REQUIRED_TRIALS
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_jaccard() -> Result<()> {
+    let size = 10_000;
+    let log2m = 12;
+    let rsd = HyperLogLog::rel_std(log2m);
+
+    let logic = HyperLogLogBuilder::new(size)
+        .word_type::<u16>()
+        .log_2_num_reg(log2m)
+        .build_hasher(Xxh3Builder::new().with_seed(0))
+        .build()?;
+
+    // Empty counters
+    let counter_0 = logic.new_counter();
+    let counter_1 = logic.new_counter();
+    assert_eq!(counter_0.jaccard(counter_1.as_ref()), 0.0);
+
+    // Identical counters
+    let mut counter_0 = logic.new_counter();
+    let mut counter_1 = logic.new_counter();
+    for x in 0..size {
+        counter_0.add(x);
+        counter_1.add(x);
+    }
+    assert!((1.0 - counter_0.jaccard(counter_1.as_ref())).abs() < 2.0 * rsd);
+
+    // Disjoint counters
+    let mut counter_0 = logic.new_counter();
+    let mut counter_1 = logic.new_counter();
+    for x in 0..size {
+        counter_0.add(x);
+        counter_1.add(x + size);
+    }
+    assert!(counter_0.jaccard(counter_1.as_ref()) < 4.0 * rsd);
+
+    Ok(())
+}
+
+#[test]
+fn test_bias_correction() -> Result<()> {
+    let log2m = 8;
+    let num_registers = 1 << log2m;
+    // Cardinalities between 2.5 and 4 times the number of registers
+    let sizes = [640, 768, 896, 1024];
+
+    let mut error = 0.0;
+    let mut abs_error = 0.0;
+    let mut corrected_error = 0.0;
+    let mut corrected_abs_error = 0.0;
+    let mut num_estimates = 0;
+
+    for size in sizes {
+        assert!(size as f64 >= 2.5 * num_registers as f64);
+        for trial in 0..NUM_TRIALS {
+            let builder = HyperLogLogBuilder::new(size)
+                .log_2_num_reg(log2m)
+                .build_hasher(Xxh3Builder::new().with_seed(trial));
+            let logic = builder.clone().build()?;
+            let corrected_logic = builder.bias_correction(true).build::<usize>()?;
+
+            let mut counter = logic.new_counter();
+            for x in 0..size {
+                counter.add(x);
+            }
+
+            let float_size = size as f64;
+            let estimate = logic.count(counter.as_ref());
+            let corrected_estimate = corrected_logic.count(counter.as_ref());
+            error += (estimate - float_size) / float_size;
+            abs_error += (estimate - float_size).abs() / float_size;
+            corrected_error += (corrected_estimate - float_size) / float_size;
+            corrected_abs_error += (corrected_estimate - float_size).abs() / float_size;
+            num_estimates += 1;
+        }
+    }
+
+    let num_estimates = num_estimates as f64;
+    let (error, abs_error) = (error / num_estimates, abs_error / num_estimates);
+    let (corrected_error, corrected_abs_error) = (
+        corrected_error / num_estimates,
+        corrected_abs_error / num_estimates,
+    );
+
+    assert!(
+        corrected_error.abs() < error.abs(),
+        "mean relative error did not improve: {} >= {}",
+        corrected_error,
+        error
+    );
+    assert!(
+        corrected_abs_error < abs_error,
+        "mean absolute relative error did not improve: {} >= {}",
+        corrected_abs_error,
+        abs_error
+    );
+
+    Ok(())
+}
+
+/// The number of elements fitting in a sparse counter with 256 5-bit registers
+/// and 64-bit hashes.
+const SPARSE_CAPACITY: usize = 20;
+
+#[test]
+fn test_sparse_promotion() -> Result<()> {
+    let builder = HyperLogLogBuilder::new(1000)
+        .word_type::<u64>()
+        .log_2_num_reg(8)
+        .build_hasher(Xxh3Builder::new().with_seed(0));
+    let dense = builder.clone().build()?;
+    let sparse = builder.sparse(true).build()?;
+
+    let mut dense_counter = dense.new_counter();
+    let mut sparse_counter = sparse.new_counter();
+    assert_eq!(sparse_counter.count(), 0.0);
+
+    // While sparse the count is exact, also in the presence of duplicates
+    for x in 0..SPARSE_CAPACITY {
+        dense_counter.add(x);
+        sparse_counter.add(x);
+        sparse_counter.add(x);
+        assert_eq!(sparse_counter.count(), (x + 1) as f64);
+    }
+
+    // After promotion the count is the HyperLogLog estimate
+    for x in SPARSE_CAPACITY..1000 {
+        dense_counter.add(x);
+        sparse_counter.add(x);
+        assert_eq!(sparse_counter.count(), dense_counter.count());
+    }
+
+    // Clearing brings the counter back to sparse mode
+    sparse_counter.clear();
+    sparse_counter.add(0);
+    assert_eq!(sparse_counter.count(), 1.0);
+
+    Ok(())
+}
+
+#[test]
+fn test_sparse_merge_sparse() -> Result<()> {
+    let builder = HyperLogLogBuilder::new(1000)
+        .word_type::<u64>()
+        .log_2_num_reg(8)
+        .build_hasher(Xxh3Builder::new().with_seed(0));
+    let dense = builder.clone().build()?;
+    let sparse = builder.sparse(true).build()?;
+
+    // The union fits in a sparse counter
+    let mut counter_0 = sparse.new_counter();
+    let mut counter_1 = sparse.new_counter();
+    for x in 0..8 {
+        counter_0.add(x);
+        counter_1.add(x + 4);
+    }
+    counter_0.merge(counter_1.as_ref());
+    assert_eq!(counter_0.count(), 12.0);
+    assert_eq!(counter_1.count(), 8.0);
+
+    // The union does not fit in a sparse counter
+    let mut counter_0 = sparse.new_counter();
+    let mut counter_1 = sparse.new_counter();
+    let mut dense_counter = dense.new_counter();
+    for x in 0..SPARSE_CAPACITY {
+        counter_0.add(x);
+        counter_1.add(x + SPARSE_CAPACITY / 2);
+        dense_counter.add(x);
+        dense_counter.add(x + SPARSE_CAPACITY / 2);
+    }
+    counter_0.merge(counter_1.as_ref());
+    assert_eq!(counter_0.count(), dense_counter.count());
+    assert_eq!(counter_1.count(), SPARSE_CAPACITY as f64);
+
+    Ok(())
+}
+
+#[test]
+fn test_sparse_merge_dense() -> Result<()> {
+    let builder = HyperLogLogBuilder::new(1000)
+        .word_type::<u64>()
+        .log_2_num_reg(8)
+        .build_hasher(Xxh3Builder::new().with_seed(0));
+    let dense = builder.clone().build()?;
+    let sparse = builder.sparse(true).build()?;
+
+    let mut small = sparse.new_counter();
+    let mut large = sparse.new_counter();
+    let mut dense_counter = dense.new_counter();
+    for x in 0..10 {
+        small.add(x);
+        dense_counter.add(x);
+    }
+    for x in 5..1000 {
+        large.add(x);
+        dense_counter.add(x);
+    }
+
+    // Sparse into dense
+    let mut merged = sparse.new_counter();
+    merged.set(large.as_ref());
+    merged.merge(small.as_ref());
+    assert_eq!(merged.count(), dense_counter.count());
+
+    // Dense into sparse
+    let mut merged = sparse.new_counter();
+    merged.set(small.as_ref());
+    merged.merge(large.as_ref());
+    assert_eq!(merged.count(), dense_counter.count());
+
+    Ok(())
+}
+
+#[test]
+fn test_add_all() -> Result<()> {
+    let sizes = [1, 10, 100, 1000, 100_000];
+    let log2ms = [4, 6, 8, 12];
+
+    for size in sizes {
+        for log2m in log2ms {
+            for sparse in [false, true] {
+                let logic = HyperLogLogBuilder::new(size)
+                    .log_2_num_reg(log2m)
+                    .sparse(sparse)
+                    .build_hasher(Xxh3Builder::new().with_seed(0))
+                    .build()?;
+                let mut counter = logic.new_counter();
+                let mut bulk_counter = logic.new_counter();
+
+                for x in 0..size {
+                    counter.add(x);
+                }
+                bulk_counter.add_all(0..size);
+                assert_eq!(counter.as_ref(), bulk_counter.as_ref());
+
+                // Adding to a nonempty counter
+                for x in size / 2..2 * size {
+                    counter.add(x);
+                }
+                bulk_counter.add_all(size / 2..2 * size);
+                assert_eq!(counter.as_ref(), bulk_counter.as_ref());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_counts() -> Result<()> {
+    let logic = HyperLogLogBuilder::new(1000)
+        .log_2_num_reg(6)
+        .build_hasher(Xxh3Builder::new().with_seed(0))
+        .build()?;
+    let mut counters = SliceCounterArray::new(logic, 100)?;
+    for i in 0..100 {
+        counters.get_counter_mut(i).add_all(0..i * 10);
+    }
+
+    let expected: Vec<f64> = (0..100).map(|i| counters.get_counter(i).count()).collect();
+    assert_eq!(counters.counts(), expected);
+    assert_eq!(counters.par_counts(&threads![]), expected);
+
+    Ok(())
+}
+
+#[test]
+fn test_backend_alignment() -> Result<()> {
+    // With 1000 elements registers have 5 bits, so counters with 16 registers
+    // fit exactly 80 bits: they can be backed by u16, but not by u32 or u64
+    assert_eq!(HyperLogLog::register_size(1000), 5);
+    let builder = HyperLogLogBuilder::new(1000).log_2_num_reg(4);
+    let logic = builder.clone().word_type::<u16>().build()?;
+    let mut counter_0 = logic.new_counter();
+    let mut counter_1 = logic.new_counter();
+    let mut union = logic.new_counter();
+    counter_0.add_all(0..10_usize);
+    counter_1.add_all(5..15_usize);
+    union.add_all(0..15_usize);
+    counter_0.merge(counter_1.as_ref());
+    assert_eq!(counter_0.as_ref(), union.as_ref());
+
+    let err = builder
+        .clone()
+        .word_type::<u32>()
+        .build::<usize>()
+        .unwrap_err();
+    assert!(err.to_string().contains("u16"));
+    assert!(builder.clone().word_type::<u64>().build::<usize>().is_err());
+
+    // With 32 registers counters fit 160 bits
+    let builder = builder.log_2_num_reg(5);
+    assert!(builder.clone().word_type::<u32>().build::<usize>().is_ok());
+    assert!(builder.word_type::<u64>().build::<usize>().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_with_backend() -> Result<()> {
+    let logic = HyperLogLogBuilder::new(1000)
+        .log_2_num_reg(6)
+        .word_type::<u64>()
+        .build_hasher(Xxh3Builder::new().with_seed(0))
+        .build()?;
+    let num_words = 100 * logic.backend_len();
+
+    // A dirty backend allocated by the caller is cleared
+    let backend = MmapSlice::from_value(u64::MAX, num_words, TempMmapOptions::Default)?;
+    let mut counters = SliceCounterArray::with_backend(logic.clone(), 100, backend)?;
+    let mut expected = SliceCounterArray::new(logic.clone(), 100)?;
+    assert_eq!(counters.as_ref(), expected.as_ref());
+
+    for i in 0..100 {
+        counters.get_counter_mut(i).add_all(0..i * 10);
+        expected.get_counter_mut(i).add_all(0..i * 10);
+    }
+    assert_eq!(counters.counts(), expected.counts());
+
+    let shorter = MmapSlice::from_value(0, num_words - 1, TempMmapOptions::Default)?;
+    assert!(SliceCounterArray::with_backend(logic.clone(), 100, shorter).is_err());
+    assert!(SliceCounterArray::with_backend(logic, 100, vec![0; num_words + 1]).is_err());
+
+    Ok(())
+}
+
 #[test]
 fn test_merge_array() -> Result<()> {
     let sizes = [1, 10, 100, 1000, 100_000];
@@ -243,3 +694,476 @@ fn test_merge_array() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_merge_from() -> Result<()> {
+    let logic = HyperLogLogBuilder::new(1000)
+        .log_2_num_reg(6)
+        .build_hasher(Xxh3Builder::new().with_seed(0))
+        .build()?;
+    let mut counters = SliceCounterArray::new(logic.clone(), 100)?;
+    let mut other = SliceCounterArray::new(logic.clone(), 100)?;
+    for i in 0..100 {
+        counters.get_counter_mut(i).add_all(0..i * 10);
+        other.get_counter_mut(i).add_all(i * 5..i * 20);
+    }
+
+    let mut expected = SliceCounterArray::new(logic.clone(), 100)?;
+    for i in 0..100 {
+        let mut counter = expected.get_counter_mut(i);
+        counter.set(counters.get_backend(i));
+        counter.merge(other.get_backend(i));
+    }
+
+    counters.merge_from(&other, &threads![])?;
+    assert_eq!(counters.as_ref(), expected.as_ref());
+
+    // Arrays with different geometries cannot be merged
+    let shorter = SliceCounterArray::new(logic, 99)?;
+    assert!(counters.merge_from(&shorter, &threads![]).is_err());
+    let different = SliceCounterArray::new(
+        HyperLogLogBuilder::new(1000)
+            .log_2_num_reg(7)
+            .build_hasher(Xxh3Builder::new().with_seed(0))
+            .build()?,
+        100,
+    )?;
+    assert!(counters.merge_from(&different, &threads![]).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_clear_counter() -> Result<()> {
+    // 16 5-bit registers per counter: counters are 5 words long
+    let logic = HyperLogLogBuilder::new(1000)
+        .word_type::<u16>()
+        .log_2_num_reg(4)
+        .build_hasher(Xxh3Builder::new().with_seed(0))
+        .build()?;
+    let mut counters = SliceCounterArray::new(logic, 10)?;
+    for i in 0..10 {
+        counters.get_counter_mut(i).add_all(i * 100..(i + 1) * 100);
+    }
+    let backends: Vec<Vec<u16>> = (0..10).map(|i| counters.get_backend(i).to_vec()).collect();
+
+    counters.clear_counter(4);
+    for (i, backend) in backends.iter().enumerate() {
+        if i == 4 {
+            assert_eq!(counters.get_counter(i).count(), 0.0);
+        } else {
+            assert_eq!(counters.get_backend(i), backend.as_slice());
+        }
+    }
+
+    counters.get_counter_mut(4).add_all(400..500);
+    assert_eq!(counters.get_backend(4), backends[4].as_slice());
+    let sync_counters = counters.as_sync_array();
+    unsafe { sync_counters.clear_counter(4) };
+    for (i, backend) in backends.iter().enumerate() {
+        if i == 4 {
+            assert_eq!(counters.get_counter(i).count(), 0.0);
+        } else {
+            assert_eq!(counters.get_backend(i), backend.as_slice());
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_estimate_with_ci() -> Result<()> {
+    let sizes = [1, 10, 100, 1000, 100_000];
+    let log2ms = [4, 6, 8, 12];
+
+    for size in sizes {
+        for log2m in log2ms {
+            let mut correct = 0;
+
+            for trial in 0..NUM_TRIALS {
+                let logic = HyperLogLogBuilder::new(size)
+                    .word_type::<u16>()
+                    .log_2_num_reg(log2m)
+                    .build_hasher(Xxh3Builder::new().with_seed(trial))
+                    .build()?;
+                let mut counters = SliceCounterArray::new(logic, 1)?;
+                let incr = (1 << 32) / size as i64;
+                let mut x = i64::MIN;
+                for _ in 0..size {
+                    counters.get_counter_mut(0).add(x);
+                    x += incr;
+                }
+
+                let (estimate, lower, upper) = counters.estimate_with_ci(0, 2.0);
+                assert_eq!(estimate, counters.get_counter(0).count());
+                assert!(lower <= estimate && estimate <= upper);
+                if (lower..=upper).contains(&(size as f64)) {
+                    correct += 1;
+                }
+            }
+
+            assert!(
+                correct >= REQUIRED_TRIALS,
+                "assertion failed for size {} and log2m {}: correct = {} < {}",
+                size,
+                log2m,
+                correct,
+                REQUIRED_TRIALS
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_estimate_with_ci_exact() -> Result<()> {
+    for builder in [
+        HyperLogLogBuilder::new(1000).count_exact_below(10),
+        HyperLogLogBuilder::new(1000).sparse(true),
+    ] {
+        let logic = builder.log_2_num_reg(6).build()?;
+        let mut counters = SliceCounterArray::new(logic, 2)?;
+        counters.get_counter_mut(0).add_all(0..5);
+        counters.get_counter_mut(1).add_all(0..1000);
+
+        // Exact counts have a zero-width interval
+        assert_eq!(counters.estimate_with_ci(0, 2.0), (5.0, 5.0, 5.0));
+        let (estimate, lower, upper) = counters.estimate_with_ci(1, 2.0);
+        assert!(lower < estimate && estimate < upper);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_into_owned() -> Result<()> {
+    let logic = HyperLogLogBuilder::new(1000)
+        .log_2_num_reg(6)
+        .build_hasher(Xxh3Builder::new().with_seed(0))
+        .build()?;
+    let mut counters = SliceCounterArray::new(logic, 10)?;
+    for i in 0..10 {
+        counters.get_counter_mut(i).add_all(0..i * 100);
+    }
+
+    let counts: Vec<f64> = (0..10).map(|i| counters.get_counter(i).count()).collect();
+    let owned: Vec<_> = (0..10)
+        .map(|i| counters.get_counter(i).into_owned())
+        .collect();
+    drop(counters);
+
+    // Owned counters can be sent to other threads
+    let owned_counts = std::thread::spawn(move || {
+        owned
+            .iter()
+            .map(|counter| counter.count())
+            .collect::<Vec<_>>()
+    })
+    .join()
+    .unwrap();
+    assert_eq!(owned_counts, counts);
+
+    Ok(())
+}
+
+#[test]
+fn test_store_load_mmap() -> Result<()> {
+    let num_counters = 100;
+    let logic = HyperLogLogBuilder::new(1000).log_2_num_reg(6).build()?;
+    let mut counters = SliceCounterArray::new(logic, num_counters)?;
+    for i in 0..num_counters {
+        let mut counter = counters.get_counter_mut(i);
+        for x in 0..i * 10 {
+            counter.add(x);
+        }
+    }
+
+    let tmp_dir = tempfile::tempdir()?;
+    let basename = tmp_dir.path().join("counters");
+    counters.store(&basename)?;
+
+    let loaded = SliceCounterArray::<
+        HyperLogLog<usize, BuildHasherDefault<DefaultHasher>, usize>,
+        usize,
+        MmapHelper<usize>,
+    >::load_mmap(&basename, MmapFlags::empty())?;
+
+    assert_eq!(loaded.len(), counters.len());
+    assert_eq!(loaded.as_ref(), counters.as_ref());
+    for i in 0..num_counters {
+        assert_eq!(
+            loaded.get_counter(i).count(),
+            counters.get_counter(i).count()
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_load_mmap_invalid_properties() -> Result<()> {
+    let logic = HyperLogLogBuilder::new(1000).log_2_num_reg(6).build()?;
+    let counters = SliceCounterArray::new(logic, 10)?;
+
+    let tmp_dir = tempfile::tempdir()?;
+    let basename = tmp_dir.path().join("counters");
+    counters.store(&basename)?;
+    let properties_path = tmp_dir.path().join("counters.properties");
+    let properties = std::fs::read_to_string(&properties_path)?;
+
+    for (key, value) in [
+        ("registersize", "0"),
+        ("registersize", "100"),
+        ("log2numregisters", "100"),
+        ("wordbits", "8"),
+        (
+            "endianness",
+            if cfg!(target_endian = "little") {
+                "big"
+            } else {
+                "little"
+            },
+        ),
+    ] {
+        let corrupted: String = properties
+            .lines()
+            .map(|line| match line.split_once('=') {
+                Some((k, _)) if k == key => format!("{}={}\n", key, value),
+                _ => format!("{}\n", line),
+            })
+            .collect();
+        std::fs::write(&properties_path, corrupted)?;
+        assert!(SliceCounterArray::<
+            HyperLogLog<usize, BuildHasherDefault<DefaultHasher>, usize>,
+            usize,
+            MmapHelper<usize>,
+        >::load_mmap(&basename, MmapFlags::empty())
+        .is_err());
+    }
+
+    // Every property is required
+    for key in ["sparse", "exactcapacity", "biascorrection", "endianness"] {
+        let missing: String = properties
+            .lines()
+            .filter(|line| !line.starts_with(&format!("{}=", key)))
+            .map(|line| format!("{}\n", line))
+            .collect();
+        std::fs::write(&properties_path, missing)?;
+        assert!(SliceCounterArray::<
+            HyperLogLog<usize, BuildHasherDefault<DefaultHasher>, usize>,
+            usize,
+            MmapHelper<usize>,
+        >::load_mmap(&basename, MmapFlags::empty())
+        .is_err());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_register_bytes() -> Result<()> {
+    let num_counters = 100;
+    let logic = HyperLogLogBuilder::new(1000).log_2_num_reg(6).build()?;
+    let mut counters = SliceCounterArray::new(logic.clone(), num_counters)?;
+    for i in 0..num_counters {
+        let mut counter = counters.get_counter_mut(i);
+        for x in 0..i * 10 {
+            counter.add(x);
+        }
+    }
+
+    let mut bytes = Vec::new();
+    for i in 0..num_counters {
+        let counter_bytes = logic.register_bytes(counters.get_backend(i));
+        assert_eq!(counter_bytes.len(), 1 << 6);
+        bytes.extend(counter_bytes);
+    }
+
+    let rebuilt = SliceCounterArray::from_register_bytes(logic.clone(), &bytes)?;
+
+    assert_eq!(rebuilt.len(), counters.len());
+    assert_eq!(rebuilt.as_ref(), counters.as_ref());
+    for i in 0..num_counters {
+        assert_eq!(
+            rebuilt.get_counter(i).count(),
+            counters.get_counter(i).count()
+        );
+    }
+
+    // Wrong number of bytes
+    assert!(SliceCounterArray::from_register_bytes(logic.clone(), &bytes[1..]).is_err());
+    // Values not fitting in a register
+    let mut invalid = bytes.clone();
+    invalid[0] = u8::MAX;
+    assert!(SliceCounterArray::from_register_bytes(logic, &invalid).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_register_bytes_sparse() -> Result<()> {
+    let sparse = HyperLogLogBuilder::new(1000)
+        .log_2_num_reg(6)
+        .sparse(true)
+        .build()?;
+    let dense = HyperLogLogBuilder::new(1000).log_2_num_reg(6).build()?;
+
+    for size in [0, 3, 1000] {
+        let mut sparse_counter = sparse.new_counter();
+        let mut dense_counter = dense.new_counter();
+        for x in 0..size {
+            sparse_counter.add(x);
+            dense_counter.add(x);
+        }
+        // Sparse counters export the registers they would have if dense
+        assert_eq!(
+            sparse.register_bytes(sparse_counter.as_ref()),
+            dense.register_bytes(dense_counter.as_ref())
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_compatible_with() -> Result<()> {
+    let logic = HyperLogLogBuilder::new(1000).log_2_num_reg(6).build()?;
+    let mut counters = SliceCounterArray::new(logic.clone(), 10)?;
+    for i in 0..10 {
+        counters.get_counter_mut(i).add_all(0..i * 10);
+    }
+    let backend = counters.as_ref().to_vec();
+
+    // The number of counters does not matter
+    assert!(counters.compatible_with(&SliceCounterArray::new(logic, 5)?));
+
+    let incompatible = [
+        HyperLogLogBuilder::new(1000).log_2_num_reg(7).build()?,
+        HyperLogLogBuilder::new(1000)
+            .log_2_num_reg(6)
+            .sparse(true)
+            .build()?,
+    ];
+    for logic in incompatible {
+        let other = SliceCounterArray::new(logic, 10)?;
+        assert!(!counters.compatible_with(&other));
+        assert!(!other.compatible_with(&counters));
+        // Merging returns an error and leaves the array untouched
+        assert!(counters.merge_from(&other, &threads![]).is_err());
+        assert_eq!(counters.as_ref(), backend.as_slice());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_zero_elements() -> Result<()> {
+    assert!(HyperLogLogBuilder::new(0).build::<usize>().is_err());
+    assert!(HyperLogLogBuilder::new(1000)
+        .num_elements(0)
+        .build::<usize>()
+        .is_err());
+    assert!(HyperLogLogBuilder::new(0)
+        .num_elements(1)
+        .build::<usize>()
+        .is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_register_histogram() -> Result<()> {
+    // 16 5-bit registers per counter: registers cross word boundaries
+    let logic = HyperLogLogBuilder::new(1000)
+        .word_type::<u16>()
+        .log_2_num_reg(4)
+        .build_hasher(Xxh3Builder::new().with_seed(0))
+        .build()?;
+    let register_size = HyperLogLog::register_size(1000);
+    let mut counters = SliceCounterArray::new(logic.clone(), 2)?;
+
+    counters.get_counter_mut(0).add_all(0..100_000);
+    counters.clear_counter(0);
+    let histogram = logic.register_histogram(counters.get_backend(0));
+    assert_eq!(histogram.len(), 1 << register_size);
+    assert_eq!(histogram[0], 16);
+    assert!(histogram[1..].iter().all(|&count| count == 0));
+
+    counters.get_counter_mut(1).add_all(0..100_000);
+    let histogram = logic.register_histogram(counters.get_backend(1));
+    assert_eq!(histogram.len(), 1 << register_size);
+    assert_eq!(histogram.iter().sum::<usize>(), 16);
+    assert_eq!(histogram[0], 0);
+    assert!(histogram.iter().filter(|&&count| count > 0).count() > 1);
+
+    // Sparse counters have the same histogram as their dense equivalent
+    let sparse = HyperLogLogBuilder::new(1000)
+        .word_type::<u16>()
+        .log_2_num_reg(4)
+        .build_hasher(Xxh3Builder::new().with_seed(0))
+        .sparse(true)
+        .build()?;
+    let mut sparse_counters = SliceCounterArray::new(sparse.clone(), 1)?;
+    let mut dense_counters = SliceCounterArray::new(logic.clone(), 1)?;
+    sparse_counters.get_counter_mut(0).add_all(0..3);
+    dense_counters.get_counter_mut(0).add_all(0..3);
+    assert_eq!(
+        sparse.register_histogram(sparse_counters.get_backend(0)),
+        logic.register_histogram(dense_counters.get_backend(0))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_count_exact_below() -> Result<()> {
+    let logic = HyperLogLogBuilder::new(1000)
+        .log_2_num_reg(6)
+        .count_exact_below(4)
+        .build()?;
+    let mut counters = SliceCounterArray::new(logic, 300)?;
+    for i in 0..300 {
+        let mut counter = counters.get_counter_mut(i);
+        let num_elements = i % 3 + 1;
+        for x in 0..num_elements {
+            counter.add(i * 10 + x);
+            // Duplicates do not change the count
+            counter.add(i * 10 + x);
+        }
+    }
+    for i in 0..300 {
+        assert_eq!(counters.get_counter(i).count(), (i % 3 + 1) as f64);
+    }
+
+    // The exact store does not use the space of the registers, which could
+    // store a single hash
+    let logic = HyperLogLogBuilder::new(1000)
+        .word_type::<u16>()
+        .log_2_num_reg(4)
+        .count_exact_below(4)
+        .build::<usize>()?;
+    let mut counter = logic.new_counter();
+    counter.add_all(0..3);
+    assert_eq!(counter.count(), 3.0);
+    // Merges keep counts exact
+    let mut other = logic.new_counter();
+    other.add_all([2]);
+    counter.merge(other.as_ref());
+    assert_eq!(counter.count(), 3.0);
+    // The registers are updated even while counts are exact, so the
+    // estimate is used as soon as the store is full
+    let mut estimate = logic.new_counter();
+    estimate.add_all(0..100);
+    counter.add_all(3..100);
+    assert_eq!(counter.count(), estimate.count());
+
+    // The header of the exact store must be able to represent its size
+    assert!(HyperLogLogBuilder::new(1000)
+        .word_type::<u16>()
+        .count_exact_below(1 << 16)
+        .build::<usize>()
+        .is_err());
+
+    Ok(())
+}
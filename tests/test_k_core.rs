@@ -0,0 +1,27 @@
+use dsi_progress_logger::prelude::*;
+use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
+use webgraph_algo::algo::k_core;
+
+#[test]
+fn test_clique_with_pendant_path() {
+    // A clique on 0, 1, 2, 3, a path 3 -> 4 -> 5, and an isolated node 6
+    let arcs = [
+        (0, 1),
+        (0, 2),
+        (0, 3),
+        (1, 2),
+        (1, 3),
+        (2, 3),
+        (3, 4),
+        (4, 5),
+        (5, 5),
+    ];
+    let mut graph = VecGraph::from_arc_list(arcs);
+    graph.add_node(6);
+    let mut transpose = VecGraph::from_arc_list(arcs.map(|(u, v)| (v, u)));
+    transpose.add_node(6);
+
+    let cores = k_core::core_numbers(Left(graph), Left(transpose), no_logging![]);
+
+    assert_eq!(cores, vec![3, 3, 3, 3, 1, 1, 0]);
+}
@@ -125,3 +125,117 @@ mod test_argmin {
         assert_eq!(argmin_filtered(&v, &t, |_, e| e > 1), Some(1));
     }
 }
+
+mod test_top_k {
+    use super::*;
+
+    #[test]
+    fn test_empty() {
+        let v: Vec<usize> = Vec::new();
+        assert_eq!(top_k(&v, 3), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_zero() {
+        let v = vec![2, 1, 5, 3];
+        assert_eq!(top_k(&v, 0), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_normal() {
+        let v = vec![2, 1, 5, 3, 4];
+        assert_eq!(top_k(&v, 3), vec![2, 4, 3]);
+    }
+
+    #[test]
+    fn test_k_larger_than_len() {
+        let v = vec![2.0, 1.0, 5.0];
+        assert_eq!(top_k(&v, 10), vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let v = vec![3, 5, 1, 5, 3, 5, 3];
+        assert_eq!(top_k(&v, 2), vec![1, 3]);
+        assert_eq!(top_k(&v, 4), vec![1, 3, 5, 0]);
+        assert_eq!(top_k(&v, 7), vec![1, 3, 5, 0, 4, 6, 2]);
+    }
+
+    #[test]
+    fn test_against_sort() {
+        let v: Vec<usize> = (0..1000).map(|i| (i * 7919) % 101).collect();
+        let mut sorted: Vec<usize> = (0..v.len()).collect();
+        sorted.sort_by(|&a, &b| v[b].cmp(&v[a]).then(a.cmp(&b)));
+        for k in [1, 10, 100, 1000] {
+            assert_eq!(top_k(&v, k), sorted[..k]);
+        }
+    }
+}
+
+mod test_total {
+    use super::*;
+
+    #[test]
+    fn test_argmax_nan() {
+        let v = vec![f64::NAN, 2.0, f64::NAN, 7.0, 1.0, f64::NAN, 7.0, f64::NAN];
+        assert_eq!(argmax_total(&v), Some(3));
+        let v = vec![3.0_f32, f32::NAN, -1.0];
+        assert_eq!(argmax_total(&v), Some(0));
+    }
+
+    #[test]
+    fn test_argmin_nan() {
+        let v = vec![f64::NAN, 2.0, f64::NAN, -7.0, 1.0, f64::NAN, -7.0, f64::NAN];
+        assert_eq!(argmin_total(&v), Some(3));
+        let v = vec![f32::NAN, 3.0_f32, f32::NAN, -1.0];
+        assert_eq!(argmin_total(&v), Some(3));
+    }
+
+    #[test]
+    fn test_all_nan_or_empty() {
+        let v = vec![f64::NAN; 5];
+        assert_eq!(argmax_total(&v), None);
+        assert_eq!(argmin_total(&v), None);
+        let v: Vec<f64> = Vec::new();
+        assert_eq!(argmax_total(&v), None);
+        assert_eq!(argmin_total(&v), None);
+    }
+
+    #[test]
+    fn test_infinities() {
+        let v = vec![f64::NAN, f64::NEG_INFINITY, 0.0, f64::INFINITY];
+        assert_eq!(argmax_total(&v), Some(3));
+        assert_eq!(argmin_total(&v), Some(1));
+    }
+}
+
+mod test_par_argmax {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use webgraph_algo::threads;
+
+    #[test]
+    fn test_agrees_with_sequential() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for len in [0, 1, 10, 1000, 100_000] {
+            // Few distinct values, so that ties are frequent
+            let v: Vec<usize> = (0..len).map(|_| rng.gen_range(0..10)).collect();
+            let t: Vec<usize> = (0..len).map(|_| rng.gen_range(0..10)).collect();
+            for num_threads in [1, 2, 7] {
+                let thread_pool = threads![num_threads];
+                assert_eq!(
+                    par_argmax_filtered(&v, &t, |_, _| true, &thread_pool),
+                    argmax_filtered(&v, &t, |_, _| true)
+                );
+                assert_eq!(
+                    par_argmax_filtered(&v, &t, |i, e| i % 3 != 0 && e < 9, &thread_pool),
+                    argmax_filtered(&v, &t, |i, e| i % 3 != 0 && e < 9)
+                );
+                assert_eq!(
+                    par_argmax_filtered(&v, &t, |_, _| false, &thread_pool),
+                    None
+                );
+            }
+        }
+    }
+}
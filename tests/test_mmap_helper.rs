@@ -1,5 +1,7 @@
 use anyhow::Result;
-use webgraph_algo::utils::{MmapFlags, MmapSlice, TempMmapOptions};
+use rayon::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use webgraph_algo::utils::{MmapFlags, MmapHelper, MmapSlice, TempMmapOptions};
 
 #[test]
 fn test_from_vec_in_memory() -> Result<()> {
@@ -106,3 +108,165 @@ fn test_slice_tempfile() -> Result<()> {
 
     Ok(())
 }
+
+fn check_resize(options: TempMmapOptions) -> Result<()> {
+    let mut v: Vec<usize> = (0..100).collect();
+    let mut mmap_slice = MmapSlice::from_vec(v.clone(), options)?;
+
+    // Growth across several pages
+    mmap_slice.resize(10_000, 42)?;
+    v.resize(10_000, 42);
+    assert_eq!(mmap_slice.as_slice(), v.as_slice());
+
+    mmap_slice[9_999] = 7;
+    v[9_999] = 7;
+
+    // Shrink
+    mmap_slice.resize(50, 42)?;
+    v.truncate(50);
+    assert_eq!(mmap_slice.as_slice(), v.as_slice());
+
+    // Shrink to empty and grow again
+    mmap_slice.resize(0, 42)?;
+    assert!(mmap_slice.is_empty());
+    mmap_slice.resize(10, 1)?;
+    assert_eq!(mmap_slice.as_slice(), vec![1; 10].as_slice());
+
+    Ok(())
+}
+
+#[test]
+fn test_resize_in_memory() -> Result<()> {
+    check_resize(TempMmapOptions::Default)
+}
+
+#[test]
+fn test_resize_tempfile() -> Result<()> {
+    check_resize(TempMmapOptions::TempDir(MmapFlags::empty()))?;
+    check_resize(TempMmapOptions::TempDir(MmapFlags::SHARED))
+}
+
+#[test]
+fn test_persistent_file() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("slice");
+    let v: Vec<usize> = (0..1000).collect();
+
+    {
+        let mut mmap_slice = MmapSlice::from_value(
+            0,
+            1000,
+            TempMmapOptions::PersistentFile(path.clone(), MmapFlags::SHARED),
+        )?;
+        mmap_slice.copy_from_slice(&v);
+    }
+
+    // The file survives the slice
+    let mmap = MmapHelper::<usize>::mmap(&path, MmapFlags::empty())?;
+    assert_eq!(mmap.as_ref(), v.as_slice());
+
+    // A new slice on the same path truncates the file
+    let mmap_slice = MmapSlice::from_vec(
+        vec![1_usize; 10],
+        TempMmapOptions::PersistentFile(path.clone(), MmapFlags::SHARED),
+    )?;
+    drop(mmap_slice);
+    let mmap = MmapHelper::<usize>::mmap(&path, MmapFlags::empty())?;
+    assert_eq!(mmap.as_ref(), vec![1; 10].as_slice());
+
+    Ok(())
+}
+
+fn check_par_iter(options: TempMmapOptions) -> Result<()> {
+    let len = 1 << 20;
+    let mut mmap_slice = MmapSlice::<usize>::from_default(len, options)?;
+
+    mmap_slice
+        .par_iter_mut()
+        .enumerate()
+        .for_each(|(i, x)| *x = 3 * i);
+
+    assert!(mmap_slice.iter().enumerate().all(|(i, &x)| x == 3 * i));
+    assert_eq!(
+        mmap_slice.par_iter().map(|&x| x as u64).sum::<u64>(),
+        3 * (len as u64) * (len as u64 - 1) / 2
+    );
+    assert_eq!((&mmap_slice).into_par_iter().count(), len);
+
+    Ok(())
+}
+
+#[test]
+fn test_par_iter_in_memory() -> Result<()> {
+    check_par_iter(TempMmapOptions::Default)
+}
+
+#[test]
+fn test_par_iter_tempfile() -> Result<()> {
+    check_par_iter(TempMmapOptions::TempDir(MmapFlags::empty()))
+}
+
+fn check_checked_access(options: TempMmapOptions) -> Result<()> {
+    let v: Vec<usize> = (0..100).collect();
+    let mut mmap_slice = MmapSlice::from_vec(v, options)?;
+
+    assert_eq!(mmap_slice.get(99), Some(&99));
+    assert_eq!(mmap_slice.get(100), None);
+    *mmap_slice.get_mut(0).unwrap() = 42;
+    assert!(mmap_slice.get_mut(100).is_none());
+
+    assert_eq!(*mmap_slice.try_index(0)?, 42);
+    assert_eq!(*mmap_slice.try_index(99)?, 99);
+    let err = mmap_slice.try_index(100).unwrap_err().to_string();
+    assert!(err.contains("100"));
+
+    Ok(())
+}
+
+#[test]
+fn test_checked_access_in_memory() -> Result<()> {
+    check_checked_access(TempMmapOptions::Default)?;
+    let err = MmapSlice::from_value(0_usize, 10, TempMmapOptions::Default)?
+        .try_index(10)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("in memory"));
+    Ok(())
+}
+
+#[test]
+fn test_checked_access_tempfile() -> Result<()> {
+    check_checked_access(TempMmapOptions::TempDir(MmapFlags::empty()))?;
+    let err = MmapSlice::from_value(0_usize, 10, TempMmapOptions::TempDir(MmapFlags::empty()))?
+        .try_index(10)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("backed by a file"));
+    Ok(())
+}
+
+#[test]
+fn test_fill_atomic() -> Result<()> {
+    let mmap_slice = MmapSlice::<AtomicUsize>::from_closure(
+        || AtomicUsize::new(usize::MAX),
+        1000,
+        TempMmapOptions::Default,
+    )?;
+
+    // Reset concurrently from several threads sharing the slice
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| mmap_slice.fill_atomic(0, Ordering::Relaxed));
+        }
+    });
+    assert!(mmap_slice
+        .par_iter()
+        .all(|x| x.load(Ordering::Relaxed) == 0));
+
+    mmap_slice.fill_atomic(7, Ordering::Relaxed);
+    assert!(mmap_slice
+        .par_iter()
+        .all(|x| x.load(Ordering::Relaxed) == 7));
+
+    Ok(())
+}
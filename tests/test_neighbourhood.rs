@@ -0,0 +1,127 @@
+use anyhow::Result;
+use dsi_progress_logger::prelude::*;
+use sux::prelude::*;
+use webgraph::{
+    labels::Left,
+    prelude::VecGraph,
+    traits::{RandomAccessGraph, SequentialLabeling},
+};
+use webgraph_algo::{
+    prelude::*,
+    threads,
+    utils::{HyperLogLog, HyperLogLogBuilder, SliceCounterArray},
+};
+
+fn simple_graph() -> Left<VecGraph<()>> {
+    Left(VecGraph::from_arc_list([
+        (0, 0),
+        (1, 0),
+        (1, 2),
+        (2, 1),
+        (2, 3),
+        (2, 4),
+        (2, 5),
+        (3, 4),
+        (4, 3),
+        (5, 5),
+        (5, 6),
+        (5, 7),
+        (5, 8),
+        (6, 7),
+        (8, 7),
+    ]))
+}
+
+/// Returns the outdegree cumulative function of a graph.
+fn cumulative_outdegrees(graph: &impl RandomAccessGraph) -> EfSeqDict {
+    let mut builder = EliasFanoBuilder::new(graph.num_nodes() + 1, graph.num_arcs() as usize);
+    let mut cumulative = 0;
+    builder.push(cumulative);
+    for node in 0..graph.num_nodes() {
+        cumulative += graph.outdegree(node);
+        builder.push(cumulative);
+    }
+    builder.build_with_seq_and_dict()
+}
+
+#[test]
+fn test_exact() {
+    let graph = simple_graph();
+    let nf = neighbourhood::exact(&graph, &threads![], no_logging![]);
+
+    // Brute-force count of the pairs at each distance
+    let mut pairs_at_distance = Vec::new();
+    for src in 0..graph.num_nodes() {
+        let mut distance = vec![usize::MAX; graph.num_nodes()];
+        let mut queue = std::collections::VecDeque::from([src]);
+        distance[src] = 0;
+        while let Some(node) = queue.pop_front() {
+            if pairs_at_distance.len() <= distance[node] {
+                pairs_at_distance.resize(distance[node] + 1, 0.0);
+            }
+            pairs_at_distance[distance[node]] += 1.0;
+            for succ in graph.successors(node) {
+                if distance[succ] == usize::MAX {
+                    distance[succ] = distance[node] + 1;
+                    queue.push_back(succ);
+                }
+            }
+        }
+    }
+    let expected: Vec<f64> = pairs_at_distance
+        .iter()
+        .scan(0.0, |sum, &pairs| {
+            *sum += pairs;
+            Some(*sum)
+        })
+        .collect();
+
+    assert_eq!(nf, expected);
+    assert_eq!(nf[0], 9.0);
+
+    for num_threads in [1, 2, 7] {
+        assert_eq!(
+            neighbourhood::exact(&graph, &threads![num_threads], no_logging![]),
+            nf
+        );
+    }
+
+    let empty = Left(VecGraph::new());
+    assert!(neighbourhood::exact(&empty, &threads![], no_logging![]).is_empty());
+}
+
+#[test]
+fn test_hyperball() -> Result<()> {
+    let graph = simple_graph();
+    let cumulative = cumulative_outdegrees(&graph);
+    let exact = neighbourhood::exact(&graph, &threads![], no_logging![]);
+
+    let log2m = 8;
+    let rsd = HyperLogLog::rel_std(log2m);
+    let logic = HyperLogLogBuilder::new(graph.num_nodes())
+        .log_2_num_reg(log2m)
+        .build()?;
+    let mut hyperball = HyperBallBuilder::new(
+        &graph,
+        &cumulative,
+        SliceCounterArray::new(logic.clone(), graph.num_nodes())?,
+        SliceCounterArray::new(logic, graph.num_nodes())?,
+    )
+    .build(no_logging![]);
+    hyperball.run_until_done(&threads![], no_logging![])?;
+    let approximate = hyperball.neighbourhood_function()?;
+
+    assert!(approximate.len() >= exact.len());
+    for (d, &approximate) in approximate.iter().enumerate() {
+        let exact = exact[d.min(exact.len() - 1)];
+        assert!(
+            (approximate - exact).abs() / exact < 3.0 * rsd,
+            "distance {}: approximate value {} too far from exact value {}",
+            d,
+            approximate,
+            exact
+        );
+    }
+
+    Ok(())
+}
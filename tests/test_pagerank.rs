@@ -0,0 +1,47 @@
+use dsi_progress_logger::prelude::*;
+use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
+use webgraph_algo::{algo::pagerank, threads};
+
+#[test]
+fn test_cycle() {
+    let arcs = [(0, 1), (1, 2), (2, 0)];
+    let graph = Left(VecGraph::from_arc_list(arcs));
+    let transpose = Left(VecGraph::from_arc_list(arcs.map(|(u, v)| (v, u))));
+
+    let rank = pagerank::compute(
+        &graph,
+        &transpose,
+        0.85,
+        1e-12,
+        100,
+        &threads![],
+        no_logging![],
+    );
+
+    for r in rank {
+        assert!((r - 1.0 / 3.0).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_dangling_nodes() {
+    // Node 2 is dangling: its rank must be redistributed uniformly, so that
+    // ranks still sum to one
+    let arcs = [(0, 1), (1, 2), (0, 2)];
+    let graph = Left(VecGraph::from_arc_list(arcs));
+    let transpose = Left(VecGraph::from_arc_list(arcs.map(|(u, v)| (v, u))));
+
+    let rank = pagerank::compute(
+        &graph,
+        &transpose,
+        0.85,
+        1e-12,
+        1000,
+        &threads![],
+        no_logging![],
+    );
+
+    assert!((rank.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!(rank[0] < rank[1]);
+    assert!(rank[1] < rank[2]);
+}
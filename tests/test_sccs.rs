@@ -4,11 +4,14 @@ use lender::for_;
 use sux::bit_vec;
 use webgraph::graphs::random::ErdosRenyi;
 use webgraph::prelude::BvGraph;
-use webgraph::traits::RandomAccessGraph;
+use webgraph::traits::{RandomAccessGraph, RandomAccessLabeling};
 use webgraph::transform;
 use webgraph::{graphs::vec_graph::VecGraph, labels::Left, traits::SequentialLabeling};
 use webgraph_algo::traits::StronglyConnectedComponents;
-use webgraph_algo::{algo::sccs, threads};
+use webgraph_algo::{
+    algo::{acyclicity, sccs},
+    threads,
+};
 
 struct MockStronglyConnectedComponent<G: RandomAccessGraph> {
     component: Vec<usize>,
@@ -55,6 +58,45 @@ fn test_compute_sizes() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_components_iter() -> Result<()> {
+    let mock_component = vec![0, 0, 0, 1, 2, 2, 1, 2, 0, 0];
+    let mock_strongly_connected_components = MockStronglyConnectedComponent::mock(
+        mock_component,
+        3,
+        BvGraph::with_basename("tests/graphs/cnr-2000").load()?,
+    );
+
+    assert_eq!(
+        mock_strongly_connected_components
+            .components_iter()
+            .collect::<Vec<_>>(),
+        vec![vec![0, 1, 2, 8, 9], vec![3, 6], vec![4, 5, 7]]
+    );
+
+    // Each node appears exactly once, in the group of its component
+    for n in (10..=100).step_by(30) {
+        let graph = Left(VecGraph::from_lender(ErdosRenyi::new(n, 0.05, 0).iter()));
+        let scc = sccs::tarjan(&graph, no_logging![]);
+        let mut seen = vec![false; n];
+        let mut num_groups = 0;
+        for (component, nodes) in scc.components_iter().enumerate() {
+            assert!(!nodes.is_empty());
+            assert_eq!(nodes, scc.component_nodes(component));
+            for node in nodes {
+                assert_eq!(scc.components()[node], component);
+                assert!(!seen[node]);
+                seen[node] = true;
+            }
+            num_groups += 1;
+        }
+        assert_eq!(num_groups, scc.num_components());
+        assert!(seen.into_iter().all(|s| s));
+    }
+
+    Ok(())
+}
+
 #[test]
 fn test_sort_by_size() -> Result<()> {
     let mock_component = vec![0, 1, 1, 1, 0, 2];
@@ -201,6 +243,10 @@ macro_rules! test_scc_algo {
 
 test_scc_algo!(|g, _, _, pl| sccs::tarjan(g, pl), tarjan);
 test_scc_algo!(|g, t, _, pl| sccs::kosaraju(g, t, pl), kosaraju);
+test_scc_algo!(
+    |g, t, th, pl| sccs::parallel_scc(g, t, th, pl),
+    parallel_scc
+);
 
 #[test]
 fn test_large() -> Result<()> {
@@ -214,6 +260,7 @@ fn test_large() -> Result<()> {
 
     assert_eq!(kosaraju.num_components(), 100977);
     assert_eq!(tarjan.num_components(), 100977);
+    assert!(sccs::same_partition(&kosaraju, &tarjan));
 
     Ok(())
 }
@@ -233,11 +280,31 @@ fn test_er() -> Result<()> {
             let tarjan = sccs::tarjan(&graph, no_logging![]);
 
             assert_eq!(kosaraju.num_components(), tarjan.num_components());
+
+            let parallel = sccs::parallel_scc(&graph, &transpose, &threads![], no_logging![]);
+            assert!(sccs::same_partition(&parallel, &tarjan));
         }
     }
     Ok(())
 }
 
+#[cfg_attr(feature = "slow_tests", test)]
+#[cfg_attr(not(feature = "slow_tests"), allow(dead_code))]
+fn test_parallel_scc_large() -> Result<()> {
+    let basename = "tests/graphs/cnr-2000";
+
+    let graph = BvGraph::with_basename(basename).load()?;
+    let transpose = BvGraph::with_basename(basename.to_string() + "-t").load()?;
+
+    let tarjan = sccs::tarjan(&graph, no_logging![]);
+    let parallel = sccs::parallel_scc(&graph, &transpose, &threads![], no_logging![]);
+
+    assert_eq!(parallel.num_components(), tarjan.num_components());
+    assert!(sccs::same_partition(&parallel, &tarjan));
+
+    Ok(())
+}
+
 #[test]
 fn test_er_symm() -> Result<()> {
     for n in (10..=100).step_by(10) {
@@ -264,3 +331,146 @@ fn test_er_symm() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_largest_component() -> Result<()> {
+    // A component of size 2 and a component of size 3, plus a trivial one
+    let arcs = [(0, 1), (1, 0), (1, 2), (2, 3), (3, 4), (4, 2), (4, 5)];
+    let graph = Left(VecGraph::from_arc_list(arcs));
+    let scc = sccs::tarjan(&graph, no_logging![]);
+
+    let largest = scc.largest_component();
+    assert_eq!(largest, scc.components()[3]);
+    assert_eq!(scc.component_nodes(largest), vec![2, 3, 4]);
+    assert_eq!(scc.component_nodes(scc.components()[0]), vec![0, 1]);
+    assert_eq!(scc.component_nodes(scc.components()[5]), vec![5]);
+
+    Ok(())
+}
+
+#[test]
+fn test_condensation() -> Result<()> {
+    // Three nontrivial components {0, 1, 2}, {3, 4} and {6, 7}, plus the
+    // trivial component {5}, with multiple arcs between components
+    let arcs = [
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (0, 3),
+        (1, 3),
+        (2, 4),
+        (3, 4),
+        (4, 3),
+        (4, 5),
+        (3, 6),
+        (5, 6),
+        (6, 7),
+        (7, 6),
+        (7, 7),
+    ];
+    let graph = Left(VecGraph::from_arc_list(arcs));
+    let scc = sccs::tarjan(&graph, no_logging![]);
+    let condensation = Left(sccs::condensation(&graph, &scc));
+
+    assert_eq!(condensation.num_nodes(), 4);
+    assert_eq!(condensation.num_arcs(), 4);
+    assert!(acyclicity(&condensation, no_logging![]));
+
+    let c = scc.components();
+    let mut arcs = vec![];
+    for_!((src, succ) in condensation.iter() {
+        for dst in succ {
+            arcs.push((src, dst));
+        }
+    });
+    arcs.sort();
+    let mut expected = vec![(c[0], c[3]), (c[3], c[5]), (c[3], c[6]), (c[5], c[6])];
+    expected.sort();
+    assert_eq!(arcs, expected);
+
+    Ok(())
+}
+
+#[test]
+fn test_weakly_connected() -> Result<()> {
+    // Two disjoint chains, the first one with an arc in the opposite direction
+    let arcs = [(0, 1), (2, 1), (2, 3), (4, 5), (5, 6)];
+    let transposed_arcs = arcs.iter().map(|(a, b)| (*b, *a)).collect::<Vec<_>>();
+
+    let graph = Left(VecGraph::from_arc_list(arcs));
+    let transposed_graph = Left(VecGraph::from_arc_list(transposed_arcs.clone()));
+
+    let components = sccs::weakly_connected(&graph, &transposed_graph, no_logging![]);
+
+    assert_eq!(components.num_components(), 2);
+    assert_eq!(components.components(), &[0, 0, 0, 0, 1, 1, 1]);
+    assert_eq!(components.compute_sizes(), vec![4, 3].into_boxed_slice());
+
+    // A node with no arcs is a component by itself
+    let mut graph = VecGraph::from_arc_list(arcs);
+    graph.add_node(7);
+    let mut transposed_graph = VecGraph::from_arc_list(transposed_arcs);
+    transposed_graph.add_node(7);
+
+    let components = sccs::weakly_connected(Left(graph), Left(transposed_graph), no_logging![]);
+
+    assert_eq!(components.num_components(), 3);
+    assert_eq!(components.components(), &[0, 0, 0, 0, 1, 1, 1, 2]);
+
+    Ok(())
+}
+
+#[test]
+fn test_same_partition() {
+    let a = sccs::BasicSccs::new(3, vec![0, 1, 0, 2].into_boxed_slice());
+    // Same partition, different numbering
+    let b = sccs::BasicSccs::new(3, vec![2, 0, 2, 1].into_boxed_slice());
+    assert!(sccs::same_partition(&a, &b));
+    assert!(sccs::same_partition(&b, &a));
+    // A coarser partition
+    let c = sccs::BasicSccs::new(2, vec![0, 1, 0, 1].into_boxed_slice());
+    assert!(!sccs::same_partition(&a, &c));
+    // Same number of components, but a different partition
+    let d = sccs::BasicSccs::new(3, vec![0, 0, 1, 2].into_boxed_slice());
+    assert!(!sccs::same_partition(&a, &d));
+    assert!(!sccs::same_partition(&d, &a));
+    // A different number of nodes
+    let e = sccs::BasicSccs::new(3, vec![0, 1, 0].into_boxed_slice());
+    assert!(!sccs::same_partition(&a, &e));
+}
+
+#[test]
+fn test_kosaraju_with_order() -> Result<()> {
+    for d in 1..10 {
+        let graph = Left(VecGraph::from_lender(
+            ErdosRenyi::new(100, (d as f64) / 50.0, 0).iter(),
+        ));
+        let transpose = Left(VecGraph::from_lender(
+            transform::transpose(&graph, 10000)?.iter(),
+        ));
+
+        let kosaraju = sccs::kosaraju(&graph, &transpose, no_logging![]);
+        let order = webgraph_algo::algo::top_sort(&graph, no_logging![]);
+        let with_order = sccs::kosaraju_with_order(&transpose, &order, no_logging![]);
+
+        assert_eq!(with_order.num_components(), kosaraju.num_components());
+        assert_eq!(with_order.components(), kosaraju.components());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_empty_graph() {
+    let graph = Left(VecGraph::<()>::new());
+
+    let tarjan = sccs::tarjan(&graph, no_logging![]);
+    assert_eq!(tarjan.num_components(), 0);
+    assert!(tarjan.components().is_empty());
+
+    let kosaraju = sccs::kosaraju(&graph, &graph, no_logging![]);
+    assert_eq!(kosaraju.num_components(), 0);
+    assert!(kosaraju.components().is_empty());
+
+    assert!(sccs::same_partition(&tarjan, &kosaraju));
+}
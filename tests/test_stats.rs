@@ -0,0 +1,122 @@
+use webgraph::{labels::Left, prelude::VecGraph};
+use webgraph_algo::{prelude::stats, threads};
+
+#[test]
+fn test_degree_distribution() {
+    let arcs = [(0, 1), (0, 2), (0, 3), (0, 4), (1, 2), (2, 1), (3, 3)];
+    let graph = Left(VecGraph::from_arc_list(arcs));
+    let transpose = Left(VecGraph::from_arc_list(arcs.map(|(u, v)| (v, u))));
+
+    // Outdegrees are 4, 1, 1, 1, 0 and indegrees are 0, 2, 2, 2, 1
+    for num_threads in [1, 3] {
+        let (outdegrees, indegrees) =
+            stats::degree_distribution(&graph, &transpose, &threads![num_threads]);
+        assert_eq!(outdegrees, vec![1, 3, 0, 0, 1]);
+        assert_eq!(indegrees, vec![1, 1, 3]);
+    }
+}
+
+#[test]
+fn test_degree_distribution_empty() {
+    let graph = Left(VecGraph::<()>::new());
+    let (outdegrees, indegrees) = stats::degree_distribution(&graph, &graph, &threads![]);
+    assert!(outdegrees.is_empty());
+    assert!(indegrees.is_empty());
+}
+
+#[test]
+fn test_reciprocity() {
+    // A symmetric graph with a loop
+    let graph = Left(VecGraph::from_arc_list([
+        (0, 1),
+        (1, 0),
+        (1, 2),
+        (2, 1),
+        (2, 2),
+    ]));
+    for num_threads in [1, 3] {
+        assert_eq!(
+            stats::reciprocity(&graph, &graph, &threads![num_threads]),
+            1.0
+        );
+    }
+
+    // A DAG
+    let arcs = [(0, 1), (0, 2), (1, 2), (2, 3)];
+    let graph = Left(VecGraph::from_arc_list(arcs));
+    let transpose = Left(VecGraph::from_arc_list(arcs.map(|(u, v)| (v, u))));
+    assert_eq!(stats::reciprocity(&graph, &transpose, &threads![]), 0.0);
+
+    // Two reciprocal arcs, a loop and a non-reciprocal arc
+    let arcs = [(0, 1), (1, 0), (1, 1), (1, 2)];
+    let graph = Left(VecGraph::from_arc_list(arcs));
+    let transpose = Left(VecGraph::from_arc_list(arcs.map(|(u, v)| (v, u))));
+    assert_eq!(stats::reciprocity(&graph, &transpose, &threads![]), 0.75);
+}
+
+#[test]
+fn test_summary() {
+    // Components {0, 1, 2}, {3, 4}, {5}, {6, 7} and {8}
+    let arcs = [
+        (0, 1),
+        (1, 2),
+        (2, 0),
+        (0, 3),
+        (2, 4),
+        (3, 4),
+        (4, 3),
+        (4, 5),
+        (3, 6),
+        (5, 6),
+        (6, 7),
+        (7, 6),
+        (8, 0),
+        (8, 7),
+    ];
+    let graph = Left(VecGraph::from_arc_list(arcs));
+    let transpose = Left(VecGraph::from_arc_list(arcs.map(|(u, v)| (v, u))));
+
+    // Outdegrees are 2, 1, 2, 2, 2, 1, 1, 1, 2 and indegrees are 2, 1, 1, 2,
+    // 2, 1, 3, 2, 0
+    for num_threads in [1, 3] {
+        assert_eq!(
+            stats::summary(&graph, &transpose, &threads![num_threads]),
+            stats::GraphSummary {
+                num_nodes: 9,
+                num_arcs: 14,
+                density: 14.0 / 72.0,
+                max_out_degree: 2,
+                max_in_degree: 3,
+                num_self_loops: 0,
+                num_sources: 1,
+                num_sinks: 0,
+            }
+        );
+    }
+
+    // Loops, a sink and an isolated node
+    let arcs = [(0, 0), (0, 1), (1, 1), (1, 2)];
+    let mut graph = VecGraph::from_arc_list(arcs);
+    graph.add_node(3);
+    let mut transpose = VecGraph::from_arc_list(arcs.map(|(u, v)| (v, u)));
+    transpose.add_node(3);
+    assert_eq!(
+        stats::summary(Left(graph), Left(transpose), &threads![]),
+        stats::GraphSummary {
+            num_nodes: 4,
+            num_arcs: 4,
+            density: 4.0 / 12.0,
+            max_out_degree: 2,
+            max_in_degree: 2,
+            num_self_loops: 2,
+            num_sources: 1,
+            num_sinks: 2,
+        }
+    );
+
+    // No nodes
+    let graph = Left(VecGraph::<()>::new());
+    let summary = stats::summary(&graph, &graph, &threads![]);
+    assert_eq!(summary.num_nodes, 0);
+    assert_eq!(summary.density, 0.0);
+}
@@ -0,0 +1,59 @@
+use anyhow::Result;
+use dsi_progress_logger::prelude::*;
+use webgraph::{
+    labels::Left,
+    prelude::VecGraph,
+    traits::{RandomAccessGraph, SequentialLabeling},
+};
+use webgraph_algo::utils::{symmetrize, transpose, TempMmapOptions};
+
+/// Returns whether a graph is symmetric.
+fn is_symmetric(graph: &impl RandomAccessGraph) -> bool {
+    (0..graph.num_nodes()).all(|node| {
+        graph
+            .successors(node)
+            .into_iter()
+            .all(|succ| graph.successors(succ).into_iter().any(|s| s == node))
+    })
+}
+
+#[test]
+fn test_directed_triangle() -> Result<()> {
+    let graph = Left(VecGraph::from_arc_list([(0, 1), (1, 2), (2, 0)]));
+    let transpose = transpose::build(&graph, TempMmapOptions::Default, no_logging![])?;
+    let symmetric = symmetrize::build(&graph, &transpose, TempMmapOptions::Default, no_logging![])?;
+
+    assert_eq!(symmetric.num_nodes(), 3);
+    assert_eq!(symmetric.num_arcs(), 6);
+    assert!(is_symmetric(&symmetric));
+    for node in 0..3 {
+        assert_eq!(
+            symmetric.successors(node).collect::<Vec<_>>(),
+            (0..3).filter(|&other| other != node).collect::<Vec<_>>()
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_duplicates_and_loops() -> Result<()> {
+    // Reciprocal arcs must not be duplicated, and loops appear once
+    let graph = Left(VecGraph::from_arc_list([
+        (0, 1),
+        (1, 0),
+        (1, 1),
+        (2, 1),
+        (3, 3),
+    ]));
+    let transpose = transpose::build(&graph, TempMmapOptions::Default, no_logging![])?;
+    let symmetric = symmetrize::build(&graph, &transpose, TempMmapOptions::Default, no_logging![])?;
+
+    assert!(is_symmetric(&symmetric));
+    assert_eq!(symmetric.successors(0).collect::<Vec<_>>(), vec![1]);
+    assert_eq!(symmetric.successors(1).collect::<Vec<_>>(), vec![0, 1, 2]);
+    assert_eq!(symmetric.successors(2).collect::<Vec<_>>(), vec![1]);
+    assert_eq!(symmetric.successors(3).collect::<Vec<_>>(), vec![3]);
+
+    Ok(())
+}
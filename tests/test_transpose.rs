@@ -0,0 +1,73 @@
+use anyhow::Result;
+use dsi_progress_logger::prelude::*;
+use webgraph::{
+    labels::Left,
+    prelude::{BvGraph, VecGraph},
+    traits::{RandomAccessGraph, SequentialLabeling},
+};
+use webgraph_algo::utils::{transpose, MmapFlags, TempMmapOptions};
+
+/// Asserts that `transposed` is the transpose of `graph`, assuming that the
+/// successors of `transposed` are sorted.
+fn assert_transposed(graph: &impl RandomAccessGraph, transposed: &impl RandomAccessGraph) {
+    assert_eq!(graph.num_nodes(), transposed.num_nodes());
+    assert_eq!(graph.num_arcs(), transposed.num_arcs());
+    let mut predecessors = vec![Vec::new(); graph.num_nodes()];
+    for node in 0..graph.num_nodes() {
+        for succ in graph.successors(node) {
+            predecessors[succ].push(node);
+        }
+    }
+    for (node, predecessors) in predecessors.into_iter().enumerate() {
+        assert_eq!(
+            transposed.successors(node).into_iter().collect::<Vec<_>>(),
+            predecessors
+        );
+    }
+}
+
+#[test]
+fn test_build() -> Result<()> {
+    let graph = Left(VecGraph::from_arc_list([
+        (0, 1),
+        (0, 2),
+        (1, 2),
+        (2, 0),
+        (2, 2),
+        (3, 1),
+    ]));
+    let transposed = transpose::build(&graph, TempMmapOptions::Default, no_logging![])?;
+    assert_transposed(&graph, &transposed);
+
+    Ok(())
+}
+
+#[test]
+fn test_build_cnr_2000() -> Result<()> {
+    let basename = "tests/graphs/cnr-2000";
+    let graph = BvGraph::with_basename(basename).load()?;
+    let transposed = transpose::build(&graph, TempMmapOptions::Default, no_logging![])?;
+    assert_transposed(&graph, &transposed);
+
+    // The result must also match the precomputed transpose
+    let expected = BvGraph::with_basename(basename.to_owned() + "-t").load()?;
+    for node in (0..graph.num_nodes()).step_by(1000) {
+        assert!(transposed
+            .successors(node)
+            .into_iter()
+            .eq(expected.successors(node)));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_build_persistent_file() -> Result<()> {
+    let graph = Left(VecGraph::from_arc_list([(0, 1), (1, 2), (2, 0)]));
+    let dir = tempfile::tempdir()?;
+    let options = TempMmapOptions::PersistentFile(dir.path().join("transpose"), MmapFlags::SHARED);
+    // The slices of the transpose cannot share a single file
+    assert!(transpose::build(&graph, options, no_logging![]).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use dsi_progress_logger::prelude::*;
+use webgraph::{graphs::vec_graph::VecGraph, labels::Left};
+use webgraph_algo::{algo::triangles, threads};
+
+#[test]
+fn test_triangle_and_edge() {
+    // A triangle on 0, 1, 2 and an isolated edge between 3 and 4, with a
+    // duplicated arc and a self-loop
+    let mut graph = VecGraph::new();
+    graph.add_node(4);
+    for (u, v) in [(0, 1), (1, 2), (2, 0), (3, 4), (0, 1)] {
+        graph.add_arc(u, v);
+        graph.add_arc(v, u);
+    }
+    graph.add_arc(2, 2);
+    let graph = Left(graph);
+
+    let (global, per_node) = triangles::count(&graph, &threads![], no_logging![]);
+
+    assert_eq!(global, 1);
+    assert_eq!(per_node, vec![1, 1, 1, 0, 0]);
+}
@@ -34,6 +34,8 @@ fn test_path() -> Result<()> {
     assert_eq!(sum_sweep.radius, 1);
     assert_eq!(sum_sweep.radial_vertex, 1);
     assert!(sum_sweep.diameter == 2 || sum_sweep.diameter == 0);
+    assert_eq!(sum_sweep.center.as_ref(), &[1]);
+    assert_eq!(sum_sweep.periphery.as_ref(), &[0, 2]);
 
     Ok(())
 }
@@ -213,13 +215,18 @@ fn test_sparse() -> Result<()> {
 }
 
 #[test]
-#[should_panic(expected = "Trying to build Radius without the radius computed")]
 fn test_empty() {
     let vec_graph: VecGraph<()> = VecGraph::new();
 
     let graph = Left(vec_graph);
 
-    Radius::compute_undirected(&graph, &threads![], no_logging![]);
+    let sum_sweep = Radius::compute_undirected(&graph, &threads![], no_logging![]);
+    assert_eq!(sum_sweep.radius, 0);
+    assert_eq!(sum_sweep.radius_iterations, 0);
+
+    let sum_sweep = Diameter::compute_undirected(&graph, &threads![], no_logging![]);
+    assert_eq!(sum_sweep.diameter, 0);
+    assert_eq!(sum_sweep.diameter_iterations, 0);
 }
 
 #[allow(clippy::needless_range_loop)]
@@ -263,3 +270,39 @@ fn test_er() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_symmetric_visits() -> Result<()> {
+    let mut undirected_iterations = 0;
+    let mut directed_iterations = 0;
+
+    for d in 2..=6 {
+        let er = ErdosRenyi::new(200, (d as f64) / 200.0, 0);
+        let graph = Left(VecGraph::from_lender(
+            transform::simplify_sorted(er, 10000)?.iter(),
+        ));
+
+        let threads = threads![];
+
+        // The graph is passed as its own transpose, so the directed
+        // computation performs backward visits duplicating forward ones
+        let undirected = All::compute_undirected(&graph, &threads, no_logging![]);
+        let directed = AllForward::compute_directed(&graph, &graph, None, &threads, no_logging![]);
+
+        assert_eq!(
+            undirected.eccentricities.as_ref(),
+            directed.forward_eccentricities.as_ref()
+        );
+        assert_eq!(undirected.diameter, directed.diameter);
+        assert_eq!(undirected.radius, directed.radius);
+        assert_eq!(undirected.center, directed.center);
+        assert_eq!(undirected.periphery, directed.periphery);
+
+        undirected_iterations += undirected.iterations;
+        directed_iterations += directed.forward_iterations;
+    }
+
+    assert!(undirected_iterations <= directed_iterations);
+
+    Ok(())
+}